@@ -1,4 +1,3 @@
-use ark_bn254::Fq;
 use ark_ff::{BigInteger, PrimeField};
 use sha3::{Digest, Keccak256};
 use std::marker::PhantomData;
@@ -9,6 +8,12 @@ pub struct Transcript<F: PrimeField> {
     hasher: Keccak256,
 }
 
+impl<F: PrimeField> Default for Transcript<F> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl<F: PrimeField> Transcript<F> {
     pub fn new() -> Self {
         Self {
@@ -21,6 +26,15 @@ impl<F: PrimeField> Transcript<F> {
         self.hasher.update(preimage)
     }
 
+    /// Absorbs `bytes` under `label`, each prefixed with its length so that
+    /// no two label and message pairs hash the same.
+    pub fn append_with_label(&mut self, label: &[u8], bytes: &[u8]) {
+        self.append(&(label.len() as u64).to_le_bytes());
+        self.append(label);
+        self.append(&(bytes.len() as u64).to_le_bytes());
+        self.append(bytes);
+    }
+
     pub fn get_random_challenge(&mut self) -> F {
         let random_challenge = self.hasher.finalize_reset();
 
@@ -28,9 +42,35 @@ impl<F: PrimeField> Transcript<F> {
 
         F::from_le_bytes_mod_order(&random_challenge)
     }
+
+    /// Draws a challenge whose distribution is statistically close to uniform
+    /// over `F`.
+    ///
+    /// The state is squeezed into at least twice the modulus byte length by
+    /// hashing the digest with a counter, and that wide integer is reduced
+    /// mod p. The bias of the reduction is below 2^-(modulus bits), which a
+    /// single 32-byte digest cannot guarantee for fields near 256 bits.
+    pub fn get_uniform_challenge(&mut self) -> F {
+        let num_bytes = 2 * (F::MODULUS_BIT_SIZE as usize).div_ceil(8);
+        let seed = self.hasher.finalize_reset();
+
+        let mut wide_bytes = Vec::with_capacity(num_bytes + 32);
+        let mut counter: u32 = 0;
+        while wide_bytes.len() < num_bytes {
+            let mut block_hasher = Keccak256::new();
+            block_hasher.update(seed);
+            block_hasher.update(counter.to_le_bytes());
+            wide_bytes.extend_from_slice(&block_hasher.finalize());
+            counter += 1;
+        }
+
+        self.append(&seed);
+
+        F::from_le_bytes_mod_order(&wide_bytes[..num_bytes])
+    }
 }
 
-pub fn fq_vec_to_bytes(values: &[Fq]) -> Vec<u8> {
+pub fn fq_vec_to_bytes<F: PrimeField>(values: &[F]) -> Vec<u8> {
     values
         .iter()
         .flat_map(|x| x.into_bigint().to_bytes_le())
@@ -41,6 +81,7 @@ pub fn fq_vec_to_bytes(values: &[Fq]) -> Vec<u8> {
 mod test {
     use super::Transcript;
     use ark_bn254::Fq;
+    use ark_ff::PrimeField;
 
     #[test]
     fn it_hashes() {
@@ -51,4 +92,58 @@ mod test {
 
         dbg!(random_challenge);
     }
+
+    #[test]
+    fn uniform_challenges_are_deterministic() {
+        let mut transcript_1: Transcript<Fq> = Transcript::new();
+        let mut transcript_2: Transcript<Fq> = Transcript::new();
+        transcript_1.append("zero knowledge".as_bytes());
+        transcript_2.append("zero knowledge".as_bytes());
+
+        assert_eq!(
+            transcript_1.get_uniform_challenge(),
+            transcript_2.get_uniform_challenge()
+        );
+        assert_ne!(
+            transcript_1.get_uniform_challenge(),
+            transcript_1.get_uniform_challenge()
+        );
+    }
+
+    #[test]
+    fn labels_are_framed() {
+        let mut transcript_1: Transcript<Fq> = Transcript::new();
+        let mut transcript_2: Transcript<Fq> = Transcript::new();
+        transcript_1.append_with_label(b"layer", b"01");
+        transcript_2.append_with_label(b"layer0", b"1");
+
+        assert_ne!(
+            transcript_1.get_uniform_challenge(),
+            transcript_2.get_uniform_challenge()
+        );
+    }
+
+    #[test]
+    fn uniform_challenges_pass_a_chi_squared_test_on_low_bits() {
+        let num_samples = 4096;
+        let num_buckets = 16;
+
+        let mut transcript: Transcript<Fq> = Transcript::new();
+        let mut counts = vec![0u64; num_buckets];
+
+        for _ in 0..num_samples {
+            let challenge = transcript.get_uniform_challenge();
+            let low_bits = challenge.into_bigint().as_ref()[0] as usize % num_buckets;
+            counts[low_bits] += 1;
+        }
+
+        let expected = (num_samples / num_buckets) as f64;
+        let chi_squared: f64 = counts
+            .iter()
+            .map(|count| (*count as f64 - expected).powi(2) / expected)
+            .sum();
+
+        // The 0.1% critical value of chi-squared with 15 degrees of freedom.
+        assert!(chi_squared < 37.7, "chi-squared statistic {}", chi_squared);
+    }
 }
@@ -0,0 +1,506 @@
+use ark_bn254::Fq;
+use ark_ff::Zero;
+use multilinear_polynomial::multilinear_polynomial_evaluation::{table_len, MultilinearPoly};
+
+use crate::gkr_error::CircuitError;
+
+/// What a gate computes from its wires. `Left` and `Scale` only read the
+/// left wire; their right wire is ignored. `Add3` and `Mul3` have fan-in
+/// three and also read the third wire.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GateOp {
+    Add,
+    Mul,
+    Sub,
+    Left,
+    Scale(Fq),
+    Add3,
+    Mul3,
+}
+
+/// Gates whose outputs share one wiring predicate. Each family contributes
+/// `predicate(b, c)·combine(W(b), W(c))` to its layer's sum-check, where the
+/// predicate's value at a gate is the gate's `coefficient`. In a layer with
+/// fan-in-3 gates the sum-check runs over `(b, c, d)` instead.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GateFamily {
+    Add,
+    Mul,
+    Sub,
+    Left,
+    Add3,
+    Mul3,
+}
+
+/// A gate reading wires `left` and `right` from the layer below it, and
+/// `third` as well if it has fan-in three.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Gate {
+    pub op: GateOp,
+    pub left: usize,
+    pub right: usize,
+    pub third: usize,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Layer {
+    pub gates: Vec<Gate>,
+}
+
+/// A layered arithmetic circuit. Layer 0 produces the outputs, and the gates
+/// of layer `i` read from layer `i + 1`; the last layer reads the inputs.
+///
+/// Every layer is padded to a power of two with gates that output zero, and
+/// `num_outputs` remembers how many outputs are real.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Circuit {
+    layers: Vec<Layer>,
+    num_inputs: usize,
+    num_outputs: usize,
+}
+
+impl Gate {
+    /// A gate of fan-in two. Its `third` wire is zero, which is where a
+    /// fan-in-3 layer's wiring predicates place it.
+    pub fn new(op: GateOp, left: usize, right: usize) -> Self {
+        Self::ternary(op, left, right, 0)
+    }
+
+    pub fn ternary(op: GateOp, left: usize, right: usize, third: usize) -> Self {
+        Self {
+            op,
+            left,
+            right,
+            third,
+        }
+    }
+
+    pub fn wires(&self) -> [usize; 3] {
+        [self.left, self.right, self.third]
+    }
+
+    fn evaluate(&self, values: &[Fq]) -> Fq {
+        let coefficient = self.op.coefficient();
+
+        coefficient
+            * self
+                .op
+                .family()
+                .combine(&self.wires().map(|wire| values[wire]))
+    }
+}
+
+impl GateOp {
+    pub fn family(&self) -> GateFamily {
+        match self {
+            GateOp::Add => GateFamily::Add,
+            GateOp::Mul => GateFamily::Mul,
+            GateOp::Sub => GateFamily::Sub,
+            GateOp::Left | GateOp::Scale(_) => GateFamily::Left,
+            GateOp::Add3 => GateFamily::Add3,
+            GateOp::Mul3 => GateFamily::Mul3,
+        }
+    }
+
+    /// The constant the gate multiplies its family's combination by.
+    pub fn coefficient(&self) -> Fq {
+        match self {
+            GateOp::Scale(constant) => *constant,
+            _ => Fq::from(1),
+        }
+    }
+}
+
+impl GateFamily {
+    pub const ALL: [GateFamily; 6] = [
+        GateFamily::Add,
+        GateFamily::Mul,
+        GateFamily::Sub,
+        GateFamily::Left,
+        GateFamily::Add3,
+        GateFamily::Mul3,
+    ];
+
+    /// The number of wires the family reads.
+    pub fn fan_in(&self) -> usize {
+        match self {
+            GateFamily::Add3 | GateFamily::Mul3 => 3,
+            _ => 2,
+        }
+    }
+
+    /// Whether `combine` reads wire `wire`: a left gate reads only the
+    /// first, the others each of their `fan_in` wires.
+    pub fn reads(&self, wire: usize) -> bool {
+        match self {
+            GateFamily::Left => wire == 0,
+            _ => wire < self.fan_in(),
+        }
+    }
+
+    /// Combines the values of a gate's wires, of which there must be at
+    /// least `fan_in`; any beyond that are ignored.
+    pub fn combine(&self, wires: &[Fq]) -> Fq {
+        match self {
+            GateFamily::Add => wires[0] + wires[1],
+            GateFamily::Mul => wires[0] * wires[1],
+            GateFamily::Sub => wires[0] - wires[1],
+            GateFamily::Left => wires[0],
+            GateFamily::Add3 => wires[0] + wires[1] + wires[2],
+            GateFamily::Mul3 => wires[0] * wires[1] * wires[2],
+        }
+    }
+}
+
+impl Layer {
+    pub fn new(gates: Vec<Gate>) -> Self {
+        Self { gates }
+    }
+
+    /// Appends gates that always output zero until the gate count is a power
+    /// of two. They are wired to nothing, so the wiring MLEs and the outputs
+    /// of the existing gates are unchanged.
+    pub fn pad_to_power_of_two(&mut self) {
+        let padding = Gate::new(GateOp::Scale(Fq::from(0)), 0, 0);
+
+        self.gates
+            .resize(self.gates.len().next_power_of_two(), padding);
+    }
+
+    /// The multilinear extension of `add(a, b, c)`, which is one exactly when
+    /// gate `a` adds wires `b` and `c` of a layer below with `input_size`
+    /// values. `a` takes the leading variables.
+    pub fn add_mle(&self, input_size: usize) -> MultilinearPoly<Fq> {
+        self.wiring_mle(GateFamily::Add, input_size)
+    }
+
+    /// Like `add_mle`, for the multiplication gates.
+    pub fn mul_mle(&self, input_size: usize) -> MultilinearPoly<Fq> {
+        self.wiring_mle(GateFamily::Mul, input_size)
+    }
+
+    /// The multilinear extension of `add3(a, b, c, d)`, which is one exactly
+    /// when gate `a` adds wires `b`, `c` and `d`.
+    pub fn add3_mle(&self, input_size: usize) -> MultilinearPoly<Fq> {
+        self.wiring_mle(GateFamily::Add3, input_size)
+    }
+
+    /// Like `add3_mle`, for the fan-in-3 multiplication gates.
+    pub fn mul3_mle(&self, input_size: usize) -> MultilinearPoly<Fq> {
+        self.wiring_mle(GateFamily::Mul3, input_size)
+    }
+
+    /// The families with at least one gate in this layer, in the order of
+    /// `GateFamily::ALL`.
+    pub fn families(&self) -> Vec<GateFamily> {
+        GateFamily::ALL
+            .into_iter()
+            .filter(|family| {
+                self.gates
+                    .iter()
+                    .any(|gate| gate.op.family() == *family && !gate.op.coefficient().is_zero())
+            })
+            .collect()
+    }
+
+    /// The number of wires every gate of the layer is read through: three if
+    /// any gate has fan-in three, and two otherwise.
+    pub fn fan_in(&self) -> usize {
+        self.gates
+            .iter()
+            .map(|gate| gate.op.family().fan_in())
+            .max()
+            .unwrap_or(2)
+    }
+
+    /// Like `add_mle`, for any family, with each gate's entry set to its
+    /// coefficient. Both sides are padded up to a power of two, and the
+    /// padding gates and wires are never wired to anything. A fan-in-3 layer
+    /// has a predicate over `(a, b, c, d)`, like `add3(a, b, c, d)`.
+    pub fn wiring_mle(&self, family: GateFamily, input_size: usize) -> MultilinearPoly<Fq> {
+        let gate_vars = self.gates.len().next_power_of_two().ilog2() as usize;
+        let wire_vars = self.fan_in() * input_size.next_power_of_two().ilog2() as usize;
+
+        let mut evaluations = vec![Fq::from(0); wire_table_len(gate_vars + wire_vars)];
+
+        for (index, wires, coefficient) in self.wiring_entries(family, input_size) {
+            evaluations[(index << wire_vars) | wires] = coefficient;
+        }
+
+        MultilinearPoly::new(evaluations)
+    }
+
+    /// The nonzero entries of `wiring_mle`: each gate of the family, its
+    /// wires packed into one index the way the predicate orders them, and its
+    /// coefficient.
+    pub fn wiring_entries(&self, family: GateFamily, input_size: usize) -> Vec<(usize, usize, Fq)> {
+        #[cfg(test)]
+        crate::preprocessed::WIRING_BUILDS.with(|builds| builds.set(builds.get() + 1));
+
+        let fan_in = self.fan_in();
+        let input_vars = input_size.next_power_of_two().ilog2() as usize;
+
+        self.gates
+            .iter()
+            .enumerate()
+            .filter(|(_, gate)| gate.op.family() == family && !gate.op.coefficient().is_zero())
+            .map(|(index, gate)| {
+                let wires = gate.wires()[..fan_in]
+                    .iter()
+                    .fold(0, |position, wire| (position << input_vars) | wire);
+
+                (index, wires, gate.op.coefficient())
+            })
+            .collect()
+    }
+}
+
+impl Circuit {
+    pub fn new(layers: Vec<Layer>, num_inputs: usize) -> Result<Self, CircuitError> {
+        if layers.is_empty() {
+            return Err(CircuitError::EmptyCircuit);
+        }
+
+        if !num_inputs.is_power_of_two() {
+            return Err(CircuitError::InputSizeNotPowerOfTwo { size: num_inputs });
+        }
+
+        for (index, layer) in layers.iter().enumerate() {
+            if layer.gates.is_empty() {
+                return Err(CircuitError::EmptyLayer { layer: index });
+            }
+
+            let num_wires = match layers.get(index + 1) {
+                Some(next_layer) => next_layer.gates.len(),
+                None => num_inputs,
+            };
+
+            for (gate_index, gate) in layer.gates.iter().enumerate() {
+                for wire in gate.wires() {
+                    if wire >= num_wires {
+                        return Err(CircuitError::InvalidWire {
+                            layer: index,
+                            gate: gate_index,
+                            wire,
+                        });
+                    }
+                }
+            }
+        }
+
+        let num_outputs = layers[0].gates.len();
+        let mut layers = layers;
+        layers.iter_mut().for_each(Layer::pad_to_power_of_two);
+
+        Ok(Self {
+            layers,
+            num_inputs,
+            num_outputs,
+        })
+    }
+
+    pub fn layers(&self) -> &[Layer] {
+        &self.layers
+    }
+
+    pub fn num_inputs(&self) -> usize {
+        self.num_inputs
+    }
+
+    /// The number of outputs before padding.
+    pub fn num_outputs(&self) -> usize {
+        self.num_outputs
+    }
+
+    /// The number of values layer `layer` reads from the layer below it.
+    pub fn layer_input_size(&self, layer: usize) -> usize {
+        match self.layers.get(layer + 1) {
+            Some(next_layer) => next_layer.gates.len(),
+            None => self.num_inputs,
+        }
+    }
+
+    /// Every layer's output values, indexed like `layers`, so the first entry
+    /// holds the circuit outputs followed by the zeros of the padding gates.
+    pub fn evaluate(&self, inputs: &[Fq]) -> Vec<Vec<Fq>> {
+        if inputs.len() != self.num_inputs {
+            panic!("expected {} inputs, got {}", self.num_inputs, inputs.len());
+        }
+
+        let mut layer_values = vec![Vec::new(); self.layers.len()];
+        let mut current = inputs.to_vec();
+
+        for (index, layer) in self.layers.iter().enumerate().rev() {
+            current = layer
+                .gates
+                .iter()
+                .map(|gate| gate.evaluate(&current))
+                .collect();
+
+            layer_values[index] = current.clone();
+        }
+
+        layer_values
+    }
+
+    /// Evaluates the circuit on every instance, laying each layer's values
+    /// out instance after instance so that the instance index occupies the
+    /// leading variables of the layer MLE.
+    pub fn evaluate_batch(&self, instances: &[Vec<Fq>]) -> Vec<Vec<Fq>> {
+        let instance_values: Vec<_> = instances
+            .iter()
+            .map(|inputs| self.evaluate(inputs))
+            .collect();
+
+        (0..self.layers.len())
+            .map(|layer| {
+                instance_values
+                    .iter()
+                    .flat_map(|values| values[layer].iter().copied())
+                    .collect()
+            })
+            .collect()
+    }
+}
+
+/// The length of a table over `num_vars` wire variables. Panics instead of
+/// wrapping when a layer is too wide for one table.
+pub(crate) fn wire_table_len(num_vars: usize) -> usize {
+    table_len(num_vars).unwrap_or_else(|error| panic!("{}", error))
+}
+
+#[cfg(test)]
+mod test {
+    use ark_bn254::Fq;
+
+    use super::{Circuit, Gate, GateFamily, GateOp, Layer};
+    use crate::gkr_error::CircuitError;
+    use multilinear_polynomial::multilinear_polynomial_evaluation::MultilinearPoly;
+
+    #[test]
+    fn it_evaluates_every_layer() {
+        let circuit = Circuit::new(
+            vec![
+                Layer::new(vec![Gate::new(GateOp::Add, 0, 1)]),
+                Layer::new(vec![
+                    Gate::new(GateOp::Mul, 0, 1),
+                    Gate::new(GateOp::Add, 2, 3),
+                ]),
+            ],
+            4,
+        )
+        .unwrap();
+
+        let values = circuit.evaluate(&[Fq::from(2), Fq::from(3), Fq::from(4), Fq::from(5)]);
+
+        assert_eq!(
+            values,
+            vec![vec![Fq::from(15)], vec![Fq::from(6), Fq::from(9)]]
+        );
+    }
+
+    fn boolean_point(index: usize, num_vars: usize) -> Vec<Fq> {
+        (0..num_vars)
+            .rev()
+            .map(|bit| Fq::from(((index >> bit) & 1) as u64))
+            .collect()
+    }
+
+    /// The wiring MLE must be one exactly at the `(a, b, c)` in `triples`.
+    fn assert_wiring(mle: &MultilinearPoly<Fq>, triples: &[(usize, usize, usize)]) {
+        // Three gates pad to two variables and four input wires take two.
+        assert_eq!(mle.num_vars(), 6);
+
+        for index in 0..1 << mle.num_vars() {
+            let triple = (index >> 4, (index >> 2) & 3, index & 3);
+            let expected = if triples.contains(&triple) { 1 } else { 0 };
+
+            assert_eq!(
+                mle.evaluate(&boolean_point(index, mle.num_vars())),
+                Fq::from(expected)
+            );
+        }
+    }
+
+    #[test]
+    fn it_builds_the_wiring_predicates_of_a_layer() {
+        let layer = Layer::new(vec![
+            Gate::new(GateOp::Add, 0, 1),
+            Gate::new(GateOp::Mul, 2, 3),
+            Gate::new(GateOp::Add, 3, 0),
+        ]);
+
+        assert_wiring(&layer.add_mle(4), &[(0, 0, 1), (2, 3, 0)]);
+        assert_wiring(&layer.mul_mle(4), &[(1, 2, 3)]);
+    }
+
+    #[test]
+    fn it_wires_fan_in_3_gates_over_four_indices() {
+        let layer = Layer::new(vec![
+            Gate::ternary(GateOp::Mul3, 0, 1, 3),
+            Gate::new(GateOp::Add, 2, 1),
+        ]);
+        assert_eq!(layer.fan_in(), 3);
+
+        let mul3 = layer.mul3_mle(4);
+        let add = layer.add_mle(4);
+        assert_eq!(mul3.num_vars(), 7);
+
+        // The fan-in-2 gate reads its third wire as zero.
+        for index in 0..1 << 7 {
+            let point = boolean_point(index, 7);
+            let quadruple = (index >> 6, (index >> 4) & 3, (index >> 2) & 3, index & 3);
+
+            let expected = if quadruple == (0, 0, 1, 3) { 1 } else { 0 };
+            assert_eq!(mul3.evaluate(&point), Fq::from(expected));
+
+            let expected = if quadruple == (1, 2, 1, 0) { 1 } else { 0 };
+            assert_eq!(add.evaluate(&point), Fq::from(expected));
+        }
+
+        let circuit = Circuit::new(vec![layer], 4).unwrap();
+        assert_eq!(
+            circuit.evaluate(&[Fq::from(2), Fq::from(3), Fq::from(4), Fq::from(5)]),
+            vec![vec![Fq::from(30), Fq::from(7)]]
+        );
+    }
+
+    #[test]
+    fn it_pads_a_layer_with_unwired_zero_gates() {
+        let gates = vec![
+            Gate::new(GateOp::Add, 0, 1),
+            Gate::new(GateOp::Mul, 2, 3),
+            Gate::new(GateOp::Add, 3, 0),
+        ];
+        let mut layer = Layer::new(gates.clone());
+        layer.pad_to_power_of_two();
+
+        assert_eq!(layer.gates.len(), 4);
+        assert_eq!(layer.gates[..3], gates);
+        assert_eq!(layer.families(), vec![GateFamily::Add, GateFamily::Mul]);
+        assert_eq!(layer.add_mle(4), Layer::new(gates).add_mle(4));
+    }
+
+    #[test]
+    fn it_rejects_a_wire_outside_the_layer_below() {
+        let result = Circuit::new(
+            vec![
+                Layer::new(vec![Gate::new(GateOp::Add, 0, 2)]),
+                Layer::new(vec![
+                    Gate::new(GateOp::Mul, 0, 1),
+                    Gate::new(GateOp::Add, 2, 3),
+                ]),
+            ],
+            4,
+        );
+
+        assert_eq!(
+            result,
+            Err(CircuitError::InvalidWire {
+                layer: 0,
+                gate: 0,
+                wire: 2
+            })
+        );
+    }
+}
@@ -0,0 +1,304 @@
+use std::collections::HashMap;
+
+use ark_bn254::Fq;
+
+use crate::{
+    circuit::{Circuit, Gate, GateOp, Layer},
+    gkr_error::BuilderError,
+};
+
+/// A wire handed out by a `CircuitBuilder`, either an input or a gate's
+/// output.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Wire(pub usize);
+
+#[derive(Debug, Clone)]
+enum Node {
+    Input(usize),
+    Gate { op: GateOp, left: Wire, right: Wire },
+}
+
+/// Builds a layered `Circuit` from an expression graph. Wires are numbered
+/// in the order they are declared, and `build` places each gate one layer
+/// above the deeper of its operands, relaying shallower operands up through
+/// `Left` gates. The inputs are padded with zero inputs up to a power of
+/// two, which no gate reads.
+#[derive(Debug, Clone, Default)]
+pub struct CircuitBuilder {
+    nodes: Vec<Node>,
+    num_inputs: usize,
+    outputs: Vec<Wire>,
+}
+
+impl CircuitBuilder {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn input(&mut self) -> Wire {
+        self.num_inputs += 1;
+
+        self.push(Node::Input(self.num_inputs - 1))
+    }
+
+    pub fn add(&mut self, left: Wire, right: Wire) -> Wire {
+        self.gate(GateOp::Add, left, right)
+    }
+
+    pub fn mul(&mut self, left: Wire, right: Wire) -> Wire {
+        self.gate(GateOp::Mul, left, right)
+    }
+
+    pub fn sub(&mut self, left: Wire, right: Wire) -> Wire {
+        self.gate(GateOp::Sub, left, right)
+    }
+
+    pub fn scale(&mut self, wire: Wire, constant: Fq) -> Wire {
+        self.gate(GateOp::Scale(constant), wire, wire)
+    }
+
+    /// Marks `wire` as the next circuit output.
+    pub fn output(&mut self, wire: Wire) {
+        self.outputs.push(wire);
+    }
+
+    fn gate(&mut self, op: GateOp, left: Wire, right: Wire) -> Wire {
+        self.push(Node::Gate { op, left, right })
+    }
+
+    fn push(&mut self, node: Node) -> Wire {
+        self.nodes.push(node);
+
+        Wire(self.nodes.len() - 1)
+    }
+
+    /// Layers the gates the outputs depend on. Fails if a gate reads a wire
+    /// that was never declared or one declared at or after the gate itself,
+    /// which would make the graph cyclic.
+    pub fn build(&self) -> Result<Circuit, BuilderError> {
+        if self.outputs.is_empty() {
+            return Err(BuilderError::NoOutputs);
+        }
+
+        for &Wire(wire) in &self.outputs {
+            if wire >= self.nodes.len() {
+                return Err(BuilderError::UndeclaredWire { wire });
+            }
+        }
+
+        let mut depths: Vec<usize> = Vec::with_capacity(self.nodes.len());
+        for (index, node) in self.nodes.iter().enumerate() {
+            let depth = match node {
+                Node::Input(_) => 0,
+                Node::Gate { left, right, .. } => {
+                    for Wire(wire) in [*left, *right] {
+                        if wire >= self.nodes.len() {
+                            return Err(BuilderError::UndeclaredWire { wire });
+                        }
+                        if wire >= index {
+                            return Err(BuilderError::Cycle { gate: index, wire });
+                        }
+                    }
+
+                    1 + depths[left.0].max(depths[right.0])
+                }
+            };
+
+            depths.push(depth);
+        }
+
+        // Even outputs that are inputs need one layer to be relayed through.
+        let num_layers = self
+            .outputs
+            .iter()
+            .map(|wire| depths[wire.0])
+            .max()
+            .unwrap()
+            .max(1);
+
+        let mut layering = Layering {
+            nodes: &self.nodes,
+            depths: &depths,
+            layers: vec![Vec::new(); num_layers],
+            positions: HashMap::new(),
+        };
+
+        // Each output gets its own gate in the top layer, so that the
+        // outputs come out in the order they were marked.
+        let output_gates = self
+            .outputs
+            .iter()
+            .map(|&wire| match &self.nodes[wire.0] {
+                Node::Gate { op, left, right } if depths[wire.0] == num_layers => Gate::new(
+                    *op,
+                    layering.place(*left, num_layers - 1),
+                    layering.place(*right, num_layers - 1),
+                ),
+                _ => {
+                    let relayed = layering.place(wire, num_layers - 1);
+
+                    Gate::new(GateOp::Left, relayed, relayed)
+                }
+            })
+            .collect();
+
+        // `layers[d]` holds the gates at depth `d`, with the inputs at depth
+        // zero and the outputs above the last; the circuit lists its layers
+        // from the outputs down.
+        let mut layers: Vec<Layer> = vec![Layer::new(output_gates)];
+        layers.extend(
+            layering.layers[1..]
+                .iter()
+                .rev()
+                .map(|gates| Layer::new(gates.clone())),
+        );
+
+        Ok(Circuit::new(layers, self.num_inputs.next_power_of_two())?)
+    }
+}
+
+/// The gates placed so far at each depth, and where each wire sits in them.
+struct Layering<'a> {
+    nodes: &'a [Node],
+    depths: &'a [usize],
+    layers: Vec<Vec<Gate>>,
+    positions: HashMap<(Wire, usize), usize>,
+}
+
+impl Layering<'_> {
+    /// The index of `wire`'s value among the values at `depth`, placing its
+    /// gate or a chain of relays there first if needed.
+    fn place(&mut self, wire: Wire, depth: usize) -> usize {
+        if let Some(position) = self.positions.get(&(wire, depth)) {
+            return *position;
+        }
+
+        let gate = match &self.nodes[wire.0] {
+            Node::Input(index) if depth == 0 => return *index,
+            Node::Gate { op, left, right } if self.depths[wire.0] == depth => Gate::new(
+                *op,
+                self.place(*left, depth - 1),
+                self.place(*right, depth - 1),
+            ),
+            _ => {
+                let relayed = self.place(wire, depth - 1);
+
+                Gate::new(GateOp::Left, relayed, relayed)
+            }
+        };
+
+        let gates = &mut self.layers[depth];
+        gates.push(gate);
+        self.positions.insert((wire, depth), gates.len() - 1);
+
+        gates.len() - 1
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use ark_bn254::Fq;
+
+    use super::{CircuitBuilder, Wire};
+    use crate::{
+        circuit::{Gate, GateOp},
+        gkr_error::BuilderError,
+        gkr_protocol::{prove, verify},
+    };
+
+    #[test]
+    fn it_builds_and_proves_an_arithmetic_expression() {
+        // (a + b)·a - 3·(c·d)
+        let mut builder = CircuitBuilder::new();
+        let a = builder.input();
+        let b = builder.input();
+        let c = builder.input();
+        let d = builder.input();
+        let sum = builder.add(a, b);
+        let left = builder.mul(sum, a);
+        let product = builder.mul(c, d);
+        let right = builder.scale(product, Fq::from(3));
+        let result = builder.sub(left, right);
+        builder.output(result);
+        builder.output(sum);
+
+        let circuit = builder.build().unwrap();
+        let inputs = vec![Fq::from(2), Fq::from(7), Fq::from(4), Fq::from(5)];
+
+        let outputs = circuit.evaluate(&inputs)[0][..circuit.num_outputs()].to_vec();
+        assert_eq!(
+            outputs,
+            vec![
+                (inputs[0] + inputs[1]) * inputs[0] - Fq::from(3) * inputs[2] * inputs[3],
+                inputs[0] + inputs[1]
+            ]
+        );
+
+        let proof = prove(&circuit, &inputs);
+        assert_eq!(verify(&circuit, &inputs, &outputs, proof), Ok(()));
+    }
+
+    #[test]
+    fn it_relays_a_wire_that_skips_a_layer() {
+        let mut builder = CircuitBuilder::new();
+        let a = builder.input();
+        let b = builder.input();
+        let c = builder.add(a, b);
+        let d = builder.mul(c, a);
+        builder.output(d);
+
+        let circuit = builder.build().unwrap();
+
+        assert_eq!(circuit.layers().len(), 2);
+        assert_eq!(
+            circuit.layers()[1].gates,
+            vec![Gate::new(GateOp::Add, 0, 1), Gate::new(GateOp::Left, 0, 0)]
+        );
+        assert_eq!(circuit.layers()[0].gates[0], Gate::new(GateOp::Mul, 0, 1));
+
+        let inputs = vec![Fq::from(3), Fq::from(4)];
+        assert_eq!(circuit.evaluate(&inputs)[0][0], Fq::from(21));
+    }
+
+    #[test]
+    fn it_pads_the_inputs_to_a_power_of_two() {
+        let mut builder = CircuitBuilder::new();
+        let a = builder.input();
+        let b = builder.input();
+        let c = builder.input();
+        let sum = builder.add(a, b);
+        let product = builder.mul(sum, c);
+        builder.output(product);
+
+        let circuit = builder.build().unwrap();
+        assert_eq!(circuit.num_inputs(), 4);
+
+        let inputs = vec![Fq::from(2), Fq::from(7), Fq::from(4), Fq::from(0)];
+        let outputs = circuit.evaluate(&inputs)[0][..circuit.num_outputs()].to_vec();
+        assert_eq!(outputs, vec![Fq::from(36)]);
+
+        let proof = prove(&circuit, &inputs);
+        assert_eq!(verify(&circuit, &inputs, &outputs, proof), Ok(()));
+    }
+
+    #[test]
+    fn it_rejects_cycles_and_undeclared_wires() {
+        let mut builder = CircuitBuilder::new();
+        let a = builder.input();
+        let looped = builder.add(a, Wire(1));
+        builder.output(looped);
+        assert_eq!(
+            builder.build().unwrap_err(),
+            BuilderError::Cycle { gate: 1, wire: 1 }
+        );
+
+        let mut builder = CircuitBuilder::new();
+        let a = builder.input();
+        let b = builder.mul(a, Wire(7));
+        builder.output(b);
+        assert_eq!(
+            builder.build().unwrap_err(),
+            BuilderError::UndeclaredWire { wire: 7 }
+        );
+    }
+}
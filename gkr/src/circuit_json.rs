@@ -0,0 +1,266 @@
+use std::str::FromStr;
+
+use ark_bn254::Fq;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    circuit::{Circuit, Gate, GateOp, Layer},
+    gkr_error::CircuitJsonError,
+};
+
+#[derive(Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct CircuitJson {
+    num_inputs: usize,
+    layers: Vec<Vec<GateJson>>,
+}
+
+#[derive(Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct GateJson {
+    op: String,
+    left: usize,
+    right: usize,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    third: Option<usize>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    constant: Option<String>,
+}
+
+impl Circuit {
+    /// Reads a circuit from JSON of the form
+    ///
+    /// ```json
+    /// {
+    ///   "num_inputs": 4,
+    ///   "layers": [
+    ///     [{"op": "add", "left": 0, "right": 1}],
+    ///     [{"op": "mul", "left": 0, "right": 1},
+    ///      {"op": "scale", "left": 2, "right": 2, "constant": "7"}]
+    ///   ]
+    /// }
+    /// ```
+    ///
+    /// with the layers listed from the outputs down, like `Circuit::new`.
+    /// `op` is one of `add`, `mul`, `sub`, `left`, `scale`, `add3` and
+    /// `mul3`; `scale` needs a `constant`, given as a decimal string, and
+    /// the fan-in-3 ops need a `third` wire, which the other ops must leave
+    /// out.
+    pub fn from_json(json: &str) -> Result<Self, CircuitJsonError> {
+        let circuit: CircuitJson = serde_json::from_str(json)
+            .map_err(|error| CircuitJsonError::Json(error.to_string()))?;
+
+        let layers = circuit
+            .layers
+            .into_iter()
+            .enumerate()
+            .map(|(layer, gates)| {
+                let gates = gates
+                    .into_iter()
+                    .enumerate()
+                    .map(|(gate, json)| json.into_gate(layer, gate))
+                    .collect::<Result<_, _>>()?;
+
+                Ok(Layer::new(gates))
+            })
+            .collect::<Result<_, CircuitJsonError>>()?;
+
+        Ok(Circuit::new(layers, circuit.num_inputs)?)
+    }
+
+    /// Writes the circuit in the format `from_json` reads. The padding of
+    /// the output layer is left out, so the number of outputs survives the
+    /// round trip; the padding of the other layers is written as zero gates.
+    pub fn to_json(&self) -> String {
+        let layers = self
+            .layers()
+            .iter()
+            .enumerate()
+            .map(|(index, layer)| {
+                let gates = match index {
+                    0 => &layer.gates[..self.num_outputs()],
+                    _ => &layer.gates[..],
+                };
+
+                gates.iter().map(GateJson::from_gate).collect()
+            })
+            .collect();
+
+        let circuit = CircuitJson {
+            num_inputs: self.num_inputs(),
+            layers,
+        };
+
+        serde_json::to_string(&circuit).expect("a circuit always serializes")
+    }
+}
+
+impl GateJson {
+    fn into_gate(self, layer: usize, gate: usize) -> Result<Gate, CircuitJsonError> {
+        let op = match self.op.as_str() {
+            "add" => GateOp::Add,
+            "mul" => GateOp::Mul,
+            "sub" => GateOp::Sub,
+            "left" => GateOp::Left,
+            "scale" => {
+                let constant = self.constant.ok_or(CircuitJsonError::MissingField {
+                    layer,
+                    gate,
+                    field: "constant",
+                })?;
+
+                GateOp::Scale(
+                    Fq::from_str(&constant)
+                        .map_err(|_| CircuitJsonError::InvalidConstant { layer, gate })?,
+                )
+            }
+            "add3" => GateOp::Add3,
+            "mul3" => GateOp::Mul3,
+            _ => {
+                return Err(CircuitJsonError::UnknownOp {
+                    layer,
+                    gate,
+                    op: self.op,
+                })
+            }
+        };
+
+        let third = match (op.family().fan_in(), self.third) {
+            (3, None) => {
+                return Err(CircuitJsonError::MissingField {
+                    layer,
+                    gate,
+                    field: "third",
+                })
+            }
+            (3, Some(third)) => third,
+            (_, None) => 0,
+            (_, Some(_)) => {
+                return Err(CircuitJsonError::UnexpectedField {
+                    layer,
+                    gate,
+                    field: "third",
+                })
+            }
+        };
+
+        Ok(Gate::ternary(op, self.left, self.right, third))
+    }
+
+    fn from_gate(gate: &Gate) -> Self {
+        let (op, constant) = match gate.op {
+            GateOp::Add => ("add", None),
+            GateOp::Mul => ("mul", None),
+            GateOp::Sub => ("sub", None),
+            GateOp::Left => ("left", None),
+            GateOp::Scale(constant) => ("scale", Some(constant.to_string())),
+            GateOp::Add3 => ("add3", None),
+            GateOp::Mul3 => ("mul3", None),
+        };
+
+        GateJson {
+            op: op.to_string(),
+            left: gate.left,
+            right: gate.right,
+            third: (gate.op.family().fan_in() == 3).then_some(gate.third),
+            constant,
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use ark_bn254::Fq;
+
+    use crate::{
+        circuit::{Circuit, Gate, GateOp},
+        gkr_error::{CircuitError, CircuitJsonError},
+    };
+
+    const CIRCUIT: &str = r#"{
+        "num_inputs": 4,
+        "layers": [
+            [{"op": "add", "left": 0, "right": 1},
+             {"op": "sub", "left": 1, "right": 2},
+             {"op": "mul3", "left": 0, "right": 1, "third": 2}],
+            [{"op": "mul", "left": 0, "right": 1},
+             {"op": "scale", "left": 2, "right": 2, "constant": "7"},
+             {"op": "left", "left": 3, "right": 0}]
+        ]
+    }"#;
+
+    #[test]
+    fn it_reads_and_round_trips_a_circuit() {
+        let circuit = Circuit::from_json(CIRCUIT).unwrap();
+        let inputs = [Fq::from(2), Fq::from(3), Fq::from(4), Fq::from(5)];
+
+        assert_eq!(circuit.num_outputs(), 3);
+        assert_eq!(
+            circuit.layers()[1].gates[1],
+            Gate::new(GateOp::Scale(Fq::from(7)), 2, 2)
+        );
+        assert_eq!(
+            circuit.evaluate(&inputs)[0][..3],
+            [Fq::from(34), Fq::from(23), Fq::from(840)]
+        );
+
+        let decoded = Circuit::from_json(&circuit.to_json()).unwrap();
+        assert_eq!(decoded, circuit);
+        assert_eq!(decoded.evaluate(&inputs), circuit.evaluate(&inputs));
+    }
+
+    #[test]
+    fn it_points_at_an_out_of_range_wire() {
+        let json = CIRCUIT.replace(r#""left": 3, "right": 0"#, r#""left": 4, "right": 0"#);
+
+        assert_eq!(
+            Circuit::from_json(&json),
+            Err(CircuitJsonError::Circuit(CircuitError::InvalidWire {
+                layer: 1,
+                gate: 2,
+                wire: 4
+            }))
+        );
+    }
+
+    #[test]
+    fn it_rejects_a_third_wire_on_a_fan_in_2_op() {
+        let json = CIRCUIT.replace(
+            r#""op": "sub", "left": 1, "right": 2"#,
+            r#""op": "sub", "left": 1, "right": 2, "third": 3"#,
+        );
+
+        assert_eq!(
+            Circuit::from_json(&json),
+            Err(CircuitJsonError::UnexpectedField {
+                layer: 0,
+                gate: 1,
+                field: "third"
+            })
+        );
+    }
+
+    #[test]
+    fn it_rejects_a_constant_that_is_not_a_field_element() {
+        let json = CIRCUIT.replace(r#""constant": "7""#, r#""constant": "seven""#);
+
+        assert_eq!(
+            Circuit::from_json(&json),
+            Err(CircuitJsonError::InvalidConstant { layer: 1, gate: 1 })
+        );
+    }
+
+    #[test]
+    fn it_points_at_an_unknown_op() {
+        let json = CIRCUIT.replace(r#""op": "sub""#, r#""op": "xor""#);
+
+        assert_eq!(
+            Circuit::from_json(&json),
+            Err(CircuitJsonError::UnknownOp {
+                layer: 0,
+                gate: 1,
+                op: "xor".to_string()
+            })
+        );
+    }
+}
@@ -0,0 +1,208 @@
+use ark_bn254::Fq;
+use fiat_shamir::fiat_shamir_transcript::Transcript;
+use multilinear_polynomial::multilinear_polynomial_evaluation::MultilinearPoly;
+
+use crate::{
+    circuit::Circuit,
+    gkr_error::{GkrError, VerificationError},
+    gkr_protocol::{prove_layers, verify_layers, CircuitProof},
+    preprocessed::layer_wiring,
+};
+
+/// Proves one circuit over a power-of-two number of input instances at once.
+///
+/// Each layer MLE ranges over `(k, g)`, the instance index followed by the
+/// gate index, and the sparse wiring predicates are shared by every
+/// instance. A layer's sum-check runs over `(k, b, c)`, or `(k, b, c, d)`
+/// for fan-in-3 gates, with `eq(r_k, k)` selecting the instance point its
+/// claims are on. Fails on any other number of instances, or an instance
+/// with the wrong number of inputs.
+pub fn prove_batch(circuit: &Circuit, instances: &[Vec<Fq>]) -> Result<CircuitProof, GkrError> {
+    if !instances.len().is_power_of_two() {
+        return Err(GkrError::InstanceCountNotPowerOfTwo {
+            count: instances.len(),
+        });
+    }
+
+    for (instance, inputs) in instances.iter().enumerate() {
+        if inputs.len() != circuit.num_inputs() {
+            return Err(GkrError::WrongNumberOfInputs {
+                instance,
+                expected: circuit.num_inputs(),
+                got: inputs.len(),
+            });
+        }
+    }
+
+    let (proof, _) = prove_layers(
+        circuit,
+        &layer_wiring(circuit),
+        &circuit.evaluate_batch(instances),
+        &instances.concat(),
+        instances.len().ilog2() as usize,
+        &mut Transcript::new(),
+    );
+
+    Ok(proof)
+}
+
+/// Checks a `prove_batch` proof against every instance's inputs and outputs.
+pub fn verify_batch(
+    circuit: &Circuit,
+    instance_inputs: &[Vec<Fq>],
+    instance_outputs: &[Vec<Fq>],
+    proof: CircuitProof,
+) -> Result<(), VerificationError> {
+    if instance_outputs.len() != instance_inputs.len() {
+        return Err(VerificationError::WrongNumberOfInstances {
+            expected: instance_inputs.len(),
+            got: instance_outputs.len(),
+        });
+    }
+
+    for inputs in instance_inputs {
+        if inputs.len() != circuit.num_inputs() {
+            return Err(VerificationError::WrongNumberOfInputs {
+                expected: circuit.num_inputs(),
+                got: inputs.len(),
+            });
+        }
+    }
+
+    let input_claim = verify_layers(
+        circuit,
+        &layer_wiring(circuit),
+        instance_outputs,
+        proof.into_layer_messages(),
+        &mut Transcript::new(),
+    )?;
+
+    // The last claim is about the inputs, which the verifier holds.
+    if !input_claim.check_against(&MultilinearPoly::new(instance_inputs.concat())) {
+        return Err(VerificationError::InputClaimMismatch);
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod test {
+    use ark_bn254::Fq;
+
+    use super::{prove_batch, verify_batch};
+    use crate::{
+        circuit::{Circuit, Gate, GateOp, Layer},
+        gkr_error::{GkrError, VerificationError},
+    };
+
+    fn sample_circuit() -> Circuit {
+        Circuit::new(
+            vec![
+                Layer::new(vec![
+                    Gate::new(GateOp::Add, 0, 1),
+                    Gate::new(GateOp::Mul, 0, 1),
+                ]),
+                Layer::new(vec![
+                    Gate::new(GateOp::Mul, 0, 1),
+                    Gate::new(GateOp::Sub, 2, 3),
+                ]),
+            ],
+            4,
+        )
+        .unwrap()
+    }
+
+    fn sample_instances() -> Vec<Vec<Fq>> {
+        (0..8u64)
+            .map(|k| (0..4u64).map(|i| Fq::from(k * 4 + i + 1)).collect())
+            .collect()
+    }
+
+    #[test]
+    fn it_proves_and_verifies_eight_instances() {
+        let circuit = sample_circuit();
+        let instances = sample_instances();
+        let outputs: Vec<_> = instances
+            .iter()
+            .map(|inputs| circuit.evaluate(inputs)[0].clone())
+            .collect();
+
+        let proof = prove_batch(&circuit, &instances).unwrap();
+
+        assert_eq!(verify_batch(&circuit, &instances, &outputs, proof), Ok(()));
+    }
+
+    #[test]
+    fn it_proves_and_verifies_instances_of_a_fan_in_3_circuit() {
+        let circuit = Circuit::new(
+            vec![
+                Layer::new(vec![Gate::new(GateOp::Add, 0, 1)]),
+                Layer::new(vec![
+                    Gate::ternary(GateOp::Mul3, 0, 1, 3),
+                    Gate::ternary(GateOp::Add3, 2, 1, 0),
+                ]),
+            ],
+            4,
+        )
+        .unwrap();
+        let instances = sample_instances();
+        let outputs: Vec<_> = instances
+            .iter()
+            .map(|inputs| circuit.evaluate(inputs)[0][..1].to_vec())
+            .collect();
+
+        let proof = prove_batch(&circuit, &instances).unwrap();
+
+        assert_eq!(verify_batch(&circuit, &instances, &outputs, proof), Ok(()));
+    }
+
+    #[test]
+    fn it_rejects_one_corrupted_instance_output() {
+        let circuit = sample_circuit();
+        let instances = sample_instances();
+        let mut outputs: Vec<_> = instances
+            .iter()
+            .map(|inputs| circuit.evaluate(inputs)[0].clone())
+            .collect();
+        outputs[5][1] += Fq::from(1);
+
+        let proof = prove_batch(&circuit, &instances).unwrap();
+
+        assert!(verify_batch(&circuit, &instances, &outputs, proof).is_err());
+    }
+
+    #[test]
+    fn it_rejects_a_batch_of_three_instances() {
+        let circuit = sample_circuit();
+        let instances = sample_instances()[..3].to_vec();
+        let outputs: Vec<_> = instances
+            .iter()
+            .map(|inputs| circuit.evaluate(inputs)[0].clone())
+            .collect();
+        let proof = prove_batch(&circuit, &sample_instances()[..4]).unwrap();
+
+        assert_eq!(
+            prove_batch(&circuit, &instances).err(),
+            Some(GkrError::InstanceCountNotPowerOfTwo { count: 3 })
+        );
+        assert_eq!(
+            verify_batch(&circuit, &instances, &outputs, proof),
+            Err(VerificationError::InstanceCountNotPowerOfTwo { count: 3 })
+        );
+    }
+
+    #[test]
+    fn it_rejects_an_instance_with_too_few_inputs() {
+        let mut instances = sample_instances();
+        instances[2].pop();
+
+        assert_eq!(
+            prove_batch(&sample_circuit(), &instances).err(),
+            Some(GkrError::WrongNumberOfInputs {
+                instance: 2,
+                expected: 4,
+                got: 3,
+            })
+        );
+    }
+}
@@ -0,0 +1,270 @@
+use std::fmt;
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum CircuitError {
+    EmptyCircuit,
+    EmptyLayer {
+        layer: usize,
+    },
+    InputSizeNotPowerOfTwo {
+        size: usize,
+    },
+    InvalidWire {
+        layer: usize,
+        gate: usize,
+        wire: usize,
+    },
+}
+
+impl fmt::Display for CircuitError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            CircuitError::EmptyCircuit => write!(f, "circuit has no layers"),
+            CircuitError::EmptyLayer { layer } => write!(f, "layer {} has no gates", layer),
+            CircuitError::InputSizeNotPowerOfTwo { size } => {
+                write!(f, "{} inputs is not a power of two", size)
+            }
+            CircuitError::InvalidWire { layer, gate, wire } => write!(
+                f,
+                "gate {} in layer {} reads wire {}, which does not exist",
+                gate, layer, wire
+            ),
+        }
+    }
+}
+
+impl std::error::Error for CircuitError {}
+
+/// Why a `CircuitBuilder` could not produce a circuit.
+#[derive(Debug, Clone, PartialEq)]
+pub enum BuilderError {
+    NoOutputs,
+    UndeclaredWire { wire: usize },
+    Cycle { gate: usize, wire: usize },
+    Circuit(CircuitError),
+}
+
+impl fmt::Display for BuilderError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            BuilderError::NoOutputs => write!(f, "circuit has no outputs"),
+            BuilderError::UndeclaredWire { wire } => {
+                write!(f, "wire {} was never declared", wire)
+            }
+            BuilderError::Cycle { gate, wire } => write!(
+                f,
+                "gate {} reads wire {}, which is not declared before it",
+                gate, wire
+            ),
+            BuilderError::Circuit(error) => write!(f, "{}", error),
+        }
+    }
+}
+
+impl std::error::Error for BuilderError {}
+
+impl From<CircuitError> for BuilderError {
+    fn from(error: CircuitError) -> Self {
+        BuilderError::Circuit(error)
+    }
+}
+
+/// Why `Circuit::from_json` could not read a circuit. Errors in a gate point
+/// at its layer and its index within the layer.
+#[derive(Debug, Clone, PartialEq)]
+pub enum CircuitJsonError {
+    Json(String),
+    UnknownOp {
+        layer: usize,
+        gate: usize,
+        op: String,
+    },
+    MissingField {
+        layer: usize,
+        gate: usize,
+        field: &'static str,
+    },
+    UnexpectedField {
+        layer: usize,
+        gate: usize,
+        field: &'static str,
+    },
+    InvalidConstant {
+        layer: usize,
+        gate: usize,
+    },
+    Circuit(CircuitError),
+}
+
+impl fmt::Display for CircuitJsonError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            CircuitJsonError::Json(message) => write!(f, "invalid circuit JSON: {}", message),
+            CircuitJsonError::UnknownOp { layer, gate, op } => {
+                write!(
+                    f,
+                    "gate {} in layer {} has unknown op {:?}",
+                    gate, layer, op
+                )
+            }
+            CircuitJsonError::MissingField { layer, gate, field } => {
+                write!(f, "gate {} in layer {} is missing {:?}", gate, layer, field)
+            }
+            CircuitJsonError::UnexpectedField { layer, gate, field } => write!(
+                f,
+                "gate {} in layer {} has {:?}, which its op does not use",
+                gate, layer, field
+            ),
+            CircuitJsonError::InvalidConstant { layer, gate } => write!(
+                f,
+                "gate {} in layer {} has a constant that is not a decimal field element",
+                gate, layer
+            ),
+            CircuitJsonError::Circuit(error) => write!(f, "{}", error),
+        }
+    }
+}
+
+impl std::error::Error for CircuitJsonError {}
+
+impl From<CircuitError> for CircuitJsonError {
+    fn from(error: CircuitError) -> Self {
+        CircuitJsonError::Circuit(error)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum VerificationError {
+    WrongNumberOfInputs {
+        expected: usize,
+        got: usize,
+    },
+    WrongNumberOfOutputs {
+        expected: usize,
+        got: usize,
+    },
+    WrongNumberOfLayers {
+        expected: usize,
+        got: usize,
+    },
+    LayerSumCheckFailed {
+        layer: usize,
+    },
+    LayerClaimMismatch {
+        layer: usize,
+    },
+    InputClaimMismatch,
+    WrongNumberOfOpenings {
+        expected: usize,
+        got: usize,
+    },
+    InvalidOpening {
+        index: usize,
+    },
+    InstanceCountNotPowerOfTwo {
+        count: usize,
+    },
+    WrongNumberOfInstances {
+        expected: usize,
+        got: usize,
+    },
+    WrongNumberOfClaims {
+        layer: usize,
+        expected: usize,
+        got: usize,
+    },
+}
+
+impl fmt::Display for VerificationError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            VerificationError::WrongNumberOfInputs { expected, got } => write!(
+                f,
+                "wrong number of inputs: expected {}, got {}",
+                expected, got
+            ),
+            VerificationError::WrongNumberOfOutputs { expected, got } => write!(
+                f,
+                "wrong number of outputs: expected {}, got {}",
+                expected, got
+            ),
+            VerificationError::WrongNumberOfLayers { expected, got } => write!(
+                f,
+                "wrong number of layer proofs: expected {}, got {}",
+                expected, got
+            ),
+            VerificationError::LayerSumCheckFailed { layer } => {
+                write!(f, "sum-check for layer {} failed", layer)
+            }
+            VerificationError::LayerClaimMismatch { layer } => write!(
+                f,
+                "layer {} sum-check does not match its wiring and claimed evaluations",
+                layer
+            ),
+            VerificationError::InputClaimMismatch => {
+                write!(f, "claimed input evaluations do not match the inputs")
+            }
+            VerificationError::WrongNumberOfOpenings { expected, got } => write!(
+                f,
+                "wrong number of input openings: expected {}, got {}",
+                expected, got
+            ),
+            VerificationError::InvalidOpening { index } => {
+                write!(f, "input opening {} does not match the commitment", index)
+            }
+            VerificationError::InstanceCountNotPowerOfTwo { count } => {
+                write!(f, "{} instances is not a power of two", count)
+            }
+            VerificationError::WrongNumberOfInstances { expected, got } => write!(
+                f,
+                "wrong number of output instances: expected {}, got {}",
+                expected, got
+            ),
+            VerificationError::WrongNumberOfClaims {
+                layer,
+                expected,
+                got,
+            } => write!(
+                f,
+                "layer {} has {} claims on the layer below, expected {}",
+                layer, got, expected
+            ),
+        }
+    }
+}
+
+impl std::error::Error for VerificationError {}
+
+/// Why `prove_batch` could not prove a batch of instances.
+#[derive(Debug, Clone, PartialEq)]
+pub enum GkrError {
+    InstanceCountNotPowerOfTwo {
+        count: usize,
+    },
+    WrongNumberOfInputs {
+        instance: usize,
+        expected: usize,
+        got: usize,
+    },
+}
+
+impl fmt::Display for GkrError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            GkrError::InstanceCountNotPowerOfTwo { count } => {
+                write!(f, "{} instances is not a power of two", count)
+            }
+            GkrError::WrongNumberOfInputs {
+                instance,
+                expected,
+                got,
+            } => write!(
+                f,
+                "instance {} has {} inputs, expected {}",
+                instance, got, expected
+            ),
+        }
+    }
+}
+
+impl std::error::Error for GkrError {}
@@ -0,0 +1,943 @@
+use ark_bn254::Fq;
+use fiat_shamir::fiat_shamir_transcript::{fq_vec_to_bytes, Transcript};
+use multilinear_polynomial::{
+    composed_polynomial::{ProductPoly, ShapeError, SumPoly},
+    evaluation_claim::CombinedClaim,
+    multilinear_polynomial_evaluation::{eq_eval, MultilinearPoly},
+};
+use sum_check::{
+    sum_check_protocol::{gkr_verify_final, GkrLayerRounds, GkrProof, GkrProofBn254},
+    sum_check_trait::{GkrLayerStatement, GkrLayerSumCheck, SumCheckProtocol},
+};
+
+use crate::{
+    circuit::{wire_table_len, Circuit, GateFamily},
+    gkr_error::VerificationError,
+    input_commitment::InputCommitment,
+    preprocessed::{layer_wiring, SparseWiring},
+};
+
+/// A GKR proof for a whole circuit: one layer sum-check per layer, walking
+/// from the outputs down to the inputs. Each carries the claimed values
+/// `w_b` and `w_c` of the layer below it.
+pub struct CircuitProof {
+    pub layer_proofs: Vec<GkrProofBn254>,
+    /// The third claim `w_d` of each layer with fan-in-3 gates, and `None`
+    /// for the other layers.
+    pub w_d: Vec<Option<Fq>>,
+}
+
+/// A GKR proof whose inputs are committed to rather than sent: the layer
+/// proofs, followed by an opening of the input MLE at each point the last
+/// layer leaves a claim on.
+pub struct CommittedCircuitProof<P> {
+    pub circuit_proof: CircuitProof,
+    pub input_openings: Vec<(Fq, P)>,
+}
+
+/// The points the last layer's claims are on, each with the coefficient of
+/// its evaluation in the final claim.
+pub(crate) type InputPoints = Vec<(Fq, Vec<Fq>)>;
+
+/// What the verifier reads from one layer's proof: its round polynomials and
+/// the claimed evaluations of the layer below, one per wire of its gates.
+pub(crate) type LayerMessages = (Vec<Vec<Fq>>, Vec<Fq>);
+
+impl CircuitProof {
+    pub(crate) fn into_layer_messages(self) -> Vec<LayerMessages> {
+        let w_d = self.w_d.into_iter().chain(std::iter::repeat(None));
+
+        self.layer_proofs
+            .into_iter()
+            .zip(w_d)
+            .map(|(proof, w_d)| {
+                let mut claims = vec![proof.w_b, proof.w_c];
+                claims.extend(w_d);
+
+                (proof.proof_polynomials, claims)
+            })
+            .collect()
+    }
+}
+
+pub fn prove(circuit: &Circuit, inputs: &[Fq]) -> CircuitProof {
+    let layer_values = circuit.evaluate(inputs);
+    let wiring = layer_wiring(circuit);
+
+    prove_layers(
+        circuit,
+        &wiring,
+        &layer_values,
+        inputs,
+        0,
+        &mut Transcript::new(),
+    )
+    .0
+}
+
+/// Like `prove`, but commits to the inputs and absorbs the commitment before
+/// anything else, so every challenge is bound to it.
+pub fn prove_committed<C: InputCommitment>(
+    circuit: &Circuit,
+    inputs: &[Fq],
+    committer: &mut C,
+) -> (C::Commitment, CommittedCircuitProof<C::OpeningProof>) {
+    let commitment = committer.commit(inputs);
+
+    let mut transcript = Transcript::<Fq>::new();
+    transcript.append_with_label(b"gkr_input_commitment", commitment.as_ref());
+
+    let layer_values = circuit.evaluate(inputs);
+    let wiring = layer_wiring(circuit);
+    let (circuit_proof, points) =
+        prove_layers(circuit, &wiring, &layer_values, inputs, 0, &mut transcript);
+    let input_openings = points
+        .iter()
+        .map(|(_, point)| committer.open(point))
+        .collect();
+
+    (
+        commitment,
+        CommittedCircuitProof {
+            circuit_proof,
+            input_openings,
+        },
+    )
+}
+
+/// Proves every layer given the circuit's `wiring` and its `layer_values`
+/// on `inputs`, in the transcript order `verify_layers` checks. The tables
+/// may hold `2^instance_vars` instances one after another, so that each
+/// layer MLE ranges over `(k, g)`, the instance index followed by the gate
+/// index, and each layer's sum-check over `(k, b, c)`, with `eq(r_k, k)`
+/// selecting the instance point its claims are on. The input points come
+/// back with that instance point in front.
+pub(crate) fn prove_layers(
+    circuit: &Circuit,
+    wiring: &[Vec<SparseWiring>],
+    layer_values: &[Vec<Fq>],
+    inputs: &[Fq],
+    instance_vars: usize,
+    transcript: &mut Transcript<Fq>,
+) -> (CircuitProof, InputPoints) {
+    transcript.append(&fq_vec_to_bytes(&layer_values[0]));
+
+    let output_point = sample_point(transcript, layer_values[0].len().ilog2() as usize);
+    let (instance_point, gate_point) = output_point.split_at(instance_vars);
+    let mut instance_point = instance_point.to_vec();
+    let mut points = vec![(Fq::from(1), gate_point.to_vec())];
+
+    let num_layers = circuit.layers().len();
+    let mut layer_proofs = Vec::with_capacity(num_layers);
+    let mut w_d = Vec::with_capacity(num_layers);
+
+    for layer in 0..num_layers {
+        let next_values = match layer_values.get(layer + 1) {
+            Some(values) => values.as_slice(),
+            None => inputs,
+        };
+
+        let fan_in = circuit.layers()[layer].fan_in();
+        let input_size = circuit.layer_input_size(layer);
+        let eq = MultilinearPoly::eq_mle(&instance_point).into_evaluations();
+
+        let composed_polynomial = SumPoly::new(
+            wiring[layer]
+                .iter()
+                .flat_map(|wiring| {
+                    family_terms(
+                        wiring.family,
+                        &eq,
+                        wiring.fold(&points).evaluations(),
+                        next_values,
+                        input_size,
+                        fan_in,
+                    )
+                })
+                .collect(),
+        );
+
+        let GkrLayerRounds {
+            claimed_sum,
+            proof_polynomials,
+            random_challenges,
+        } = GkrLayerSumCheck { layer }
+            .prove(&composed_polynomial, transcript)
+            .expect(
+                "every layer polynomial ranges over the instances and one copy of the layer below per wire",
+            );
+
+        let (r_k, wire_point) = random_challenges.split_at(instance_vars);
+        let wire_points = split_wire_points(wire_point, fan_in);
+        let next_poly = MultilinearPoly::new(next_values.to_vec());
+        let claims: Vec<Fq> = wire_points
+            .iter()
+            .map(|point| next_poly.evaluate(&[r_k, point].concat()))
+            .collect();
+
+        let (_, coefficients) = fold_claims(transcript, &claims);
+        instance_point = r_k.to_vec();
+        points = coefficients.into_iter().zip(wire_points).collect();
+
+        layer_proofs.push(GkrProof {
+            proof_polynomials,
+            claimed_sum,
+            random_challenges,
+            w_b: claims[0],
+            w_c: claims[1],
+        });
+        w_d.push(claims.get(2).copied());
+    }
+
+    (
+        CircuitProof { layer_proofs, w_d },
+        with_instance_point(&instance_point, points),
+    )
+}
+
+pub fn verify(
+    circuit: &Circuit,
+    inputs: &[Fq],
+    outputs: &[Fq],
+    proof: CircuitProof,
+) -> Result<(), VerificationError> {
+    if inputs.len() != circuit.num_inputs() {
+        return Err(VerificationError::WrongNumberOfInputs {
+            expected: circuit.num_inputs(),
+            got: inputs.len(),
+        });
+    }
+
+    let input_claim = verify_layers(
+        circuit,
+        &layer_wiring(circuit),
+        &[outputs],
+        proof.into_layer_messages(),
+        &mut Transcript::new(),
+    )?;
+
+    // The last claim is about the inputs, which the verifier holds.
+    if !input_claim.check_against(&input_polynomial(inputs)?) {
+        return Err(VerificationError::InputClaimMismatch);
+    }
+
+    Ok(())
+}
+
+/// Like `verify`, but checks the last claim against openings of committed
+/// inputs instead of the inputs themselves.
+pub fn verify_committed<C: InputCommitment>(
+    circuit: &Circuit,
+    commitment: &C::Commitment,
+    outputs: &[Fq],
+    proof: CommittedCircuitProof<C::OpeningProof>,
+) -> Result<(), VerificationError> {
+    let mut transcript = Transcript::<Fq>::new();
+    transcript.append_with_label(b"gkr_input_commitment", commitment.as_ref());
+
+    let input_claim = verify_layers(
+        circuit,
+        &layer_wiring(circuit),
+        &[outputs],
+        proof.circuit_proof.into_layer_messages(),
+        &mut transcript,
+    )?;
+
+    if proof.input_openings.len() != input_claim.points.len() {
+        return Err(VerificationError::WrongNumberOfOpenings {
+            expected: input_claim.points.len(),
+            got: proof.input_openings.len(),
+        });
+    }
+
+    let mut opened = Fq::from(0);
+    for (index, ((coefficient, point), (eval, opening))) in input_claim
+        .coefficients
+        .iter()
+        .zip(&input_claim.points)
+        .zip(&proof.input_openings)
+        .enumerate()
+    {
+        if !C::verify_opening(commitment, point, *eval, opening) {
+            return Err(VerificationError::InvalidOpening { index });
+        }
+
+        opened += *coefficient * eval;
+    }
+
+    if opened != input_claim.value {
+        return Err(VerificationError::InputClaimMismatch);
+    }
+
+    Ok(())
+}
+
+/// Checks every layer proof, returning the last claim: a combination of
+/// evaluations of the input MLE. There is one output list per instance, and
+/// a power of two of them, as `prove_layers` lays out.
+///
+/// This fixes the transcript order every GKR proof follows: the outputs,
+/// then each layer's rounds followed by its `w_b`, `w_c` and, for a fan-in-3
+/// layer, `w_d`.
+pub(crate) fn verify_layers<O: AsRef<[Fq]>>(
+    circuit: &Circuit,
+    wiring: &[Vec<SparseWiring>],
+    instance_outputs: &[O],
+    layers: Vec<LayerMessages>,
+    transcript: &mut Transcript<Fq>,
+) -> Result<CombinedClaim<Fq>, VerificationError> {
+    let num_layers = circuit.layers().len();
+    let num_instances = instance_outputs.len();
+
+    if !num_instances.is_power_of_two() {
+        return Err(VerificationError::InstanceCountNotPowerOfTwo {
+            count: num_instances,
+        });
+    }
+
+    // The padding gates of the output layer always output zero.
+    let num_outputs = circuit.layers()[0].gates.len();
+    let mut outputs = Vec::with_capacity(num_instances * num_outputs);
+    for instance in instance_outputs {
+        let instance = instance.as_ref();
+        if instance.len() != circuit.num_outputs() {
+            return Err(VerificationError::WrongNumberOfOutputs {
+                expected: circuit.num_outputs(),
+                got: instance.len(),
+            });
+        }
+
+        outputs.extend_from_slice(instance);
+        outputs.resize(outputs.len() + num_outputs - instance.len(), Fq::from(0));
+    }
+
+    if layers.len() != num_layers {
+        return Err(VerificationError::WrongNumberOfLayers {
+            expected: num_layers,
+            got: layers.len(),
+        });
+    }
+
+    let instance_vars = num_instances.ilog2() as usize;
+
+    transcript.append(&fq_vec_to_bytes(&outputs));
+
+    let output_point = sample_point(transcript, outputs.len().ilog2() as usize);
+    let mut claimed_sum = MultilinearPoly::new(outputs).evaluate(&output_point);
+    let (instance_point, gate_point) = output_point.split_at(instance_vars);
+    let mut instance_point = instance_point.to_vec();
+    let mut points = vec![(Fq::from(1), gate_point.to_vec())];
+
+    for (layer, (round_polynomials, claims)) in layers.into_iter().enumerate() {
+        let circuit_layer = &circuit.layers()[layer];
+        let fan_in = circuit_layer.fan_in();
+
+        if claims.len() != fan_in {
+            return Err(VerificationError::WrongNumberOfClaims {
+                layer,
+                expected: fan_in,
+                got: claims.len(),
+            });
+        }
+
+        let wire_vars = circuit.layer_input_size(layer).ilog2() as usize;
+        let mut degrees = vec![instance_degree(&wiring[layer]); instance_vars];
+        degrees.extend(layer_degrees(&wiring[layer], fan_in, wire_vars));
+        let statement = GkrLayerStatement {
+            claimed_sum,
+            degrees,
+        };
+        let rounds = GkrLayerRounds {
+            claimed_sum,
+            proof_polynomials: round_polynomials,
+            random_challenges: Vec::new(),
+        };
+        let result = GkrLayerSumCheck { layer }
+            .verify_partial(&statement, rounds, transcript)
+            .map_err(|_| VerificationError::LayerSumCheckFailed { layer })?;
+        let (r_k, wire_point) = result.point.split_at(instance_vars);
+
+        let wiring_eval: Fq = wiring[layer]
+            .iter()
+            .map(|wiring| wiring.evaluate(&points, wire_point) * wiring.family.combine(&claims))
+            .sum();
+        let oracle_eval = eq_eval(&instance_point, r_k) * wiring_eval;
+        if gkr_verify_final(result.value, oracle_eval).is_err() {
+            return Err(VerificationError::LayerClaimMismatch { layer });
+        }
+
+        let (next_claim, coefficients) = fold_claims(transcript, &claims);
+        claimed_sum = next_claim;
+        instance_point = r_k.to_vec();
+        points = coefficients
+            .into_iter()
+            .zip(split_wire_points(wire_point, fan_in))
+            .collect();
+    }
+
+    let (coefficients, points) = with_instance_point(&instance_point, points)
+        .into_iter()
+        .unzip();
+
+    Ok(CombinedClaim {
+        points,
+        coefficients,
+        value: claimed_sum,
+    })
+}
+
+/// Puts the instance point in front of each wire point, giving points on
+/// the MLE of every instance's values.
+fn with_instance_point(instance_point: &[Fq], points: InputPoints) -> InputPoints {
+    points
+        .into_iter()
+        .map(|(coefficient, point)| (coefficient, [instance_point, &point].concat()))
+        .collect()
+}
+
+/// The bound on each round over an instance variable: `W(k, b)` and
+/// `W(k, c)` share the instance variables with `eq(r_k, k)`, so an addition
+/// has degree two in them, a multiplication three and a fan-in-3 one four.
+fn instance_degree(wiring: &[SparseWiring]) -> usize {
+    wiring
+        .iter()
+        .map(|wiring| match wiring.family {
+            GateFamily::Mul => 3,
+            GateFamily::Mul3 => 4,
+            _ => 2,
+        })
+        .max()
+        .unwrap_or(1)
+}
+
+/// The bound `degree_in_variable` puts on each round of a layer's
+/// sum-check over `(b, c)`, or `(b, c, d)` for fan-in 3: a variable of one
+/// wire sits in the wiring factor and, if some family reads that wire, in
+/// its `W` factor, which ranges over that wire's variables alone.
+fn layer_degrees(wiring: &[SparseWiring], fan_in: usize, wire_vars: usize) -> Vec<usize> {
+    (0..fan_in)
+        .flat_map(|wire| {
+            let read = wiring.iter().any(|wiring| wiring.family.reads(wire));
+
+            std::iter::repeat_n(1 + read as usize, wire_vars)
+        })
+        .collect()
+}
+
+/// The input MLE, failing rather than panicking if there is no power of two
+/// of inputs.
+pub(crate) fn input_polynomial(inputs: &[Fq]) -> Result<MultilinearPoly<Fq>, VerificationError> {
+    MultilinearPoly::try_new(inputs.to_vec()).map_err(|_| VerificationError::InputClaimMismatch)
+}
+
+pub(crate) fn sample_point(transcript: &mut Transcript<Fq>, num_vars: usize) -> Vec<Fq> {
+    (0..num_vars)
+        .map(|_| transcript.get_uniform_challenge())
+        .collect()
+}
+
+/// Absorbs the claims `W(r_b) = w_b`, `W(r_c) = w_c`, ... left by a layer and
+/// folds them into the single claim `α·w_b + β·w_c + ...` on the layer below.
+/// Returns that claim with one coefficient per claim; the prover and the
+/// verifier both call this, so they draw the same coefficients.
+pub fn fold_claims(transcript: &mut Transcript<Fq>, claims: &[Fq]) -> (Fq, Vec<Fq>) {
+    transcript.append(&fq_vec_to_bytes(claims));
+
+    let coefficients: Vec<Fq> = claims
+        .iter()
+        .map(|_| transcript.get_uniform_challenge())
+        .collect();
+    let folded = coefficients
+        .iter()
+        .zip(claims)
+        .map(|(coefficient, claim)| *coefficient * claim)
+        .sum();
+
+    (folded, coefficients)
+}
+
+/// Splits a layer's final point into the `fan_in` points its claims on the
+/// layer below are on, one per wire.
+pub(crate) fn split_wire_points(point: &[Fq], fan_in: usize) -> Vec<Vec<Fq>> {
+    let wire_vars = point.len() / fan_in;
+
+    (0..fan_in)
+        .map(|wire| point[wire * wire_vars..(wire + 1) * wire_vars].to_vec())
+        .collect()
+}
+
+/// `Σ coefficient·wiring(point, b, c)`, as a polynomial in `(b, c)`, or in
+/// `(b, c, d)` for a fan-in-3 layer. The dense reference `SparseWiring::fold`
+/// is tested against.
+#[cfg(test)]
+pub(crate) fn fold_wiring(
+    wiring: &MultilinearPoly<Fq>,
+    points: &[(Fq, Vec<Fq>)],
+) -> MultilinearPoly<Fq> {
+    points
+        .iter()
+        .map(|(coefficient, point)| {
+            let gate_point: Vec<_> = point.iter().copied().enumerate().collect();
+
+            wiring
+                .restrict(&gate_point)
+                .expect("the gate variables come first")
+                * *coefficient
+        })
+        .reduce(|acc, poly| acc + poly)
+        .unwrap()
+}
+
+/// `Σ coefficient·wiring(point, r_b, r_c)`, where `wire_point` is `r_b`
+/// followed by `r_c` and, for a fan-in-3 layer, `r_d`. The dense reference
+/// `SparseWiring::evaluate` is tested against.
+#[cfg(test)]
+pub(crate) fn evaluate_wiring(
+    wiring: &MultilinearPoly<Fq>,
+    points: &[(Fq, Vec<Fq>)],
+    wire_point: &[Fq],
+) -> Fq {
+    points
+        .iter()
+        .map(|(coefficient, point)| {
+            *coefficient * wiring.evaluate(&[point.as_slice(), wire_point].concat())
+        })
+        .sum()
+}
+
+/// The terms one family contributes to a layer's composed polynomial over
+/// `(k, b, c)`: `eq(r_k, k)·wiring(b, c)` times `combine(W(k, b), W(k, c))`,
+/// written as products of multilinear factors, so a fan-in-3 product is
+/// `eq·wiring·W(b)·W(c)·W(d)`. A layer of fan-in `fan_in` ranges over that
+/// many copies of the gates. A single instance has no `k` and `eq = [1]`.
+pub(crate) fn family_terms(
+    family: GateFamily,
+    eq: &[Fq],
+    wiring: &[Fq],
+    next_values: &[Fq],
+    input_size: usize,
+    fan_in: usize,
+) -> Vec<ProductPoly<Fq>> {
+    let selector: Vec<Fq> = eq
+        .iter()
+        .flat_map(|e| wiring.iter().map(move |w| *e * w))
+        .collect();
+
+    // `W(k, ·)` read through each wire the family uses, over every
+    // `(k, b, c, ...)`.
+    let wire_vars = input_size.ilog2() as usize;
+    let mut wires = (0..family.fan_in()).map(|wire| -> Vec<Fq> {
+        let shift = (fan_in - 1 - wire) * wire_vars;
+
+        next_values
+            .chunks(input_size)
+            .flat_map(|values| {
+                (0..wire_table_len(fan_in * wire_vars))
+                    .map(move |index| values[(index >> shift) & (input_size - 1)])
+            })
+            .collect()
+    });
+
+    match family {
+        GateFamily::Add | GateFamily::Add3 => wires
+            .map(|wire| ProductPoly::new(vec![selector.clone(), wire]))
+            .collect(),
+        GateFamily::Sub => vec![
+            ProductPoly::new(vec![selector.clone(), wires.next().unwrap()]),
+            ProductPoly::new(vec![
+                selector.iter().map(|s| -*s).collect(),
+                wires.next().unwrap(),
+            ]),
+        ],
+        GateFamily::Mul | GateFamily::Mul3 => {
+            vec![ProductPoly::new(
+                std::iter::once(selector).chain(wires).collect(),
+            )]
+        }
+        GateFamily::Left => vec![ProductPoly::new(vec![selector, wires.next().unwrap()])],
+    }
+}
+
+/// `add(b, c)·(W(b) + W(c)) + mul(b, c)·W(b)·W(c)` over `(b, c)`, the
+/// textbook layer polynomial, where `add_mle` and `mul_mle` are the wiring
+/// predicates already bound at the gate point and `w_b`, `w_c` range over
+/// `b` and `c` alone. Fails if their variables do not add up.
+pub fn gkr_layer_poly(
+    add_mle: &MultilinearPoly<Fq>,
+    mul_mle: &MultilinearPoly<Fq>,
+    w_b: &MultilinearPoly<Fq>,
+    w_c: &MultilinearPoly<Fq>,
+) -> Result<SumPoly<Fq>, ShapeError> {
+    let num_vars = add_mle.num_vars();
+    let wire_vars = w_b.num_vars() + w_c.num_vars();
+    if wire_vars != num_vars {
+        return Err(ShapeError::MismatchedNumVars {
+            term: 0,
+            factor: 1,
+            expected: num_vars,
+            got: wire_vars,
+        });
+    }
+
+    let ones = |num_vars| MultilinearPoly::new(vec![Fq::from(1); wire_table_len(num_vars)]);
+    let lifted_b = w_b
+        .tensor(&ones(w_c.num_vars()))
+        .expect("no larger than add_mle");
+    let lifted_c = ones(w_b.num_vars())
+        .tensor(w_c)
+        .expect("no larger than add_mle");
+
+    SumPoly::zero(num_vars)
+        .add_product(vec![add_mle.clone(), lifted_b.clone()])?
+        .add_product(vec![add_mle.clone(), lifted_c.clone()])?
+        .add_product(vec![mul_mle.clone(), lifted_b, lifted_c])
+}
+
+#[cfg(test)]
+mod test {
+    use ark_bn254::Fq;
+    use multilinear_polynomial::{
+        composed_polynomial::{ProductPoly, SumPoly},
+        multilinear_polynomial_evaluation::MultilinearPoly,
+    };
+
+    use super::{
+        family_terms, fold_wiring, gkr_layer_poly, prove, prove_committed, verify, verify_committed,
+    };
+    use crate::{
+        circuit::{Circuit, Gate, GateFamily, GateOp, Layer},
+        gkr_error::VerificationError,
+        input_commitment::{InputCommitment, MerkleCommitment},
+    };
+
+    fn sample_circuit() -> Circuit {
+        Circuit::new(
+            vec![
+                Layer::new(vec![
+                    Gate::new(GateOp::Add, 0, 1),
+                    Gate::new(GateOp::Mul, 0, 1),
+                ]),
+                Layer::new(vec![
+                    Gate::new(GateOp::Mul, 0, 1),
+                    Gate::new(GateOp::Add, 2, 3),
+                ]),
+            ],
+            4,
+        )
+        .unwrap()
+    }
+
+    fn sample_inputs() -> Vec<Fq> {
+        vec![Fq::from(2), Fq::from(3), Fq::from(4), Fq::from(5)]
+    }
+
+    #[test]
+    fn it_proves_and_verifies_a_two_layer_circuit() {
+        let circuit = sample_circuit();
+        let inputs = sample_inputs();
+
+        let outputs = circuit.evaluate(&inputs)[0].clone();
+        assert_eq!(outputs, vec![Fq::from(15), Fq::from(54)]);
+
+        let proof = prove(&circuit, &inputs);
+
+        assert_eq!(verify(&circuit, &inputs, &outputs, proof), Ok(()));
+    }
+
+    #[test]
+    fn it_rejects_a_tampered_middle_layer_evaluation() {
+        let circuit = Circuit::new(
+            vec![
+                Layer::new(vec![Gate::new(GateOp::Mul, 0, 1)]),
+                Layer::new(vec![
+                    Gate::new(GateOp::Add, 0, 1),
+                    Gate::new(GateOp::Mul, 0, 1),
+                ]),
+                Layer::new(vec![
+                    Gate::new(GateOp::Mul, 0, 1),
+                    Gate::new(GateOp::Add, 2, 3),
+                ]),
+            ],
+            4,
+        )
+        .unwrap();
+        let inputs = sample_inputs();
+        let outputs = circuit.evaluate(&inputs)[0].clone();
+
+        let mut proof = prove(&circuit, &inputs);
+        proof.layer_proofs[1].w_b += Fq::from(1);
+
+        assert!(verify(&circuit, &inputs, &outputs, proof).is_err());
+    }
+
+    fn mixed_circuit() -> Circuit {
+        Circuit::new(
+            vec![
+                Layer::new(vec![
+                    Gate::new(GateOp::Sub, 0, 1),
+                    Gate::new(GateOp::Mul, 2, 3),
+                ]),
+                Layer::new(vec![
+                    Gate::new(GateOp::Add, 0, 1),
+                    Gate::new(GateOp::Sub, 3, 2),
+                    Gate::new(GateOp::Scale(Fq::from(7)), 1, 0),
+                    Gate::new(GateOp::Left, 3, 0),
+                ]),
+            ],
+            4,
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn it_proves_and_verifies_a_circuit_with_custom_gates() {
+        let circuit = mixed_circuit();
+        let inputs = sample_inputs();
+
+        let outputs = circuit.evaluate(&inputs)[0].clone();
+        assert_eq!(outputs, vec![Fq::from(4), Fq::from(105)]);
+
+        let proof = prove(&circuit, &inputs);
+
+        assert_eq!(verify(&circuit, &inputs, &outputs, proof), Ok(()));
+    }
+
+    #[test]
+    fn it_rejects_a_tampered_sub_gate_output() {
+        let circuit = mixed_circuit();
+        let inputs = sample_inputs();
+
+        let mut outputs = circuit.evaluate(&inputs)[0].clone();
+        outputs[0] += Fq::from(1);
+
+        let proof = prove(&circuit, &inputs);
+
+        assert!(verify(&circuit, &inputs, &outputs, proof).is_err());
+    }
+
+    #[test]
+    fn it_proves_and_verifies_layers_that_are_not_powers_of_two() {
+        let circuit = Circuit::new(
+            vec![
+                Layer::new(vec![
+                    Gate::new(GateOp::Add, 0, 1),
+                    Gate::new(GateOp::Mul, 2, 3),
+                    Gate::new(GateOp::Sub, 4, 0),
+                ]),
+                Layer::new(vec![
+                    Gate::new(GateOp::Mul, 0, 1),
+                    Gate::new(GateOp::Add, 2, 3),
+                    Gate::new(GateOp::Add, 4, 5),
+                    Gate::new(GateOp::Mul, 6, 7),
+                    Gate::new(GateOp::Sub, 7, 0),
+                ]),
+            ],
+            8,
+        )
+        .unwrap();
+        let inputs: Vec<Fq> = (1..=8u64).map(Fq::from).collect();
+
+        let layer_values = circuit.evaluate(&inputs);
+        assert_eq!(layer_values[1].len(), 8);
+        assert_eq!(
+            layer_values[1][..5],
+            [
+                Fq::from(2),
+                Fq::from(7),
+                Fq::from(11),
+                Fq::from(56),
+                Fq::from(7)
+            ]
+        );
+        assert!(layer_values[1][5..]
+            .iter()
+            .all(|value| *value == Fq::from(0)));
+
+        let outputs = layer_values[0][..circuit.num_outputs()].to_vec();
+        assert_eq!(outputs, vec![Fq::from(9), Fq::from(616), Fq::from(5)]);
+
+        let proof = prove(&circuit, &inputs);
+
+        assert_eq!(verify(&circuit, &inputs, &outputs, proof), Ok(()));
+    }
+
+    /// A fan-in-3 product over the inputs, under a layer that reads it
+    /// alongside a fan-in-2 gate in the same layer.
+    fn fan_in_3_circuit() -> Circuit {
+        Circuit::new(
+            vec![
+                Layer::new(vec![
+                    Gate::new(GateOp::Add, 0, 1),
+                    Gate::new(GateOp::Mul, 0, 1),
+                ]),
+                Layer::new(vec![
+                    Gate::ternary(GateOp::Mul3, 0, 1, 3),
+                    Gate::new(GateOp::Sub, 2, 1),
+                ]),
+            ],
+            4,
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn it_proves_and_verifies_a_fan_in_3_gate() {
+        let circuit = fan_in_3_circuit();
+        let inputs = sample_inputs();
+
+        let outputs = circuit.evaluate(&inputs)[0].clone();
+        assert_eq!(outputs, vec![Fq::from(31), Fq::from(30)]);
+
+        let proof = prove(&circuit, &inputs);
+        assert_eq!(proof.w_d, vec![None, Some(proof.w_d[1].unwrap())]);
+
+        // The fan-in-3 layer's rounds run over `(b, c, d)`, and each
+        // variable sits in the wiring and in one `W`, so every round is
+        // quadratic and sent as three evaluations.
+        assert_eq!(proof.layer_proofs[1].proof_polynomials.len(), 6);
+        assert!(proof.layer_proofs[1]
+            .proof_polynomials
+            .iter()
+            .all(|round| round.len() == 3));
+
+        assert_eq!(verify(&circuit, &inputs, &outputs, proof), Ok(()));
+    }
+
+    #[test]
+    fn a_fan_in_3_product_is_a_product_of_three_wire_polynomials() {
+        let circuit = fan_in_3_circuit();
+        let inputs = sample_inputs();
+        let layer = &circuit.layers()[1];
+        let wiring = layer.wiring_mle(GateFamily::Mul3, 4);
+        let gate_point = vec![Fq::from(3)];
+
+        let terms = family_terms(
+            GateFamily::Mul3,
+            &[Fq::from(1)],
+            fold_wiring(&wiring, &[(Fq::from(1), gate_point)]).evaluations(),
+            &inputs,
+            4,
+            3,
+        );
+        let layer_poly = SumPoly::new(terms);
+
+        // `wiring·W(b)·W(c)·W(d)`: degree 4 in all, 3 in the wire values,
+        // and 2 in any one variable.
+        assert_eq!(layer_poly.polys.len(), 1);
+        assert_eq!(layer_poly.get_degree(), 4);
+        assert!((0..6).all(|index| layer_poly.degree_in_variable(index) == 2));
+    }
+
+    #[test]
+    fn it_rejects_a_tampered_third_claim() {
+        let circuit = fan_in_3_circuit();
+        let inputs = sample_inputs();
+        let outputs = circuit.evaluate(&inputs)[0].clone();
+
+        let mut proof = prove(&circuit, &inputs);
+        *proof.w_d[1].as_mut().unwrap() += Fq::from(1);
+        assert_eq!(
+            verify(&circuit, &inputs, &outputs, proof),
+            Err(VerificationError::LayerClaimMismatch { layer: 1 })
+        );
+
+        let mut proof = prove(&circuit, &inputs);
+        proof.w_d[1] = None;
+        assert_eq!(
+            verify(&circuit, &inputs, &outputs, proof),
+            Err(VerificationError::WrongNumberOfClaims {
+                layer: 1,
+                expected: 3,
+                got: 2
+            })
+        );
+    }
+
+    #[test]
+    fn it_rejects_a_tampered_output() {
+        let circuit = sample_circuit();
+        let inputs = sample_inputs();
+
+        let mut outputs = circuit.evaluate(&inputs)[0].clone();
+        outputs[1] += Fq::from(1);
+
+        let proof = prove(&circuit, &inputs);
+
+        assert_eq!(
+            verify(&circuit, &inputs, &outputs, proof),
+            Err(VerificationError::LayerSumCheckFailed { layer: 0 })
+        );
+    }
+
+    #[test]
+    fn it_proves_and_verifies_against_committed_inputs() {
+        let circuit = sample_circuit();
+        let inputs = sample_inputs();
+        let outputs = circuit.evaluate(&inputs)[0].clone();
+
+        let (commitment, proof) =
+            prove_committed(&circuit, &inputs, &mut MerkleCommitment::default());
+
+        assert_eq!(
+            verify_committed::<MerkleCommitment>(&circuit, &commitment, &outputs, proof),
+            Ok(())
+        );
+    }
+
+    #[test]
+    fn it_rejects_an_opening_of_different_inputs() {
+        let circuit = sample_circuit();
+        let inputs = sample_inputs();
+        let outputs = circuit.evaluate(&inputs)[0].clone();
+
+        let (commitment, mut proof) =
+            prove_committed(&circuit, &inputs, &mut MerkleCommitment::default());
+
+        // The swapped inputs give the same outputs, so only the openings
+        // can tell them apart.
+        let mut other = MerkleCommitment::default();
+        other.commit(&[Fq::from(3), Fq::from(2), Fq::from(5), Fq::from(4)]);
+        let challenges = &proof.circuit_proof.layer_proofs[1].random_challenges;
+        proof.input_openings = challenges
+            .chunks(challenges.len() / 2)
+            .map(|point| other.open(point))
+            .collect();
+
+        assert_eq!(
+            verify_committed::<MerkleCommitment>(&circuit, &commitment, &outputs, proof),
+            Err(VerificationError::InvalidOpening { index: 0 })
+        );
+    }
+
+    #[test]
+    fn the_layer_polynomial_matches_its_raw_terms() {
+        let table = |num_vars, seed: u64| {
+            MultilinearPoly::from_fn(num_vars, |index| {
+                Fq::from(seed * (index as u64 + 1) * (index as u64 + 3) % 97)
+            })
+        };
+        let (add, mul) = (table(4, 5), table(4, 11));
+        let w = table(2, 7);
+
+        let built = gkr_layer_poly(&add, &mul, &w, &w).unwrap();
+
+        let w_b: Vec<Fq> = (0..16).map(|index| w.evaluations()[index >> 2]).collect();
+        let w_c: Vec<Fq> = (0..16).map(|index| w.evaluations()[index & 3]).collect();
+        let add = add.into_evaluations();
+        let raw = SumPoly::new(vec![
+            ProductPoly::new(vec![add.clone(), w_b.clone()]),
+            ProductPoly::new(vec![add, w_c.clone()]),
+            ProductPoly::new(vec![mul.into_evaluations(), w_b, w_c]),
+        ]);
+
+        for seed in 1..5u64 {
+            let point: Vec<Fq> = (0..4)
+                .map(|i| Fq::from(seed * 31 + i * 17) / Fq::from(seed + i + 2))
+                .collect();
+            assert_eq!(built.evaluate(&point), raw.evaluate(&point));
+        }
+        assert_eq!(built.reduce(), raw.reduce());
+
+        assert!(gkr_layer_poly(&table(4, 5), &table(4, 11), &w, &table(1, 3)).is_err());
+    }
+}
@@ -0,0 +1,153 @@
+use ark_bn254::Fq;
+use fiat_shamir::fiat_shamir_transcript::fq_vec_to_bytes;
+use multilinear_polynomial::multilinear_polynomial_evaluation::MultilinearPoly;
+use sha3::{Digest, Keccak256};
+
+/// A commitment to the circuit inputs, opened at the points the last GKR
+/// layer leaves its claims on, so the verifier never needs the inputs
+/// themselves.
+pub trait InputCommitment {
+    type Commitment: AsRef<[u8]>;
+    type OpeningProof;
+
+    /// Commits to the evaluations of the input MLE over the hypercube.
+    fn commit(&mut self, evals: &[Fq]) -> Self::Commitment;
+
+    /// The committed MLE's value at `point`, with a proof of it.
+    fn open(&self, point: &[Fq]) -> (Fq, Self::OpeningProof);
+
+    fn verify_opening(
+        commitment: &Self::Commitment,
+        point: &[Fq],
+        eval: Fq,
+        proof: &Self::OpeningProof,
+    ) -> bool;
+}
+
+/// A Merkle tree over the input evaluations, with Keccak-256 as the hash.
+///
+/// An MLE evaluation depends on every leaf, so an opening reveals all of
+/// them and is as large as the inputs. It is binding but neither succinct
+/// nor hiding; a real polynomial commitment slots in through the trait.
+#[derive(Debug, Clone, Default)]
+pub struct MerkleCommitment {
+    evals: Vec<Fq>,
+}
+
+/// Every leaf of the tree, from which the verifier rebuilds the root.
+#[derive(Debug, Clone, PartialEq)]
+pub struct MerkleOpening {
+    pub evals: Vec<Fq>,
+}
+
+/// The root of a Merkle tree over `evals`, whose length must be a power of
+/// two. Leaves and inner nodes are hashed under different prefixes so that
+/// neither can pass for the other.
+pub fn merkle_root(evals: &[Fq]) -> [u8; 32] {
+    let mut level: Vec<[u8; 32]> = evals
+        .iter()
+        .map(|eval| hash(&[&[0], fq_vec_to_bytes(&[*eval]).as_slice()]))
+        .collect();
+
+    while level.len() > 1 {
+        level = level
+            .chunks(2)
+            .map(|pair| hash(&[&[1], &pair[0], &pair[1]]))
+            .collect();
+    }
+
+    level[0]
+}
+
+fn hash(parts: &[&[u8]]) -> [u8; 32] {
+    let mut hasher = Keccak256::new();
+    for part in parts {
+        hasher.update(part);
+    }
+
+    hasher.finalize().into()
+}
+
+impl InputCommitment for MerkleCommitment {
+    type Commitment = [u8; 32];
+    type OpeningProof = MerkleOpening;
+
+    fn commit(&mut self, evals: &[Fq]) -> [u8; 32] {
+        if !evals.len().is_power_of_two() {
+            panic!("the number of evaluations must be a power of two");
+        }
+
+        self.evals = evals.to_vec();
+
+        merkle_root(evals)
+    }
+
+    fn open(&self, point: &[Fq]) -> (Fq, MerkleOpening) {
+        let eval = MultilinearPoly::new(self.evals.clone()).evaluate(point);
+
+        (
+            eval,
+            MerkleOpening {
+                evals: self.evals.clone(),
+            },
+        )
+    }
+
+    fn verify_opening(
+        commitment: &[u8; 32],
+        point: &[Fq],
+        eval: Fq,
+        proof: &MerkleOpening,
+    ) -> bool {
+        // A table that is not a hypercube is rejected before it is hashed.
+        MultilinearPoly::try_new(proof.evals.clone()).and_then(|poly| poly.try_evaluate(point))
+            == Ok(eval)
+            && merkle_root(&proof.evals) == *commitment
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use ark_bn254::Fq;
+
+    use super::{InputCommitment, MerkleCommitment, MerkleOpening};
+
+    #[test]
+    fn it_opens_a_committed_vector() {
+        let evals = vec![Fq::from(2), Fq::from(3), Fq::from(4), Fq::from(5)];
+        let point = vec![Fq::from(7), Fq::from(9)];
+
+        let mut committer = MerkleCommitment::default();
+        let commitment = committer.commit(&evals);
+        let (eval, proof) = committer.open(&point);
+
+        assert!(MerkleCommitment::verify_opening(
+            &commitment,
+            &point,
+            eval,
+            &proof
+        ));
+        assert!(!MerkleCommitment::verify_opening(
+            &commitment,
+            &point,
+            eval + Fq::from(1),
+            &proof
+        ));
+        assert!(!MerkleCommitment::verify_opening(
+            &commitment,
+            &point[..1],
+            eval,
+            &proof
+        ));
+
+        let truncated = MerkleOpening {
+            evals: evals[..3].to_vec(),
+        };
+        assert!(!MerkleCommitment::verify_opening(
+            &commitment,
+            &point,
+            eval,
+            &truncated
+        ));
+    }
+}
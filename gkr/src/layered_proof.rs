@@ -0,0 +1,237 @@
+use ark_bn254::Fq;
+use ark_serialize::{
+    CanonicalDeserialize, CanonicalSerialize, Compress, Read, SerializationError, Valid, Validate,
+    Write,
+};
+use fiat_shamir::fiat_shamir_transcript::Transcript;
+use multilinear_polynomial::multilinear_polynomial_evaluation::MultilinearPoly;
+use sum_check::proof_serialization::{deserialize_nested_vec, deserialize_vec};
+
+use crate::{
+    gkr_error::VerificationError,
+    gkr_protocol::{input_polynomial, prove_layers, verify_layers},
+    preprocessed::{ProvingKey, VerifyingKey},
+};
+
+/// Everything a GKR proof for a whole circuit carries, in one place: the
+/// outputs, each layer's round polynomials and its claims `[w_b, w_c]` on
+/// the layer below, with `w_d` after them on a fan-in-3 layer, and the final
+/// claim on the inputs.
+#[derive(Debug, Clone, PartialEq)]
+pub struct LayeredGkrProof {
+    pub outputs: Vec<Fq>,
+    pub round_polynomials: Vec<Vec<Vec<Fq>>>,
+    pub layer_claims: Vec<Vec<Fq>>,
+    /// `α·W(r_b) + β·W(r_c)` for the input MLE `W` at the last layer's
+    /// points, which the verifier checks against the inputs.
+    pub input_claim: Fq,
+}
+
+/// Proves the key's circuit on `inputs` with the transcript order of
+/// `prove`, after absorbing the circuit digest.
+pub fn prove_circuit(key: &ProvingKey, inputs: &[Fq]) -> LayeredGkrProof {
+    let circuit = key.circuit();
+    let mut transcript = Transcript::<Fq>::new();
+    transcript.append_with_label(b"gkr_circuit", key.digest());
+
+    let layer_values = circuit.evaluate(inputs);
+    let (proof, points) = prove_layers(
+        circuit,
+        key.wiring(),
+        &layer_values,
+        inputs,
+        0,
+        &mut transcript,
+    );
+
+    let input_poly = MultilinearPoly::new(inputs.to_vec());
+    let input_claim = points
+        .iter()
+        .map(|(coefficient, point)| *coefficient * input_poly.evaluate(point))
+        .sum();
+
+    let (round_polynomials, layer_claims) = proof.into_layer_messages().into_iter().unzip();
+
+    LayeredGkrProof {
+        outputs: layer_values[0][..circuit.num_outputs()].to_vec(),
+        round_polynomials,
+        layer_claims,
+        input_claim,
+    }
+}
+
+pub fn verify_circuit(
+    key: &VerifyingKey,
+    inputs: &[Fq],
+    proof: LayeredGkrProof,
+) -> Result<(), VerificationError> {
+    let circuit = key.circuit();
+
+    if inputs.len() != circuit.num_inputs() {
+        return Err(VerificationError::WrongNumberOfInputs {
+            expected: circuit.num_inputs(),
+            got: inputs.len(),
+        });
+    }
+
+    if proof.layer_claims.len() != proof.round_polynomials.len() {
+        return Err(VerificationError::WrongNumberOfLayers {
+            expected: proof.round_polynomials.len(),
+            got: proof.layer_claims.len(),
+        });
+    }
+
+    let layers = proof
+        .round_polynomials
+        .into_iter()
+        .zip(proof.layer_claims)
+        .collect();
+    let mut transcript = Transcript::<Fq>::new();
+    transcript.append_with_label(b"gkr_circuit", key.digest());
+
+    let input_claim = verify_layers(
+        circuit,
+        key.wiring(),
+        &[&proof.outputs],
+        layers,
+        &mut transcript,
+    )?;
+
+    if proof.input_claim != input_claim.value
+        || !input_claim.check_against(&input_polynomial(inputs)?)
+    {
+        return Err(VerificationError::InputClaimMismatch);
+    }
+
+    Ok(())
+}
+
+impl CanonicalSerialize for LayeredGkrProof {
+    fn serialize_with_mode<W: Write>(
+        &self,
+        mut writer: W,
+        compress: Compress,
+    ) -> Result<(), SerializationError> {
+        self.outputs.serialize_with_mode(&mut writer, compress)?;
+        self.round_polynomials
+            .serialize_with_mode(&mut writer, compress)?;
+        self.layer_claims
+            .serialize_with_mode(&mut writer, compress)?;
+        self.input_claim.serialize_with_mode(&mut writer, compress)
+    }
+
+    fn serialized_size(&self, compress: Compress) -> usize {
+        self.outputs.serialized_size(compress)
+            + self.round_polynomials.serialized_size(compress)
+            + self.layer_claims.serialized_size(compress)
+            + self.input_claim.serialized_size(compress)
+    }
+}
+
+impl Valid for LayeredGkrProof {
+    /// Every layer needs both its round polynomials and its claims.
+    fn check(&self) -> Result<(), SerializationError> {
+        if self.round_polynomials.len() != self.layer_claims.len() {
+            return Err(SerializationError::InvalidData);
+        }
+
+        Ok(())
+    }
+}
+
+impl CanonicalDeserialize for LayeredGkrProof {
+    fn deserialize_with_mode<R: Read>(
+        mut reader: R,
+        compress: Compress,
+        validate: Validate,
+    ) -> Result<Self, SerializationError> {
+        let outputs = deserialize_vec(&mut reader, compress, validate)?;
+
+        let num_layers = u64::deserialize_with_mode(&mut reader, compress, validate)?;
+        let mut round_polynomials = Vec::new();
+        for _ in 0..num_layers {
+            round_polynomials.push(deserialize_nested_vec(&mut reader, compress, validate)?);
+        }
+
+        let proof = LayeredGkrProof {
+            outputs,
+            round_polynomials,
+            layer_claims: deserialize_nested_vec(&mut reader, compress, validate)?,
+            input_claim: Fq::deserialize_with_mode(&mut reader, compress, validate)?,
+        };
+
+        if let Validate::Yes = validate {
+            proof.check()?;
+        }
+
+        Ok(proof)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use ark_bn254::Fq;
+    use ark_serialize::{CanonicalDeserialize, CanonicalSerialize};
+
+    use super::{prove_circuit, verify_circuit, LayeredGkrProof};
+    use crate::{
+        circuit::{Circuit, Gate, GateOp, Layer},
+        gkr_error::VerificationError,
+    };
+
+    fn deep_circuit() -> Circuit {
+        Circuit::new(
+            vec![
+                Layer::new(vec![Gate::new(GateOp::Mul, 0, 1)]),
+                Layer::new(vec![
+                    Gate::new(GateOp::Add, 0, 1),
+                    Gate::new(GateOp::Sub, 1, 0),
+                ]),
+                Layer::new(vec![
+                    Gate::new(GateOp::Mul, 0, 1),
+                    Gate::new(GateOp::Add, 2, 3),
+                ]),
+                Layer::new(vec![
+                    Gate::new(GateOp::Add, 0, 1),
+                    Gate::new(GateOp::Mul, 1, 2),
+                    Gate::new(GateOp::Scale(Fq::from(3)), 3, 0),
+                    Gate::new(GateOp::Left, 2, 3),
+                ]),
+            ],
+            4,
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn it_round_trips_and_verifies_a_deep_circuit() {
+        let circuit = deep_circuit();
+        let key = circuit.preprocess();
+        let inputs = vec![Fq::from(2), Fq::from(3), Fq::from(4), Fq::from(5)];
+
+        let proof = prove_circuit(&key, &inputs);
+        assert_eq!(proof.round_polynomials.len(), 4);
+        assert_eq!(proof.outputs, circuit.evaluate(&inputs)[0][..1]);
+
+        let mut bytes = Vec::new();
+        proof.serialize_compressed(&mut bytes).unwrap();
+        let decoded = LayeredGkrProof::deserialize_compressed(bytes.as_slice()).unwrap();
+        assert_eq!(decoded, proof);
+
+        assert_eq!(verify_circuit(&key, &inputs, decoded), Ok(()));
+    }
+
+    #[test]
+    fn it_rejects_a_wrong_input_claim() {
+        let key = deep_circuit().preprocess();
+        let inputs = vec![Fq::from(2), Fq::from(3), Fq::from(4), Fq::from(5)];
+
+        let mut proof = prove_circuit(&key, &inputs);
+        proof.input_claim += Fq::from(1);
+
+        assert_eq!(
+            verify_circuit(&key, &inputs, proof),
+            Err(VerificationError::InputClaimMismatch)
+        );
+    }
+}
@@ -0,0 +1,14 @@
+pub mod circuit;
+pub mod circuit_builder;
+pub mod circuit_json;
+pub mod data_parallel;
+pub mod gkr_error;
+pub mod gkr_protocol;
+pub mod input_commitment;
+pub mod layered_proof;
+pub mod preprocessed;
+
+pub use data_parallel::{prove_batch, verify_batch};
+pub use gkr_protocol::{prove, prove_committed, verify, verify_committed};
+pub use layered_proof::{prove_circuit, verify_circuit, LayeredGkrProof};
+pub use preprocessed::{PreprocessedCircuit, ProvingKey, VerifyingKey};
@@ -0,0 +1,203 @@
+use ark_bn254::Fq;
+use multilinear_polynomial::multilinear_polynomial_evaluation::{eq_at_index, MultilinearPoly};
+use sha3::{Digest, Keccak256};
+
+use crate::circuit::{wire_table_len, Circuit, GateFamily, Layer};
+
+#[cfg(test)]
+thread_local! {
+    /// How many wiring predicates this thread has built, so tests can check
+    /// that a preprocessed circuit does not build them again.
+    pub(crate) static WIRING_BUILDS: std::cell::Cell<usize> = const { std::cell::Cell::new(0) };
+}
+
+/// One family's wiring predicate, kept as its nonzero entries rather than as
+/// an MLE over every `(a, b, c)`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SparseWiring {
+    pub family: GateFamily,
+    entries: Vec<(usize, usize, Fq)>,
+    wire_vars: usize,
+}
+
+/// A circuit together with the wiring predicates of its layers and a digest
+/// of its description, everything about proving it that does not depend on
+/// the inputs.
+#[derive(Debug, Clone, PartialEq)]
+pub struct PreprocessedCircuit {
+    circuit: Circuit,
+    wiring: Vec<Vec<SparseWiring>>,
+    digest: [u8; 32],
+}
+
+/// The prover and the verifier need the same wiring, so both keys are the
+/// preprocessed circuit.
+pub type ProvingKey = PreprocessedCircuit;
+pub type VerifyingKey = PreprocessedCircuit;
+
+impl Circuit {
+    /// Builds the wiring of every layer once, for proving the circuit on
+    /// many inputs.
+    pub fn preprocess(&self) -> PreprocessedCircuit {
+        let digest = Keccak256::digest(self.to_json().as_bytes()).into();
+
+        PreprocessedCircuit {
+            circuit: self.clone(),
+            wiring: layer_wiring(self),
+            digest,
+        }
+    }
+}
+
+impl PreprocessedCircuit {
+    pub fn circuit(&self) -> &Circuit {
+        &self.circuit
+    }
+
+    /// Each layer's wiring, one predicate per family in the order of
+    /// `Layer::families`.
+    pub fn wiring(&self) -> &[Vec<SparseWiring>] {
+        &self.wiring
+    }
+
+    /// A hash of the circuit description, which proofs absorb before
+    /// anything else.
+    pub fn digest(&self) -> &[u8; 32] {
+        &self.digest
+    }
+}
+
+/// The wiring of every layer of `circuit`, indexed like its layers.
+pub(crate) fn layer_wiring(circuit: &Circuit) -> Vec<Vec<SparseWiring>> {
+    circuit
+        .layers()
+        .iter()
+        .enumerate()
+        .map(|(index, layer)| {
+            let input_size = circuit.layer_input_size(index);
+
+            layer
+                .families()
+                .into_iter()
+                .map(|family| SparseWiring::new(layer, family, input_size))
+                .collect()
+        })
+        .collect()
+}
+
+impl SparseWiring {
+    pub fn new(layer: &Layer, family: GateFamily, input_size: usize) -> Self {
+        Self {
+            family,
+            entries: layer.wiring_entries(family, input_size),
+            wire_vars: layer.fan_in() * input_size.next_power_of_two().ilog2() as usize,
+        }
+    }
+
+    /// `Σ coefficient·wiring(point, b, c)` as a polynomial in the wires, the
+    /// same as folding the dense predicate, in time linear in the gates.
+    pub fn fold(&self, points: &[(Fq, Vec<Fq>)]) -> MultilinearPoly<Fq> {
+        let selectors = self.gate_selectors(points);
+
+        let mut evaluations = vec![Fq::from(0); wire_table_len(self.wire_vars)];
+        for (gate, wires, coefficient) in &self.entries {
+            evaluations[*wires] += selectors[*gate] * coefficient;
+        }
+
+        MultilinearPoly::new(evaluations)
+    }
+
+    /// `Σ coefficient·wiring(point, wire_point)`.
+    pub fn evaluate(&self, points: &[(Fq, Vec<Fq>)], wire_point: &[Fq]) -> Fq {
+        let selectors = self.gate_selectors(points);
+
+        self.entries
+            .iter()
+            .map(|(gate, wires, coefficient)| {
+                selectors[*gate] * coefficient * eq_at_index(wire_point, *wires)
+            })
+            .sum()
+    }
+
+    /// `Σ coefficient·eq(point, a)` for every gate `a`.
+    fn gate_selectors(&self, points: &[(Fq, Vec<Fq>)]) -> Vec<Fq> {
+        points
+            .iter()
+            .map(|(coefficient, point)| {
+                MultilinearPoly::eq_mle(point)
+                    .into_evaluations()
+                    .into_iter()
+                    .map(|eq| eq * coefficient)
+                    .collect::<Vec<_>>()
+            })
+            .reduce(|acc, table| acc.iter().zip(table).map(|(a, b)| *a + b).collect())
+            .unwrap()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use ark_bn254::Fq;
+
+    use super::WIRING_BUILDS;
+    use crate::{
+        circuit::{Circuit, Gate, GateOp, Layer},
+        gkr_protocol::{evaluate_wiring, fold_wiring},
+        layered_proof::{prove_circuit, verify_circuit},
+    };
+
+    fn sample_circuit() -> Circuit {
+        Circuit::new(
+            vec![
+                Layer::new(vec![
+                    Gate::new(GateOp::Add, 0, 1),
+                    Gate::new(GateOp::Mul, 0, 1),
+                ]),
+                Layer::new(vec![
+                    Gate::ternary(GateOp::Mul3, 0, 1, 3),
+                    Gate::new(GateOp::Sub, 2, 1),
+                    Gate::new(GateOp::Scale(Fq::from(5)), 3, 3),
+                ]),
+            ],
+            4,
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn it_matches_the_dense_wiring() {
+        let circuit = sample_circuit();
+        let key = circuit.preprocess();
+
+        let points = vec![
+            (Fq::from(3), vec![Fq::from(7), Fq::from(11)]),
+            (Fq::from(4), vec![Fq::from(2), Fq::from(9)]),
+        ];
+        let wire_point: Vec<Fq> = (1..=6u64).map(Fq::from).collect();
+
+        for wiring in &key.wiring()[1] {
+            let dense = circuit.layers()[1].wiring_mle(wiring.family, 4);
+
+            assert_eq!(wiring.fold(&points), fold_wiring(&dense, &points));
+            assert_eq!(
+                wiring.evaluate(&points, &wire_point),
+                evaluate_wiring(&dense, &points, &wire_point)
+            );
+        }
+    }
+
+    #[test]
+    fn it_proves_many_inputs_without_rebuilding_the_wiring() {
+        let key = sample_circuit().preprocess();
+        let builds = WIRING_BUILDS.with(|builds| builds.get());
+
+        for seed in 0..3u64 {
+            let inputs: Vec<Fq> = (0..4).map(|i| Fq::from(seed * 4 + i + 1)).collect();
+
+            let proof = prove_circuit(&key, &inputs);
+            assert_eq!(verify_circuit(&key, &inputs, proof), Ok(()));
+        }
+
+        assert_eq!(WIRING_BUILDS.with(|builds| builds.get()), builds);
+    }
+}
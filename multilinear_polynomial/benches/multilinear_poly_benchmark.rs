@@ -22,7 +22,7 @@ pub fn criterion_benchmark(c: &mut Criterion) {
     c.bench_function("MultilinearPoly evaluate", |b| {
         b.iter(|| {
             // Use black_box to prevent compiler optimizations from removing our computation.
-            let result = poly.evaluate(black_box(values.clone()));
+            let result = poly.evaluate(black_box(&values));
             black_box(result);
         })
     });
@@ -0,0 +1,42 @@
+use std::time::Instant;
+
+use ark_bn254::Fq;
+use ark_ff::UniformRand;
+use multilinear_polynomial::multilinear_polynomial_evaluation::MultilinearPoly;
+use rand::{rngs::StdRng, SeedableRng};
+
+/// Evaluates a table of 2^20 entries at 32 points, one `evaluate` at a time
+/// and with `evaluate_batch`. Run it with and without `--features parallel`:
+///
+///     cargo run --release --example batch_evaluation
+///     cargo run --release --example batch_evaluation --features parallel
+fn main() {
+    let num_vars = 20;
+    let mut rng = StdRng::seed_from_u64(0);
+
+    let polynomial = MultilinearPoly::new((0..1 << num_vars).map(|_| Fq::rand(&mut rng)).collect());
+    let points: Vec<Vec<Fq>> = (0..32)
+        .map(|_| (0..num_vars).map(|_| Fq::rand(&mut rng)).collect())
+        .collect();
+
+    let start = Instant::now();
+    let repeated: Vec<Fq> = points
+        .iter()
+        .map(|point| polynomial.evaluate(point))
+        .collect();
+    let repeated_time = start.elapsed();
+
+    let start = Instant::now();
+    let batched = polynomial.evaluate_batch(&points);
+    let batched_time = start.elapsed();
+
+    assert_eq!(batched, repeated);
+
+    println!(
+        "{} points over 2^{} entries: repeated {:?}, batched {:?}",
+        points.len(),
+        num_vars,
+        repeated_time,
+        batched_time
+    );
+}
@@ -0,0 +1,33 @@
+use std::time::Instant;
+
+use ark_bn254::Fq;
+use ark_ff::UniformRand;
+use multilinear_polynomial::multilinear_polynomial_evaluation::MultilinearPoly;
+use rand::{rngs::StdRng, SeedableRng};
+
+/// Evaluates a table of 2^22 entries at one point, with `evaluate` and with
+/// the eq-weight walk of `evaluate_streaming`:
+///
+///     cargo run --release --example streaming_evaluation
+fn main() {
+    let num_vars = 22;
+    let mut rng = StdRng::seed_from_u64(0);
+
+    let polynomial = MultilinearPoly::new((0..1 << num_vars).map(|_| Fq::rand(&mut rng)).collect());
+    let point: Vec<Fq> = (0..num_vars).map(|_| Fq::rand(&mut rng)).collect();
+
+    let start = Instant::now();
+    let folded = polynomial.evaluate(&point);
+    let folded_time = start.elapsed();
+
+    let start = Instant::now();
+    let streamed = polynomial.evaluate_streaming(&point);
+    let streamed_time = start.elapsed();
+
+    assert_eq!(streamed, folded);
+
+    println!(
+        "one point over 2^{} entries: evaluate {:?}, evaluate_streaming {:?}",
+        num_vars, folded_time, streamed_time
+    );
+}
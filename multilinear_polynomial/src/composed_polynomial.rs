@@ -1,17 +1,381 @@
+use std::fmt;
+
 use ark_ff::PrimeField;
+use ark_std::rand::RngCore;
+#[cfg(feature = "parallel")]
+use rayon::prelude::*;
+use univariate_polynomial::univariate_polynomial_dense::UnivariatePoly;
+
+use crate::{
+    multilinear_polynomial_evaluation::{checked_table_len, MultilinearPoly},
+    sparse_multilinear_polynomial::SparseMultilinearPoly,
+};
+
+/// Counts the dense tables bound while a test watches, so tests can see zero
+/// terms being skipped. Binds are counted on the watching thread and, under
+/// the `parallel` feature, on the workers of a pool of the test's own, so
+/// other tests binding at the same time are never counted.
+#[cfg(test)]
+pub(crate) mod table_folds {
+    use std::{
+        cell::Cell,
+        sync::{
+            atomic::{AtomicUsize, Ordering},
+            Mutex,
+        },
+    };
+
+    thread_local! {
+        static WATCHED: Cell<bool> = const { Cell::new(false) };
+    }
+
+    static TABLE_FOLDS: AtomicUsize = AtomicUsize::new(0);
+    static ONE_WATCHER: Mutex<()> = Mutex::new(());
+
+    pub(crate) fn record() {
+        if WATCHED.with(Cell::get) {
+            TABLE_FOLDS.fetch_add(1, Ordering::Relaxed);
+        }
+    }
+
+    /// Runs `op` and returns its result with the number of dense tables it
+    /// bound.
+    pub(crate) fn count<T: Send>(op: impl FnOnce() -> T + Send) -> (T, usize) {
+        let _watching = ONE_WATCHER
+            .lock()
+            .unwrap_or_else(|error| error.into_inner());
+        let before = TABLE_FOLDS.load(Ordering::Relaxed);
+
+        #[cfg(feature = "parallel")]
+        let result = rayon::ThreadPoolBuilder::new()
+            .num_threads(2)
+            .start_handler(|_| WATCHED.with(|watched| watched.set(true)))
+            .build()
+            .expect("a test thread pool")
+            .install(op);
+
+        #[cfg(not(feature = "parallel"))]
+        let result = {
+            WATCHED.with(|watched| watched.set(true));
+            let result = op();
+            WATCHED.with(|watched| watched.set(false));
+            result
+        };
+
+        (result, TABLE_FOLDS.load(Ordering::Relaxed) - before)
+    }
+}
 
-use crate::multilinear_polynomial_evaluation::MultilinearPoly;
+/// One factor of a product term, kept as a full table or as its nonzero
+/// entries, e.g. for a wiring predicate.
+#[derive(Clone, Debug, PartialEq)]
+pub enum Factor<F: PrimeField> {
+    Dense(MultilinearPoly<F>),
+    Sparse(SparseMultilinearPoly<F>),
+}
 
 #[derive(Clone, Debug, PartialEq)]
 pub struct ProductPoly<F: PrimeField> {
-    pub evaluation: Vec<MultilinearPoly<F>>,
+    pub evaluation: Vec<Factor<F>>,
 }
 
-#[derive(Clone, Debug, PartialEq)]
+/// Prints, and debug-prints, as its `shape` rather than its tables.
+#[derive(Clone, PartialEq)]
 pub struct SumPoly<F: PrimeField> {
     pub polys: Vec<ProductPoly<F>>,
 }
 
+/// The structure of a composed polynomial without its tables: each term's
+/// factors, in order. Prints as e.g.
+/// `SumPoly { terms: 2, term0: [mle(2 vars), sparse(2 vars)], term1: [zero(2 vars)] }`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct SumPolyShape {
+    pub terms: Vec<Vec<FactorShape>>,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct FactorShape {
+    pub num_vars: usize,
+    pub sparse: bool,
+    /// Whether the factor is zero everywhere.
+    pub zero: bool,
+}
+
+impl fmt::Display for SumPolyShape {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "SumPoly {{ terms: {}", self.terms.len())?;
+        for (index, term) in self.terms.iter().enumerate() {
+            write!(f, ", term{}: [", index)?;
+            for (position, factor) in term.iter().enumerate() {
+                if position > 0 {
+                    write!(f, ", ")?;
+                }
+                write!(f, "{}", factor)?;
+            }
+            write!(f, "]")?;
+        }
+
+        write!(f, " }}")
+    }
+}
+
+impl fmt::Display for FactorShape {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let kind = match (self.zero, self.sparse) {
+            (true, _) => "zero",
+            (false, true) => "sparse",
+            (false, false) => "mle",
+        };
+        let vars = match self.num_vars {
+            1 => "var",
+            _ => "vars",
+        };
+
+        write!(f, "{}({} {})", kind, self.num_vars, vars)
+    }
+}
+
+impl<F: PrimeField> fmt::Display for SumPoly<F> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.shape())
+    }
+}
+
+impl<F: PrimeField> fmt::Debug for SumPoly<F> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.shape())
+    }
+}
+
+/// Why a `SumPoly` built field by field does not range over one set of
+/// variables.
+#[derive(Debug, Clone, PartialEq)]
+pub enum ShapeError {
+    EmptySumPoly,
+    EmptyProduct {
+        term: usize,
+    },
+    MismatchedNumVars {
+        term: usize,
+        factor: usize,
+        expected: usize,
+        got: usize,
+    },
+    WrongPointLength {
+        expected: usize,
+        got: usize,
+    },
+    VariableOutOfRange {
+        index: usize,
+        num_vars: usize,
+    },
+}
+
+impl fmt::Display for ShapeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ShapeError::EmptySumPoly => write!(f, "composed polynomial has no terms"),
+            ShapeError::EmptyProduct { term } => write!(f, "term {} has no factors", term),
+            ShapeError::MismatchedNumVars {
+                term,
+                factor,
+                expected,
+                got,
+            } => write!(
+                f,
+                "factor {} of term {} has {} variables, expected {}",
+                factor, term, got, expected
+            ),
+            ShapeError::WrongPointLength { expected, got } => write!(
+                f,
+                "a point of {} values for a polynomial of {} variables",
+                got, expected
+            ),
+            ShapeError::VariableOutOfRange { index, num_vars } => write!(
+                f,
+                "variable {} of a polynomial of {} variables",
+                index, num_vars
+            ),
+        }
+    }
+}
+
+impl std::error::Error for ShapeError {}
+
+/// A sum of products of multilinear polynomials, as the GKR prover consumes
+/// it: round by round, binding the first variable each time. `SumPoly` and
+/// `VirtualPolynomial` both are one.
+pub trait ComposedPolynomial<F: PrimeField>: Clone + Sync {
+    fn num_vars(&self) -> Result<usize, ShapeError>;
+
+    /// The largest number of factors in any term.
+    fn degree_in_bound_variable(&self) -> usize;
+
+    /// Each term's factors as their number of variables and whether they
+    /// are sparse or zero, for error messages and tests.
+    fn shape(&self) -> SumPolyShape;
+
+    /// The degree in variable `index`: the most factors of one term that
+    /// depend on it, never more than `degree_in_bound_variable`.
+    fn degree_in_variable(&self, index: usize) -> usize;
+
+    fn hypercube_sum(&self) -> F;
+
+    /// Adds the polynomial at `x_0 = 0, 1, ..., sums.len() - 1`, with the
+    /// other variables at hypercube index `j`, into `sums`. `scratch` is
+    /// working space the caller keeps between calls.
+    fn add_round_values(&self, j: usize, sums: &mut [F], scratch: &mut Vec<F>);
+
+    /// Binds the first variable to `value`.
+    fn partial_evaluate(&self, value: &F) -> Self;
+
+    /// `partial_evaluate`, reusing the tables' storage where it can.
+    fn fold_in_place(&mut self, value: &F);
+
+    /// The sum-check round polynomial in the first variable, the others
+    /// summed over the hypercube, as its values at `0, 1, ..., d` for `d`
+    /// the degree in that variable, at least one. All the points come from
+    /// one pass over the tables, spread over threads under the `parallel`
+    /// feature. Panics if the polynomial is malformed.
+    fn round_evaluations(&self) -> Vec<F> {
+        let degree = self.degree_in_variable(0).max(1);
+        let num_vars = self.num_vars().unwrap_or_else(|error| panic!("{}", error));
+        let half = 1 << (num_vars - 1);
+
+        // The second vector is scratch space for the polynomial's own use.
+        let accumulate = |(mut sums, mut scratch): (Vec<F>, Vec<F>), j: usize| {
+            self.add_round_values(j, &mut sums, &mut scratch);
+
+            (sums, scratch)
+        };
+        let init = || (vec![F::zero(); degree + 1], Vec::new());
+
+        #[cfg(feature = "parallel")]
+        let evaluations = (0..half)
+            .into_par_iter()
+            .fold(init, accumulate)
+            .map(|(sums, _)| sums)
+            .reduce(
+                || vec![F::zero(); degree + 1],
+                |mut acc, sums| {
+                    acc.iter_mut().zip(sums).for_each(|(a, b)| *a += b);
+                    acc
+                },
+            );
+
+        #[cfg(not(feature = "parallel"))]
+        let evaluations = (0..half).fold(init(), accumulate).0;
+
+        evaluations
+    }
+}
+
+impl<F: PrimeField> Factor<F> {
+    pub fn num_of_vars(&self) -> usize {
+        match self {
+            Factor::Dense(poly) => poly.num_of_vars,
+            Factor::Sparse(poly) => poly.num_vars,
+        }
+    }
+
+    /// The value at hypercube index `index`.
+    pub fn get(&self, index: usize) -> F {
+        match self {
+            Factor::Dense(poly) => poly.evaluation[index],
+            Factor::Sparse(poly) => poly.get(index),
+        }
+    }
+
+    pub fn evaluate(&self, point: &[F]) -> F {
+        match self {
+            Factor::Dense(poly) => poly.evaluate(point),
+            Factor::Sparse(poly) => poly.evaluate(point),
+        }
+    }
+
+    /// Binds variable `bit`, which the caller knows is in range. A sparse
+    /// factor may come back dense.
+    pub(crate) fn bind(&self, bit: usize, value: &F) -> Self {
+        match self {
+            Factor::Dense(poly) => {
+                #[cfg(test)]
+                table_folds::record();
+                Factor::Dense(poly.bind(bit, value))
+            }
+            Factor::Sparse(poly) => poly.bind(bit, value),
+        }
+    }
+
+    fn bind_in_place(&mut self, bit: usize, value: &F) {
+        match self {
+            Factor::Dense(poly) => {
+                #[cfg(test)]
+                table_folds::record();
+                poly.bind_in_place(bit, value)
+            }
+            Factor::Sparse(poly) => *self = poly.bind(bit, value),
+        }
+    }
+
+    /// Whether the factor changes along variable `bit`, which the caller
+    /// knows is in range.
+    fn depends_on(&self, bit: usize) -> bool {
+        match self {
+            Factor::Dense(poly) => poly.depends_on(bit),
+            Factor::Sparse(poly) => poly.depends_on(bit),
+        }
+    }
+
+    pub fn shape(&self) -> FactorShape {
+        FactorShape {
+            num_vars: self.num_of_vars(),
+            sparse: matches!(self, Factor::Sparse(_)),
+            zero: self.is_zero(),
+        }
+    }
+
+    /// Whether the factor is zero everywhere. A dense factor keeps the
+    /// answer until its table changes.
+    pub fn is_zero(&self) -> bool {
+        match self {
+            Factor::Dense(poly) => poly.is_zero(),
+            Factor::Sparse(poly) => poly.entries().is_empty(),
+        }
+    }
+
+    pub fn to_dense(&self) -> MultilinearPoly<F> {
+        match self {
+            Factor::Dense(poly) => poly.clone(),
+            Factor::Sparse(poly) => poly.to_dense(),
+        }
+    }
+
+    fn scale(self, c: F) -> Self {
+        match self {
+            Factor::Dense(poly) => Factor::Dense(poly * c),
+            Factor::Sparse(poly) => Factor::Sparse(SparseMultilinearPoly::new(
+                poly.num_vars,
+                poly.entries()
+                    .iter()
+                    .map(|(index, value)| (*index, *value * c))
+                    .collect(),
+            )),
+        }
+    }
+}
+
+impl<F: PrimeField> From<MultilinearPoly<F>> for Factor<F> {
+    fn from(poly: MultilinearPoly<F>) -> Self {
+        Factor::Dense(poly)
+    }
+}
+
+impl<F: PrimeField> From<SparseMultilinearPoly<F>> for Factor<F> {
+    fn from(poly: SparseMultilinearPoly<F>) -> Self {
+        Factor::Sparse(poly)
+    }
+}
+
 impl<F: PrimeField> ProductPoly<F> {
     pub fn new(evaluations: Vec<Vec<F>>) -> Self {
         let length_1 = evaluations[0].len();
@@ -22,60 +386,404 @@ impl<F: PrimeField> ProductPoly<F> {
 
         let polys = evaluations
             .iter()
-            .map(|evaluation| MultilinearPoly::new(evaluation.to_vec()))
+            .map(|evaluation| Factor::Dense(MultilinearPoly::new(evaluation.to_vec())))
             .collect();
 
         Self { evaluation: polys }
     }
 
-    fn evaluate(&self, values: Vec<F>) -> F {
+    /// A product of `degree` random dense factors over `num_vars` variables.
+    pub fn rand<R: RngCore + ?Sized>(num_vars: usize, degree: usize, rng: &mut R) -> Self {
+        Self::from_factors(
+            (0..degree)
+                .map(|_| Factor::Dense(MultilinearPoly::rand(num_vars, rng)))
+                .collect(),
+        )
+    }
+
+    /// A product of dense and sparse factors, which must all range over the
+    /// same variables.
+    pub fn from_factors(factors: Vec<Factor<F>>) -> Self {
+        let num_vars = factors[0].num_of_vars();
+
+        if factors
+            .iter()
+            .any(|factor| factor.num_of_vars() != num_vars)
+        {
+            panic!("all factors must range over the same variables");
+        }
+
+        Self {
+            evaluation: factors,
+        }
+    }
+
+    fn evaluate(&self, point: &[F]) -> F {
         self.evaluation
             .iter()
-            .map(|poly| poly.evaluate(values.clone()))
+            .map(|poly| poly.evaluate(point))
             .product()
     }
 
+    /// Whether some factor is zero everywhere, which makes the term zero.
+    pub fn is_zero(&self) -> bool {
+        self.evaluation.iter().any(Factor::is_zero)
+    }
+
+    /// A zero term over `num_vars` variables with as many factors as this
+    /// one, so the degree is unchanged, none of which has a table.
+    fn zeroed(&self, num_vars: usize) -> Self {
+        Self {
+            evaluation: vec![
+                Factor::Sparse(SparseMultilinearPoly::new(num_vars, vec![]));
+                self.evaluation.len()
+            ],
+        }
+    }
+
     fn partial_evaluate(&self, value: &F) -> Self {
+        if self.is_zero() {
+            return self.zeroed(self.evaluation[0].num_of_vars() - 1);
+        }
+
+        #[cfg(feature = "parallel")]
         let partial_polys = self
             .evaluation
-            .iter()
-            .map(|poly| {
-                let partial_res = poly.partial_evaluate(0, value);
+            .par_iter()
+            .map(|poly| poly.bind(0, value))
+            .collect();
 
-                partial_res.evaluation
-            })
+        #[cfg(not(feature = "parallel"))]
+        let partial_polys = self
+            .evaluation
+            .iter()
+            .map(|poly| poly.bind(0, value))
             .collect();
 
-        Self::new(partial_polys)
+        Self::from_factors(partial_polys)
     }
 
-    fn reduce(&self) -> Vec<F> {
-        (self.evaluation[0].clone() * self.evaluation[1].clone()).evaluation
+    fn fold_in_place(&mut self, value: &F) {
+        if self.is_zero() {
+            *self = self.zeroed(self.evaluation[0].num_of_vars() - 1);
+            return;
+        }
+
+        #[cfg(feature = "parallel")]
+        self.evaluation
+            .par_iter_mut()
+            .for_each(|poly| poly.bind_in_place(0, value));
+
+        #[cfg(not(feature = "parallel"))]
+        for poly in self.evaluation.iter_mut() {
+            poly.bind_in_place(0, value);
+        }
+    }
+
+    /// The table of the product: entry `i` is the product of every factor's
+    /// entry `i`. Agrees with `evaluate` on the hypercube only; off it the
+    /// product has degree above one in each variable. Panics if the term has
+    /// no factors or they range over different variables.
+    pub fn reduce(&self) -> MultilinearPoly<F> {
+        MultilinearPoly::new(self.reduce_evaluations())
+    }
+
+    fn reduce_evaluations(&self) -> Vec<F> {
+        let (first, rest) = self
+            .evaluation
+            .split_first()
+            .expect("a product term has at least one factor");
+        if rest
+            .iter()
+            .any(|factor| factor.num_of_vars() != first.num_of_vars())
+        {
+            panic!("all factors must range over the same variables");
+        }
+
+        let len = checked_table_len(first.num_of_vars());
+        match self.is_zero() {
+            true => vec![F::zero(); len],
+            false => sum_of_term_tables(&[self], len),
+        }
+    }
+
+    /// The product of the factors at hypercube index `index`.
+    fn value_at(&self, index: usize) -> F {
+        self.evaluation
+            .iter()
+            .map(|factor| match factor {
+                Factor::Dense(poly) => poly.evaluation[index],
+                Factor::Sparse(poly) => poly.get(index),
+            })
+            .product()
     }
 
     fn get_degree(&self) -> usize {
         self.evaluation.len()
     }
+
+    /// Adds the term at `x_0 = 0, 1, ..., sums.len() - 1`, with the other
+    /// variables at hypercube index `j`, into `sums`. Along the bound
+    /// variable each factor is the line `a + x·(b - a)`, so its values are
+    /// reached by repeatedly adding `b - a`.
+    fn add_round_values(&self, j: usize, half: usize, sums: &mut [F], scratch: &mut Vec<F>) {
+        scratch.clear();
+        scratch.resize(sums.len(), F::one());
+
+        for factor in &self.evaluation {
+            let a = factor.get(j);
+            let step = factor.get(j + half) - a;
+            let mut value = a;
+
+            for term in scratch.iter_mut() {
+                *term *= value;
+                value += step;
+            }
+        }
+
+        for (sum, term) in sums.iter_mut().zip(scratch.iter()) {
+            *sum += term;
+        }
+    }
+
+    /// The term's contribution to a sum-check round: the univariate in the
+    /// first variable left by summing the product over the other variables,
+    /// in coefficient form. Found from its values at as many points as it
+    /// has factors plus one. Panics if the factors range over different
+    /// variables, or none.
+    pub fn round_univariate(&self) -> UnivariatePoly<F> {
+        let (first, rest) = self
+            .evaluation
+            .split_first()
+            .expect("a product term has at least one factor");
+        let num_vars = first.num_of_vars();
+        if rest.iter().any(|factor| factor.num_of_vars() != num_vars) {
+            panic!("all factors must range over the same variables");
+        }
+        if num_vars == 0 {
+            panic!("{}", ShapeError::VariableOutOfRange { index: 0, num_vars });
+        }
+
+        let half = 1 << (num_vars - 1);
+        let mut sums = vec![F::zero(); self.get_degree() + 1];
+        let mut scratch = Vec::new();
+        if !self.is_zero() {
+            for j in 0..half {
+                self.add_round_values(j, half, &mut sums, &mut scratch);
+            }
+        }
+
+        interpolate_from_zero(sums)
+    }
+}
+
+/// The polynomial through `(i, evaluations[i])`.
+fn interpolate_from_zero<F: PrimeField>(evaluations: Vec<F>) -> UnivariatePoly<F> {
+    UnivariatePoly::interpolate(
+        evaluations
+            .into_iter()
+            .enumerate()
+            .map(|(i, y)| (F::from(i as u64), y))
+            .collect(),
+    )
+    .expect("a round has at least one evaluation at distinct points")
 }
 
 impl<F: PrimeField> SumPoly<F> {
+    /// Product terms may have different numbers of factors, but all range
+    /// over the same variables.
     pub fn new(polys: Vec<ProductPoly<F>>) -> Self {
-        let degree_1 = polys[0].get_degree();
-        if polys.iter().any(|poly| poly.get_degree() != degree_1) {
-            panic!("all product polys must have same degree");
+        let num_vars = polys[0].evaluation[0].num_of_vars();
+        if polys
+            .iter()
+            .any(|poly| poly.evaluation[0].num_of_vars() != num_vars)
+        {
+            panic!("all product polys must range over the same variables");
         }
 
         Self { polys }
     }
 
-    pub fn evaluate(&self, values: Vec<F>) -> F {
-        self.polys
+    /// A sum of `num_terms` random products of `degree` factors each, over
+    /// `num_vars` variables.
+    pub fn rand<R: RngCore + ?Sized>(
+        num_vars: usize,
+        num_terms: usize,
+        degree: usize,
+        rng: &mut R,
+    ) -> Self {
+        Self::new(
+            (0..num_terms)
+                .map(|_| ProductPoly::rand(num_vars, degree, rng))
+                .collect(),
+        )
+    }
+
+    /// The zero polynomial over `num_vars` variables, to build on with
+    /// `add_product` and `add`. It is a single term whose sparse factor has
+    /// no entries, and the first real term replaces it.
+    pub fn zero(num_vars: usize) -> Self {
+        Self {
+            polys: vec![ProductPoly {
+                evaluation: vec![Factor::Sparse(SparseMultilinearPoly::new(num_vars, vec![]))],
+            }],
+        }
+    }
+
+    /// Adds the term `Π factors`. Fails if there are no factors or any of
+    /// them ranges over different variables than the polynomial.
+    pub fn add_product(self, factors: Vec<MultilinearPoly<F>>) -> Result<Self, ShapeError> {
+        let num_vars = self.num_vars()?;
+        let term = self.polys.len();
+
+        if factors.is_empty() {
+            return Err(ShapeError::EmptyProduct { term });
+        }
+
+        if let Some((factor, poly)) = factors
             .iter()
-            .map(|poly| poly.evaluate(values.clone()))
-            .sum()
+            .enumerate()
+            .find(|(_, poly)| poly.num_vars() != num_vars)
+        {
+            return Err(ShapeError::MismatchedNumVars {
+                term,
+                factor,
+                expected: num_vars,
+                got: poly.num_vars(),
+            });
+        }
+
+        let product = ProductPoly {
+            evaluation: factors.into_iter().map(Factor::Dense).collect(),
+        };
+
+        Ok(self.with_terms(num_vars, vec![product]))
+    }
+
+    /// `self + other`, keeping the terms of both. Fails if either is
+    /// malformed or they range over different variables, which is why this
+    /// is not `Add`.
+    #[allow(clippy::should_implement_trait)]
+    pub fn add(self, other: Self) -> Result<Self, ShapeError> {
+        let num_vars = self.num_vars()?;
+        let other_vars = other.num_vars()?;
+
+        if other_vars != num_vars {
+            return Err(ShapeError::MismatchedNumVars {
+                term: self.polys.len(),
+                factor: 0,
+                expected: num_vars,
+                got: other_vars,
+            });
+        }
+
+        Ok(self.with_terms(num_vars, other.polys))
+    }
+
+    /// `c·self`, scaling the first factor of every term. Fails if the
+    /// polynomial is malformed.
+    pub fn scale(self, c: F) -> Result<Self, ShapeError> {
+        self.num_vars()?;
+
+        let polys = self
+            .polys
+            .into_iter()
+            .map(|mut product| {
+                let first = product.evaluation.remove(0);
+                product.evaluation.insert(0, first.scale(c));
+
+                product
+            })
+            .collect();
+
+        Ok(Self { polys })
+    }
+
+    /// Appends `terms`, dropping any `zero` placeholder term unless nothing
+    /// else is left.
+    fn with_terms(mut self, num_vars: usize, terms: Vec<ProductPoly<F>>) -> Self {
+        let is_placeholder = |product: &ProductPoly<F>| {
+            matches!(
+                product.evaluation.as_slice(),
+                [Factor::Sparse(poly)] if poly.entries().is_empty()
+            )
+        };
+
+        self.polys.extend(terms);
+        self.polys.retain(|product| !is_placeholder(product));
+
+        match self.polys.is_empty() {
+            true => Self::zero(num_vars),
+            false => self,
+        }
     }
 
+    /// The number of variables every factor ranges over. The fields are
+    /// public, so this checks they all agree rather than trusting the first.
+    pub fn num_vars(&self) -> Result<usize, ShapeError> {
+        let num_vars = self
+            .polys
+            .first()
+            .ok_or(ShapeError::EmptySumPoly)?
+            .evaluation
+            .first()
+            .ok_or(ShapeError::EmptyProduct { term: 0 })?
+            .num_of_vars();
+
+        for (term, product) in self.polys.iter().enumerate() {
+            if product.evaluation.is_empty() {
+                return Err(ShapeError::EmptyProduct { term });
+            }
+
+            for (factor, poly) in product.evaluation.iter().enumerate() {
+                if poly.num_of_vars() != num_vars {
+                    return Err(ShapeError::MismatchedNumVars {
+                        term,
+                        factor,
+                        expected: num_vars,
+                        got: poly.num_of_vars(),
+                    });
+                }
+            }
+        }
+
+        Ok(num_vars)
+    }
+
+    /// `Σ_terms Π_factors factor(point)`, evaluating each factor once.
+    /// Fails if the factors disagree on their variables or `point` does not
+    /// have one value per variable.
+    pub fn try_evaluate(&self, point: &[F]) -> Result<F, ShapeError> {
+        let num_vars = self.num_vars()?;
+        if point.len() != num_vars {
+            return Err(ShapeError::WrongPointLength {
+                expected: num_vars,
+                got: point.len(),
+            });
+        }
+
+        Ok(self.polys.iter().map(|poly| poly.evaluate(point)).sum())
+    }
+
+    /// `try_evaluate` for a well-formed polynomial and point. Panics
+    /// otherwise.
+    pub fn evaluate(&self, point: &[F]) -> F {
+        self.try_evaluate(point)
+            .unwrap_or_else(|error| panic!("{}", error))
+    }
+
+    /// Binds the first variable to `value`. Under the `parallel` feature the
+    /// terms, and the factors within each, are bound in parallel; the result
+    /// is the same either way.
     pub fn partial_evaluate(&self, value: &F) -> Self {
+        #[cfg(feature = "parallel")]
+        let partial_polys = self
+            .polys
+            .par_iter()
+            .map(|product_poly| product_poly.partial_evaluate(value))
+            .collect();
+
+        #[cfg(not(feature = "parallel"))]
         let partial_polys = self
             .polys
             .iter()
@@ -85,29 +793,252 @@ impl<F: PrimeField> SumPoly<F> {
         Self::new(partial_polys)
     }
 
-    pub fn reduce(&self) -> Vec<F> {
-        let poly_a = &self.polys[0].reduce();
-        let poly_b = &self.polys[1].reduce();
+    /// Binds variable `index` of every factor to `value`, leaving a
+    /// polynomial in the other variables in their original order. Fails if
+    /// the factors disagree on their variables or `index` is not one of them.
+    pub fn partial_evaluate_at(&self, index: usize, value: &F) -> Result<Self, ShapeError> {
+        let num_vars = self.num_vars()?;
+        if index >= num_vars {
+            return Err(ShapeError::VariableOutOfRange { index, num_vars });
+        }
+
+        Ok(self.bind_each(num_vars - 1, |factor| factor.bind(index, value)))
+    }
+
+    /// Binds the first `rs.len()` variables to `rs`. Fails if the factors
+    /// disagree on their variables or there are fewer than `rs.len()`.
+    pub fn restrict_prefix(&self, rs: &[F]) -> Result<Self, ShapeError> {
+        let num_vars = self.num_vars()?;
+        if rs.len() > num_vars {
+            return Err(ShapeError::VariableOutOfRange {
+                index: rs.len() - 1,
+                num_vars,
+            });
+        }
+
+        Ok(self.bind_each(num_vars - rs.len(), |factor| {
+            let mut factor = factor.clone();
+            for r in rs {
+                factor.bind_in_place(0, r);
+            }
+
+            factor
+        }))
+    }
 
-        let result = poly_a
+    /// Applies `bind` to every factor of the nonzero terms, leaving
+    /// `num_vars` variables.
+    fn bind_each(&self, num_vars: usize, bind: impl Fn(&Factor<F>) -> Factor<F>) -> Self {
+        let polys = self
+            .polys
             .iter()
-            .zip(poly_b.iter())
-            .map(|(a, b)| *a + *b)
+            .map(|product| match product.is_zero() {
+                true => product.zeroed(num_vars),
+                false => ProductPoly {
+                    evaluation: product.evaluation.iter().map(&bind).collect(),
+                },
+            })
             .collect();
 
-        result
+        Self { polys }
+    }
+
+    /// Binds the first variable to `value` like `partial_evaluate`, reusing
+    /// the tables' storage instead of allocating new ones.
+    pub fn fold_in_place(&mut self, value: &F) {
+        #[cfg(feature = "parallel")]
+        self.polys
+            .par_iter_mut()
+            .for_each(|product_poly| product_poly.fold_in_place(value));
+
+        #[cfg(not(feature = "parallel"))]
+        for product_poly in self.polys.iter_mut() {
+            product_poly.fold_in_place(value);
+        }
+    }
+
+    /// The table of the whole polynomial: entry `i` is the sum over terms of
+    /// the product of that term's factors at `i`, so its hypercube sum is the
+    /// polynomial's. Terms may have different numbers of factors, but every
+    /// factor must range over the same variables; panics otherwise.
+    pub fn reduce(&self) -> MultilinearPoly<F> {
+        self.num_vars().unwrap_or_else(|error| panic!("{}", error));
+
+        MultilinearPoly::new(self.reduce_evaluations())
+    }
+
+    /// `reduce` as a bare table.
+    #[deprecated(note = "use `reduce`, which returns a `MultilinearPoly`")]
+    pub fn reduce_to_vec(&self) -> Vec<F> {
+        self.reduce().into_evaluations()
+    }
+
+    /// Zero terms are left out rather than multiplied out.
+    fn reduce_evaluations(&self) -> Vec<F> {
+        let live: Vec<&ProductPoly<F>> = self.polys.iter().filter(|poly| !poly.is_zero()).collect();
+        let len = checked_table_len(self.polys[0].evaluation[0].num_of_vars());
+
+        sum_of_term_tables(&live, len)
     }
 
+    /// The largest number of factors in any product term. Each factor is
+    /// linear in the variable a sum-check round binds, so this bounds the
+    /// degree of every round polynomial. It is computed from the terms, never
+    /// stored, so it cannot fall out of step with them. Panics if there are
+    /// no terms.
+    pub fn degree_in_bound_variable(&self) -> usize {
+        self.polys
+            .iter()
+            .map(|poly| poly.get_degree())
+            .max()
+            .expect("a composed polynomial has at least one term")
+    }
+
+    /// The degree in variable `index`: the most factors of one nonzero term
+    /// whose tables change along it. A factor such as `W(b)` in a layer
+    /// polynomial over `(b, c)` is constant in `c`, so the `c` variables can
+    /// have a lower degree than `degree_in_bound_variable`. Panics if the
+    /// factors disagree on their variables or `index` is not one of them.
+    pub fn degree_in_variable(&self, index: usize) -> usize {
+        let num_vars = self.num_vars().unwrap_or_else(|error| panic!("{}", error));
+        if index >= num_vars {
+            panic!("{}", ShapeError::VariableOutOfRange { index, num_vars });
+        }
+
+        self.polys
+            .iter()
+            .filter(|poly| !poly.is_zero())
+            .map(|poly| {
+                poly.evaluation
+                    .iter()
+                    .filter(|factor| factor.depends_on(index))
+                    .count()
+            })
+            .max()
+            .unwrap_or(0)
+    }
+
+    /// The structure of the terms, without their tables. Unlike `num_vars`
+    /// it describes a malformed polynomial too.
+    pub fn shape(&self) -> SumPolyShape {
+        SumPolyShape {
+            terms: self
+                .polys
+                .iter()
+                .map(|poly| poly.evaluation.iter().map(Factor::shape).collect())
+                .collect(),
+        }
+    }
+
+    /// The sum-check round polynomial in the first variable in coefficient
+    /// form: the sum of the terms' `round_univariate`s, interpolated from the
+    /// round message `gkr_prove` sends. Panics if the polynomial is
+    /// malformed or has no variables.
+    pub fn round_univariate(&self) -> UnivariatePoly<F> {
+        let num_vars = self.num_vars().unwrap_or_else(|error| panic!("{}", error));
+        if num_vars == 0 {
+            panic!("{}", ShapeError::VariableOutOfRange { index: 0, num_vars });
+        }
+
+        interpolate_from_zero(ComposedPolynomial::round_evaluations(self))
+    }
+
+    /// `degree_in_bound_variable`.
     pub fn get_degree(&self) -> usize {
-        self.polys[0].get_degree()
+        self.degree_in_bound_variable()
+    }
+}
+
+/// The table `Σ_terms Π_factors` of `terms`, of `len` entries. Terms of
+/// dense factors only are summed entry by entry, in parallel over the entries
+/// under the `parallel` feature and with no allocation per entry. A term with
+/// a sparse factor is zero off that factor's entries, so it is added at
+/// those alone. Field addition is exact, so the table does not depend on how
+/// the work is split.
+fn sum_of_term_tables<F: PrimeField>(terms: &[&ProductPoly<F>], len: usize) -> Vec<F> {
+    let (dense, sparse): (Vec<&ProductPoly<F>>, Vec<&ProductPoly<F>>) =
+        terms.iter().partition(|term| {
+            term.evaluation
+                .iter()
+                .all(|factor| matches!(factor, Factor::Dense(_)))
+        });
+
+    let entry = |index: usize| -> F { dense.iter().map(|term| term.value_at(index)).sum() };
+
+    #[cfg(feature = "parallel")]
+    let mut table: Vec<F> = (0..len).into_par_iter().map(entry).collect();
+
+    #[cfg(not(feature = "parallel"))]
+    let mut table: Vec<F> = (0..len).map(entry).collect();
+
+    for term in sparse {
+        let support = term
+            .evaluation
+            .iter()
+            .find_map(|factor| match factor {
+                Factor::Sparse(poly) => Some(poly.entries()),
+                Factor::Dense(_) => None,
+            })
+            .expect("the term has a sparse factor");
+
+        for (index, _) in support {
+            table[*index] += term.value_at(*index);
+        }
+    }
+
+    table
+}
+
+impl<F: PrimeField> ComposedPolynomial<F> for SumPoly<F> {
+    fn num_vars(&self) -> Result<usize, ShapeError> {
+        SumPoly::num_vars(self)
+    }
+
+    fn degree_in_bound_variable(&self) -> usize {
+        SumPoly::degree_in_bound_variable(self)
+    }
+
+    fn degree_in_variable(&self, index: usize) -> usize {
+        SumPoly::degree_in_variable(self, index)
+    }
+
+    fn shape(&self) -> SumPolyShape {
+        SumPoly::shape(self)
+    }
+
+    fn hypercube_sum(&self) -> F {
+        self.reduce().hypercube_sum()
+    }
+
+    fn add_round_values(&self, j: usize, sums: &mut [F], scratch: &mut Vec<F>) {
+        let half = 1 << (self.polys[0].evaluation[0].num_of_vars() - 1);
+
+        // A zero term adds nothing.
+        for product in self.polys.iter().filter(|product| !product.is_zero()) {
+            product.add_round_values(j, half, sums, scratch);
+        }
+    }
+
+    fn partial_evaluate(&self, value: &F) -> Self {
+        SumPoly::partial_evaluate(self, value)
+    }
+
+    fn fold_in_place(&mut self, value: &F) {
+        SumPoly::fold_in_place(self, value)
     }
 }
 
 #[cfg(test)]
 mod test {
     use ark_bn254::Fq;
+    use ark_ff::UniformRand;
+    use rand::{rngs::StdRng, SeedableRng};
 
-    use super::{ProductPoly, SumPoly};
+    use super::{table_folds, ComposedPolynomial, Factor, ProductPoly, ShapeError, SumPoly};
+    use crate::{
+        multilinear_polynomial_evaluation::MultilinearPoly,
+        sparse_multilinear_polynomial::SparseMultilinearPoly,
+    };
 
     #[test]
     fn product_poly_evaluates_multiple_polys() {
@@ -122,7 +1053,7 @@ mod test {
 
         let expected_evaluation = Fq::from(216);
 
-        let result = product_polys.evaluate(values);
+        let result = product_polys.evaluate(&values);
 
         assert_eq!(expected_evaluation, result);
     }
@@ -148,7 +1079,7 @@ mod test {
         let result_polys: Vec<_> = result
             .evaluation
             .iter()
-            .map(|poly| poly.evaluation.clone())
+            .map(|poly| poly.to_dense().evaluation)
             .collect();
 
         assert_eq!(result_polys, expected_evaluation);
@@ -175,10 +1106,271 @@ mod test {
     }
 
     #[test]
-    fn product_poly_gets_correct_degree() {}
+    fn product_poly_gets_correct_degree() {
+        let product_poly = ProductPoly::new(vec![
+            vec![Fq::from(1), Fq::from(2)],
+            vec![Fq::from(3), Fq::from(4)],
+            vec![Fq::from(5), Fq::from(6)],
+        ]);
+
+        assert_eq!(product_poly.get_degree(), 3);
+    }
+
+    #[test]
+    fn sum_poly_gets_correct_degree() {
+        let sum_poly = SumPoly::new(vec![
+            ProductPoly::new(vec![vec![Fq::from(1), Fq::from(2)]]),
+            ProductPoly::new(vec![
+                vec![Fq::from(3), Fq::from(4)],
+                vec![Fq::from(5), Fq::from(6)],
+                vec![Fq::from(7), Fq::from(8)],
+            ]),
+            ProductPoly::new(vec![
+                vec![Fq::from(9), Fq::from(1)],
+                vec![Fq::from(2), Fq::from(3)],
+            ]),
+        ]);
+
+        assert_eq!(sum_poly.degree_in_bound_variable(), 3);
+        assert_eq!(sum_poly.get_degree(), 3);
+    }
+
+    #[test]
+    fn sum_poly_reports_its_number_of_variables() {
+        let sum_poly = SumPoly::new(vec![
+            ProductPoly::new(vec![vec![Fq::from(1), Fq::from(2)]]),
+            ProductPoly::new(vec![
+                vec![Fq::from(3), Fq::from(4)],
+                vec![Fq::from(5), Fq::from(6)],
+            ]),
+        ]);
+        assert_eq!(sum_poly.num_vars(), Ok(1));
+
+        assert_eq!(
+            SumPoly::<Fq> { polys: vec![] }.num_vars(),
+            Err(ShapeError::EmptySumPoly)
+        );
+
+        let mismatched = SumPoly {
+            polys: vec![
+                ProductPoly::new(vec![vec![Fq::from(1), Fq::from(2)]]),
+                ProductPoly {
+                    evaluation: vec![
+                        MultilinearPoly::new(vec![Fq::from(3), Fq::from(4)]).into(),
+                        MultilinearPoly::new(vec![Fq::from(0); 4]).into(),
+                    ],
+                },
+            ],
+        };
+        let error = mismatched.num_vars().unwrap_err();
+        assert_eq!(
+            error,
+            ShapeError::MismatchedNumVars {
+                term: 1,
+                factor: 1,
+                expected: 1,
+                got: 2
+            }
+        );
+        assert_eq!(
+            error.to_string(),
+            "factor 1 of term 1 has 2 variables, expected 1"
+        );
+    }
+
+    #[test]
+    fn sum_poly_folds_in_place_like_partial_evaluate() {
+        let mut sum_poly = SumPoly::new(vec![
+            ProductPoly::new(vec![vec![
+                Fq::from(1),
+                Fq::from(2),
+                Fq::from(3),
+                Fq::from(5),
+            ]]),
+            ProductPoly::new(vec![
+                vec![Fq::from(0), Fq::from(3), Fq::from(1), Fq::from(6)],
+                vec![Fq::from(1), Fq::from(1), Fq::from(5), Fq::from(2)],
+            ]),
+        ]);
+        let expected = sum_poly.partial_evaluate(&Fq::from(7));
+
+        sum_poly.fold_in_place(&Fq::from(7));
+
+        assert_eq!(sum_poly, expected);
+    }
+
+    #[test]
+    fn random_sum_polys_have_the_requested_shape() {
+        let mut rng = StdRng::seed_from_u64(74);
+        let sum_poly = SumPoly::<Fq>::rand(4, 3, 2, &mut rng);
+
+        assert_eq!(sum_poly.num_vars(), Ok(4));
+        assert_eq!(sum_poly.polys.len(), 3);
+        assert_eq!(sum_poly.get_degree(), 2);
+
+        let point: Vec<Fq> = (0..4).map(|_| Fq::rand(&mut rng)).collect();
+        let expected: Fq = sum_poly
+            .polys
+            .iter()
+            .map(|product| {
+                product
+                    .evaluation
+                    .iter()
+                    .map(|factor| factor.evaluate(&point))
+                    .product::<Fq>()
+            })
+            .sum();
+        assert_eq!(sum_poly.evaluate(&point), expected);
+
+        // The same seed gives the same polynomial.
+        assert_eq!(
+            SumPoly::<Fq>::rand(4, 3, 2, &mut StdRng::seed_from_u64(74)),
+            sum_poly
+        );
+    }
+
+    #[test]
+    fn sum_poly_takes_sparse_factors() {
+        let wiring = SparseMultilinearPoly::new(3, vec![(1, Fq::from(2)), (6, Fq::from(5))]);
+        let table = MultilinearPoly::new((1..=8u64).map(Fq::from).collect());
+
+        let mixed = SumPoly::new(vec![
+            ProductPoly::from_factors(vec![wiring.clone().into(), table.clone().into()]),
+            ProductPoly::new(vec![table.evaluation.clone()]),
+        ]);
+        let dense = SumPoly::new(vec![
+            ProductPoly::new(vec![wiring.to_dense().evaluation, table.evaluation.clone()]),
+            ProductPoly::new(vec![table.evaluation.clone()]),
+        ]);
+
+        assert_eq!(mixed.reduce(), dense.reduce());
+
+        let point = [Fq::from(3), Fq::from(5), Fq::from(7)];
+        assert_eq!(mixed.evaluate(&point), dense.evaluate(&point));
+
+        let mut folded = mixed.clone();
+        folded.fold_in_place(&Fq::from(4));
+        assert_eq!(folded, mixed.partial_evaluate(&Fq::from(4)));
+        assert_eq!(
+            folded.reduce(),
+            dense.partial_evaluate(&Fq::from(4)).reduce()
+        );
+        assert!(matches!(folded.polys[0].evaluation[0], Factor::Dense(_)));
+    }
+
+    #[test]
+    fn sum_poly_reduces_terms_of_different_degrees() {
+        let sum_poly = SumPoly::new(vec![
+            ProductPoly::new(vec![vec![Fq::from(1), Fq::from(2)]]),
+            ProductPoly::new(vec![
+                vec![Fq::from(3), Fq::from(4)],
+                vec![Fq::from(5), Fq::from(6)],
+                vec![Fq::from(7), Fq::from(8)],
+            ]),
+        ]);
+
+        assert_eq!(
+            sum_poly.reduce(),
+            MultilinearPoly::new(vec![Fq::from(106), Fq::from(194)])
+        );
+    }
 
     #[test]
-    fn sum_poly_gets_correct_degree() {}
+    fn reduce_agrees_with_the_factors_on_the_hypercube() {
+        let mut rng = StdRng::seed_from_u64(87);
+
+        for num_vars in 0..5 {
+            let sparse = SparseMultilinearPoly::new(num_vars, vec![(0, Fq::from(3))]);
+            let sum_poly = SumPoly::new(vec![
+                ProductPoly::rand(num_vars, 1, &mut rng),
+                ProductPoly::rand(num_vars, 3, &mut rng),
+                ProductPoly::from_factors(vec![
+                    Factor::Dense(MultilinearPoly::rand(num_vars, &mut rng)),
+                    Factor::Sparse(sparse),
+                ]),
+            ]);
+            let reduced = sum_poly.reduce();
+
+            for index in 0..1usize << num_vars {
+                let point: Vec<Fq> = (0..num_vars)
+                    .map(|i| Fq::from((index >> (num_vars - 1 - i) & 1) as u64))
+                    .collect();
+
+                let expected: Fq = sum_poly
+                    .polys
+                    .iter()
+                    .map(|term| {
+                        term.evaluation
+                            .iter()
+                            .map(|factor| factor.evaluate(&point))
+                            .product::<Fq>()
+                    })
+                    .sum();
+                assert_eq!(reduced.evaluate(&point), expected);
+            }
+
+            let terms: Vec<MultilinearPoly<Fq>> =
+                sum_poly.polys.iter().map(|term| term.reduce()).collect();
+            let summed = terms.into_iter().reduce(|sum, term| sum + term).unwrap();
+            assert_eq!(summed, reduced);
+        }
+    }
+
+    #[test]
+    fn the_builder_matches_the_raw_terms() {
+        let mut rng = StdRng::seed_from_u64(89);
+        let [f, g, h] = [(); 3].map(|_| MultilinearPoly::<Fq>::rand(3, &mut rng));
+        let c = Fq::from(7);
+
+        let built = SumPoly::zero(3)
+            .add_product(vec![f.clone(), g.clone()])
+            .unwrap()
+            .add(SumPoly::zero(3).add_product(vec![h.clone()]).unwrap())
+            .unwrap()
+            .scale(c)
+            .unwrap();
+        let raw = SumPoly::new(vec![
+            ProductPoly::from_factors(vec![Factor::Dense(f.clone() * c), Factor::Dense(g)]),
+            ProductPoly::from_factors(vec![Factor::Dense(h * c)]),
+        ]);
+        assert_eq!(built, raw);
+
+        for _ in 0..4 {
+            let point: Vec<Fq> = (0..3).map(|_| Fq::rand(&mut rng)).collect();
+            assert_eq!(built.evaluate(&point), raw.evaluate(&point));
+        }
+
+        let zero = SumPoly::<Fq>::zero(2).add(SumPoly::zero(2)).unwrap();
+        assert_eq!(zero, SumPoly::zero(2));
+        assert_eq!(zero.reduce(), MultilinearPoly::zero(2));
+
+        assert_eq!(
+            SumPoly::zero(3).add_product(vec![f.clone(), MultilinearPoly::zero(2)]),
+            Err(ShapeError::MismatchedNumVars {
+                term: 1,
+                factor: 1,
+                expected: 3,
+                got: 2
+            })
+        );
+        assert_eq!(
+            SumPoly::<Fq>::zero(3).add_product(vec![]),
+            Err(ShapeError::EmptyProduct { term: 1 })
+        );
+        assert!(SumPoly::<Fq>::zero(3).add(SumPoly::zero(2)).is_err());
+    }
+
+    #[test]
+    #[should_panic(expected = "factor 1 of term 0 has 2 variables, expected 1")]
+    fn reducing_mismatched_factors_panics() {
+        let mut sum_poly = SumPoly::new(vec![ProductPoly::new(vec![
+            vec![Fq::from(1), Fq::from(2)],
+            vec![Fq::from(3), Fq::from(4)],
+        ])]);
+        sum_poly.polys[0].evaluation[1] = Factor::Dense(MultilinearPoly::zero(2));
+
+        sum_poly.reduce();
+    }
 
     #[test]
     fn sum_poly_evaluates_properly() {
@@ -201,11 +1393,110 @@ mod test {
 
         let expected_result = Fq::from(936);
 
-        let result = sum_poly.evaluate(values);
+        let result = sum_poly.evaluate(&values);
 
         assert_eq!(expected_result, result);
     }
 
+    #[test]
+    fn sum_poly_evaluates_like_binding_every_variable() {
+        let mut rng = StdRng::seed_from_u64(86);
+
+        for (num_vars, num_terms, degree) in [(1, 1, 1), (3, 2, 3), (5, 3, 2)] {
+            let sum_poly = SumPoly::<Fq>::rand(num_vars, num_terms, degree, &mut rng);
+            let point: Vec<Fq> = (0..num_vars).map(|_| Fq::rand(&mut rng)).collect();
+
+            let bound = point
+                .iter()
+                .fold(sum_poly.clone(), |poly, value| poly.partial_evaluate(value));
+            assert_eq!(
+                sum_poly.try_evaluate(&point),
+                Ok(bound.reduce().evaluations()[0])
+            );
+        }
+
+        let sum_poly = SumPoly::<Fq>::rand(3, 2, 2, &mut rng);
+        assert_eq!(
+            sum_poly.try_evaluate(&[Fq::from(1); 2]),
+            Err(ShapeError::WrongPointLength {
+                expected: 3,
+                got: 2
+            })
+        );
+
+        let mut broken = sum_poly;
+        broken.polys[1].evaluation.clear();
+        assert_eq!(
+            broken.try_evaluate(&[Fq::from(1); 3]),
+            Err(ShapeError::EmptyProduct { term: 1 })
+        );
+    }
+
+    #[test]
+    fn restricting_then_evaluating_matches_the_combined_point() {
+        let mut rng = StdRng::seed_from_u64(92);
+        let sparse = SparseMultilinearPoly::new(4, vec![(3, Fq::from(2)), (12, Fq::from(5))]);
+        let sum_poly = SumPoly::new(vec![
+            ProductPoly::rand(4, 2, &mut rng),
+            ProductPoly::from_factors(vec![
+                Factor::Dense(MultilinearPoly::rand(4, &mut rng)),
+                Factor::Sparse(sparse),
+            ]),
+        ]);
+
+        for prefix_len in 0..=4 {
+            let rs: Vec<Fq> = (0..prefix_len).map(|_| Fq::rand(&mut rng)).collect();
+            let restricted = sum_poly.restrict_prefix(&rs).unwrap();
+            let suffix_len = 4 - prefix_len;
+            assert_eq!(restricted.num_vars(), Ok(suffix_len));
+
+            for suffix in 0..1usize << suffix_len {
+                let suffix: Vec<Fq> = (0..suffix_len)
+                    .map(|i| Fq::from((suffix >> (suffix_len - 1 - i) & 1) as u64))
+                    .collect();
+
+                assert_eq!(
+                    restricted.evaluate(&suffix),
+                    sum_poly.evaluate(&[rs.as_slice(), &suffix].concat())
+                );
+            }
+        }
+
+        let value = Fq::rand(&mut rng);
+        let point: Vec<Fq> = (0..3).map(|_| Fq::rand(&mut rng)).collect();
+        for index in 0..4 {
+            let mut full = point.clone();
+            full.insert(index, value);
+
+            assert_eq!(
+                sum_poly
+                    .partial_evaluate_at(index, &value)
+                    .unwrap()
+                    .evaluate(&point),
+                sum_poly.evaluate(&full)
+            );
+        }
+        assert_eq!(
+            sum_poly.partial_evaluate_at(0, &value).unwrap(),
+            sum_poly.partial_evaluate(&value)
+        );
+
+        assert_eq!(
+            sum_poly.partial_evaluate_at(4, &value),
+            Err(ShapeError::VariableOutOfRange {
+                index: 4,
+                num_vars: 4
+            })
+        );
+        assert_eq!(
+            sum_poly.restrict_prefix(&[value; 5]),
+            Err(ShapeError::VariableOutOfRange {
+                index: 4,
+                num_vars: 4
+            })
+        );
+    }
+
     #[test]
     fn sum_poly_partially_evaluates_properly() {
         let evaluations_1 = vec![
@@ -244,7 +1535,7 @@ mod test {
                 product_poly
                     .evaluation
                     .iter()
-                    .map(|poly| poly.evaluation.clone())
+                    .map(|poly| poly.to_dense().evaluation)
                     .collect::<Vec<_>>()
             })
             .collect();
@@ -254,4 +1545,174 @@ mod test {
             result_polys
         );
     }
+
+    #[test]
+    fn a_zero_term_is_never_folded() {
+        let mut rng = StdRng::seed_from_u64(93);
+        let [add, w] = [(); 2].map(|_| MultilinearPoly::<Fq>::rand(4, &mut rng).into_evaluations());
+        let add_term = SumPoly::new(vec![ProductPoly::new(vec![add.clone(), w.clone()])]);
+
+        // No gate is a multiplication, so the `mul·W·W` term is zero.
+        let mut sum_poly = SumPoly::new(vec![
+            ProductPoly::new(vec![add, w.clone()]),
+            ProductPoly::new(vec![vec![Fq::from(0); 16], w.clone(), w]),
+        ]);
+        assert!(sum_poly.polys[1].is_zero());
+        assert_eq!(sum_poly.reduce(), add_term.reduce());
+
+        let mut expected = add_term;
+        for round in 0..4 {
+            let challenge = Fq::rand(&mut rng);
+
+            // Only `add` and the first `W` are bound.
+            let folds;
+            (sum_poly, folds) = table_folds::count(|| match round % 2 {
+                0 => sum_poly.partial_evaluate(&challenge),
+                _ => {
+                    sum_poly.fold_in_place(&challenge);
+                    sum_poly
+                }
+            });
+            assert_eq!(folds, 2);
+
+            expected.fold_in_place(&challenge);
+            assert_eq!(sum_poly.degree_in_bound_variable(), 3);
+            assert_eq!(sum_poly.reduce(), expected.reduce());
+        }
+    }
+
+    #[test]
+    fn degree_in_variable_counts_the_factors_that_change_along_it() {
+        let mut rng = StdRng::seed_from_u64(94);
+        let [add, mul] = [(); 2].map(|_| MultilinearPoly::<Fq>::rand(4, &mut rng));
+        let ones = MultilinearPoly::new(vec![Fq::from(1); 4]);
+        let w = MultilinearPoly::<Fq>::rand(2, &mut rng);
+        let w_b = w.tensor(&ones).unwrap();
+        let w_c = ones.tensor(&w).unwrap();
+
+        let layer = SumPoly::zero(4)
+            .add_product(vec![add.clone(), w_b.clone()])
+            .unwrap()
+            .add_product(vec![add.clone(), w_c.clone()])
+            .unwrap()
+            .add_product(vec![mul, w_b, w_c])
+            .unwrap();
+        assert_eq!(layer.degree_in_bound_variable(), 3);
+        assert!((0..4).all(|index| layer.degree_in_variable(index) == 2));
+
+        // A sparse factor that only sets the last variable.
+        let sparse = SparseMultilinearPoly::new(4, vec![(1, Fq::from(5)), (9, Fq::from(5))]);
+        let sum_poly = SumPoly::new(vec![ProductPoly::from_factors(vec![
+            Factor::Dense(add),
+            Factor::Sparse(sparse),
+        ])]);
+        assert_eq!(sum_poly.degree_in_variable(0), 1);
+        assert_eq!(sum_poly.degree_in_variable(3), 2);
+    }
+
+    #[test]
+    #[should_panic(expected = "variable 4 of a polynomial of 4 variables")]
+    fn degree_in_a_missing_variable_panics() {
+        SumPoly::new(vec![ProductPoly::new(vec![vec![Fq::from(1); 16]])]).degree_in_variable(4);
+    }
+
+    #[test]
+    fn round_values_come_from_one_pass_without_folding() {
+        let mut rng = StdRng::seed_from_u64(97);
+        let sum_poly = SumPoly::new(vec![
+            ProductPoly::rand(4, 1, &mut rng),
+            ProductPoly::rand(4, 3, &mut rng),
+        ]);
+        let (sums, folds) = table_folds::count(|| {
+            let (mut sums, mut scratch) = (vec![Fq::from(0); 4], Vec::new());
+            for j in 0..8 {
+                ComposedPolynomial::add_round_values(&sum_poly, j, &mut sums, &mut scratch);
+            }
+
+            sums
+        });
+        assert_eq!(folds, 0);
+
+        // Binding the first variable instead folds all four tables per point.
+        let (expected, folds) = table_folds::count(|| {
+            (0..4u64)
+                .map(|i| {
+                    sum_poly
+                        .partial_evaluate(&Fq::from(i))
+                        .reduce()
+                        .hypercube_sum()
+                })
+                .collect::<Vec<Fq>>()
+        });
+        assert_eq!(folds, 16);
+        assert_eq!(sums, expected);
+    }
+
+    #[test]
+    fn it_prints_its_structure_without_the_tables() {
+        let sum_poly = SumPoly::new(vec![
+            ProductPoly::new(vec![vec![Fq::from(3); 4], vec![Fq::from(0); 4]]),
+            ProductPoly::from_factors(vec![
+                Factor::Sparse(SparseMultilinearPoly::new(2, vec![(1, Fq::from(2))])),
+                Factor::Dense(MultilinearPoly::new(vec![Fq::from(5); 4])),
+                Factor::Sparse(SparseMultilinearPoly::new(2, vec![])),
+            ]),
+        ]);
+        let expected = "SumPoly { terms: 2, term0: [mle(2 vars), zero(2 vars)], \
+                        term1: [sparse(2 vars), mle(2 vars), zero(2 vars)] }";
+
+        assert_eq!(sum_poly.to_string(), expected);
+        assert_eq!(format!("{:?}", sum_poly), expected);
+
+        let shape = sum_poly.shape();
+        assert_eq!(shape.terms[1].len(), 3);
+        assert!(shape.terms[1][0].sparse && !shape.terms[1][0].zero);
+        assert_ne!(
+            shape,
+            SumPoly::new(vec![ProductPoly::new(vec![vec![Fq::from(1); 2]])]).shape()
+        );
+        assert_eq!(
+            SumPoly::<Fq> { polys: vec![] }.to_string(),
+            "SumPoly { terms: 0 }"
+        );
+    }
+
+    #[test]
+    fn round_univariates_sum_to_the_hypercube_sum_at_zero_and_one() {
+        let mut rng = StdRng::seed_from_u64(99);
+        let terms: Vec<ProductPoly<Fq>> = (1..=3)
+            .map(|degree| ProductPoly::rand(4, degree, &mut rng))
+            .collect();
+        let sum_poly = SumPoly::new(terms.clone());
+
+        for term in &terms {
+            let univariate = term.round_univariate();
+            assert_eq!(
+                univariate.evaluate(Fq::from(0)) + univariate.evaluate(Fq::from(1)),
+                term.reduce().hypercube_sum()
+            );
+        }
+
+        let univariate = sum_poly.round_univariate();
+        let terms_sum = terms
+            .iter()
+            .map(ProductPoly::round_univariate)
+            .reduce(|a, b| a + b)
+            .unwrap();
+        assert_eq!(univariate, terms_sum);
+        assert_eq!(
+            univariate.evaluate(Fq::from(0)) + univariate.evaluate(Fq::from(1)),
+            sum_poly.reduce().hypercube_sum()
+        );
+
+        // Binding the first variable leaves the univariate at that value.
+        let challenge = Fq::rand(&mut rng);
+        assert_eq!(
+            univariate.evaluate(challenge),
+            sum_poly
+                .partial_evaluate(&challenge)
+                .reduce()
+                .hypercube_sum()
+        );
+    }
 }
@@ -0,0 +1,244 @@
+use std::fmt;
+
+use ark_ff::PrimeField;
+
+use crate::multilinear_polynomial_evaluation::MultilinearPoly;
+
+/// Above this many variables a polynomial prints only its first terms, or
+/// table rows, unless `max_vars` says otherwise.
+pub const DEFAULT_MAX_VARS: usize = 6;
+
+/// How many terms, and table rows, a truncated polynomial still prints.
+const TRUNCATED_LEN: usize = 8;
+
+/// A polynomial printed in monomial form, e.g. `3 + 2*x_1 + 7*x_0*x_2`.
+/// Terms go by degree, then by their variables; a coefficient closer to `p`
+/// than to zero prints as a negative number.
+///
+/// Built by `MultilinearPoly::display`, which names variable `i` `x_i` and
+/// truncates past `DEFAULT_MAX_VARS` variables.
+pub struct MonomialDisplay<'a, F: PrimeField> {
+    polynomial: &'a MultilinearPoly<F>,
+    names: &'a dyn Fn(usize) -> String,
+    max_vars: usize,
+}
+
+impl<'a, F: PrimeField> MonomialDisplay<'a, F> {
+    /// Names variable `i` `names(i)` instead of `x_i`.
+    pub fn variable_names(mut self, names: &'a dyn Fn(usize) -> String) -> Self {
+        self.names = names;
+        self
+    }
+
+    /// Prints every term of polynomials of up to `max_vars` variables, and
+    /// only the first few of larger ones.
+    pub fn max_vars(mut self, max_vars: usize) -> Self {
+        self.max_vars = max_vars;
+        self
+    }
+}
+
+impl<F: PrimeField> fmt::Display for MonomialDisplay<'_, F> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let num_vars = self.polynomial.num_vars();
+        let coefficients = self.polynomial.to_coefficients();
+
+        // Monomial `m` holds variable `i` when bit `num_vars - 1 - i` is set.
+        let variables = |monomial: usize| -> Vec<usize> {
+            (0..num_vars)
+                .filter(|i| monomial >> (num_vars - 1 - i) & 1 == 1)
+                .collect()
+        };
+
+        let mut terms: Vec<(Vec<usize>, F)> = coefficients
+            .into_iter()
+            .enumerate()
+            .filter(|(_, coefficient)| !coefficient.is_zero())
+            .map(|(monomial, coefficient)| (variables(monomial), coefficient))
+            .collect();
+        terms.sort_by(|(a, _), (b, _)| a.len().cmp(&b.len()).then_with(|| a.cmp(b)));
+
+        if terms.is_empty() {
+            return write!(f, "0");
+        }
+
+        let shown = match num_vars > self.max_vars {
+            true => TRUNCATED_LEN.min(terms.len()),
+            false => terms.len(),
+        };
+
+        for (position, (variables, coefficient)) in terms[..shown].iter().enumerate() {
+            let (negative, magnitude) = signed(*coefficient);
+
+            match (position, negative) {
+                (0, true) => write!(f, "-")?,
+                (0, false) => {}
+                (_, true) => write!(f, " - ")?,
+                (_, false) => write!(f, " + ")?,
+            }
+
+            let names: Vec<String> = variables.iter().map(|i| (self.names)(*i)).collect();
+            match (magnitude.is_one(), names.is_empty()) {
+                (true, false) => write!(f, "{}", names.join("*"))?,
+                (_, true) => write!(f, "{}", magnitude)?,
+                (false, false) => write!(f, "{}*{}", magnitude, names.join("*"))?,
+            }
+        }
+
+        if shown < terms.len() {
+            write!(f, " + ... ({} more terms)", terms.len() - shown)?;
+        }
+
+        Ok(())
+    }
+}
+
+impl<F: PrimeField> MultilinearPoly<F> {
+    /// The polynomial in monomial form; see `MonomialDisplay`. `{}` on the
+    /// polynomial itself prints the same with the defaults.
+    pub fn display(&self) -> MonomialDisplay<'_, F> {
+        MonomialDisplay {
+            polynomial: self,
+            names: &default_name,
+            max_vars: DEFAULT_MAX_VARS,
+        }
+    }
+
+    /// The evaluation table, one `index: value` row per hypercube point with
+    /// the index in binary, first variable leftmost. Past `DEFAULT_MAX_VARS`
+    /// variables only the first rows are printed.
+    pub fn fmt_evaluations_table(&self) -> String {
+        let num_vars = self.num_vars();
+        let shown = match num_vars > DEFAULT_MAX_VARS {
+            true => TRUNCATED_LEN,
+            false => self.len(),
+        };
+
+        let mut rows: Vec<String> = self.evaluations()[..shown]
+            .iter()
+            .enumerate()
+            .map(|(index, value)| {
+                let (negative, magnitude) = signed(*value);
+                let sign = if negative { "-" } else { "" };
+
+                match num_vars {
+                    0 => format!("{}{}", sign, magnitude),
+                    _ => format!(
+                        "{:0width$b}: {}{}",
+                        index,
+                        sign,
+                        magnitude,
+                        width = num_vars
+                    ),
+                }
+            })
+            .collect();
+
+        if shown < self.len() {
+            rows.push(format!("... ({} more rows)", self.len() - shown));
+        }
+
+        rows.join("\n")
+    }
+}
+
+impl<F: PrimeField> fmt::Display for MultilinearPoly<F> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.display().fmt(f)
+    }
+}
+
+fn default_name(i: usize) -> String {
+    format!("x_{}", i)
+}
+
+/// Whether `value` reads better as `-magnitude`, and that magnitude.
+fn signed<F: PrimeField>(value: F) -> (bool, F) {
+    match (-value).into_bigint() < value.into_bigint() {
+        true => (true, -value),
+        false => (false, value),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use ark_bn254::Fq;
+
+    use crate::multilinear_polynomial_evaluation::MultilinearPoly;
+
+    #[test]
+    fn it_prints_the_monomial_form() {
+        // 3 + 2*x_1 + 7*x_0*x_2
+        let polynomial = MultilinearPoly::from_coefficients(
+            [3, 0, 2, 0, 0, 7, 0, 0].into_iter().map(Fq::from).collect(),
+        );
+        assert_eq!(polynomial.to_string(), "3 + 2*x_1 + 7*x_0*x_2");
+
+        // Coefficients close to p print as negative numbers.
+        let polynomial = MultilinearPoly::from_coefficients(vec![
+            Fq::from(0),
+            Fq::from(-1),
+            Fq::from(1),
+            Fq::from(-4),
+        ]);
+        assert_eq!(polynomial.to_string(), "x_0 - x_1 - 4*x_0*x_1");
+
+        let names = |i: usize| ["a", "b"][i].to_string();
+        assert_eq!(
+            polynomial.display().variable_names(&names).to_string(),
+            "a - b - 4*a*b"
+        );
+
+        assert_eq!(MultilinearPoly::<Fq>::zero(3).to_string(), "0");
+        assert_eq!(MultilinearPoly::new(vec![Fq::from(-5)]).to_string(), "-5");
+    }
+
+    #[test]
+    fn it_truncates_large_polynomials() {
+        // 1 + x_0 + ... + x_6, with 8 terms over 7 variables.
+        let mut coefficients = vec![Fq::from(0); 1 << 7];
+        coefficients[0] = Fq::from(1);
+        for bit in 0..7 {
+            coefficients[1 << bit] = Fq::from(1);
+        }
+        let polynomial = MultilinearPoly::from_coefficients(coefficients);
+
+        assert_eq!(
+            polynomial.display().to_string(),
+            "1 + x_0 + x_1 + x_2 + x_3 + x_4 + x_5 + x_6"
+        );
+
+        let mut coefficients = polynomial.to_coefficients();
+        coefficients[3] = Fq::from(2);
+        let polynomial = MultilinearPoly::from_coefficients(coefficients);
+        assert_eq!(
+            polynomial.display().to_string(),
+            "1 + x_0 + x_1 + x_2 + x_3 + x_4 + x_5 + x_6 + ... (1 more terms)"
+        );
+        assert_eq!(
+            polynomial.display().max_vars(7).to_string(),
+            "1 + x_0 + x_1 + x_2 + x_3 + x_4 + x_5 + x_6 + 2*x_5*x_6"
+        );
+    }
+
+    #[test]
+    fn it_prints_the_evaluation_table() {
+        let polynomial =
+            MultilinearPoly::new(vec![Fq::from(0), Fq::from(-2), Fq::from(3), Fq::from(9)]);
+        assert_eq!(
+            polynomial.fmt_evaluations_table(),
+            "00: 0\n01: -2\n10: 3\n11: 9"
+        );
+
+        assert_eq!(
+            MultilinearPoly::new(vec![Fq::from(4)]).fmt_evaluations_table(),
+            "4"
+        );
+
+        let polynomial = MultilinearPoly::from_fn(7, |index| Fq::from(index as u64));
+        let table = polynomial.fmt_evaluations_table();
+        assert_eq!(table.lines().count(), 9);
+        assert!(table.starts_with("0000000: 0\n0000001: 1\n"));
+        assert!(table.ends_with("0000111: 7\n... (120 more rows)"));
+    }
+}
@@ -0,0 +1,370 @@
+use std::{
+    fs::File,
+    io::{self, BufWriter, Write},
+    marker::PhantomData,
+    ops::Index,
+    path::{Path, PathBuf},
+    sync::OnceLock,
+};
+
+use ark_ff::PrimeField;
+use memmap2::Mmap;
+
+use crate::multilinear_polynomial_evaluation::{table_len, MultilinearError, MultilinearPoly};
+
+/// Where an evaluation table lives, indexed like a slice and read front to
+/// back in chunks. Provers that only stream over a table can take any store
+/// instead of a `MultilinearPoly`, so the table never has to fit in memory.
+pub trait EvalStore<F: PrimeField>: Index<usize, Output = F> {
+    /// The number of evaluations.
+    fn len(&self) -> usize;
+
+    fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// The evaluations in table order, `chunk_size` at a time with a shorter
+    /// last chunk.
+    fn read_chunks(&self, chunk_size: usize) -> Box<dyn Iterator<Item = io::Result<Vec<F>>> + '_>;
+}
+
+impl<F: PrimeField> EvalStore<F> for Vec<F> {
+    fn len(&self) -> usize {
+        self.as_slice().len()
+    }
+
+    fn read_chunks(&self, chunk_size: usize) -> Box<dyn Iterator<Item = io::Result<Vec<F>>> + '_> {
+        Box::new(self.chunks(chunk_size).map(|chunk| Ok(chunk.to_vec())))
+    }
+}
+
+/// How many evaluations `FileEvals` decodes at once when indexed.
+const DECODED_CHUNK: usize = 1 << 12;
+
+/// A table dumped by `MultilinearPoly::write_evals_to_file`: `num_of_vars`
+/// as a little-endian `u64`, then every evaluation compressed. The file is
+/// mapped read-only; chunked reads decode straight from the mapping, and
+/// indexing decodes the surrounding chunk once and keeps it.
+#[derive(Debug)]
+pub struct FileEvals<F: PrimeField> {
+    path: PathBuf,
+    map: Mmap,
+    num_vars: usize,
+    decoded: Vec<OnceLock<Box<[F]>>>,
+}
+
+impl<F: PrimeField> FileEvals<F> {
+    /// Maps a dump, checking that the file holds exactly the table its
+    /// header announces.
+    pub fn open(path: impl AsRef<Path>) -> io::Result<Self> {
+        let path = path.as_ref().to_path_buf();
+        let file = File::open(&path)?;
+
+        // Safety: the mapping is read-only and private to this store. A dump
+        // rewritten by another process while mapped changes what later reads
+        // see, as it would for reads through the file.
+        let map = unsafe { Mmap::map(&file)? };
+
+        let header: [u8; 8] = map
+            .get(..8)
+            .and_then(|header| header.try_into().ok())
+            .ok_or_else(|| invalid_data(format!("{} has no header", path.display())))?;
+        let num_vars = u64::from_le_bytes(header);
+
+        let len = usize::try_from(num_vars)
+            .ok()
+            .and_then(|num_vars| table_len(num_vars).ok());
+        let expected = len
+            .and_then(|len| (len as u64).checked_mul(element_size::<F>() as u64))
+            .and_then(|bytes| bytes.checked_add(8));
+        let len = match len {
+            Some(len) if expected == Some(map.len() as u64) => len,
+            _ => {
+                return Err(invalid_data(format!(
+                    "{} is not a table of {} variables",
+                    path.display(),
+                    num_vars
+                )))
+            }
+        };
+
+        Ok(Self {
+            path,
+            map,
+            num_vars: num_vars as usize,
+            decoded: (0..len.div_ceil(DECODED_CHUNK))
+                .map(|_| OnceLock::new())
+                .collect(),
+        })
+    }
+
+    pub fn num_vars(&self) -> usize {
+        self.num_vars
+    }
+
+    /// Decodes evaluations `start..end` from the mapping.
+    fn decode(&self, start: usize, end: usize) -> io::Result<Vec<F>> {
+        let size = element_size::<F>();
+
+        self.map[8 + start * size..8 + end * size]
+            .chunks_exact(size)
+            .map(|bytes| {
+                F::deserialize_compressed(bytes).map_err(|error| invalid_data(error.to_string()))
+            })
+            .collect()
+    }
+}
+
+impl<F: PrimeField> Index<usize> for FileEvals<F> {
+    type Output = F;
+
+    /// Panics past the end of the table, or if the chunk holding `index` is
+    /// not made of field elements.
+    fn index(&self, index: usize) -> &F {
+        let len = self.len();
+        assert!(
+            index < len,
+            "index {} is outside a table of {} evaluations",
+            index,
+            len
+        );
+
+        let chunk = index / DECODED_CHUNK;
+        let decoded = self.decoded[chunk].get_or_init(|| {
+            let start = chunk * DECODED_CHUNK;
+
+            self.decode(start, len.min(start + DECODED_CHUNK))
+                .unwrap_or_else(|error| panic!("{}: {}", self.path.display(), error))
+                .into_boxed_slice()
+        });
+
+        &decoded[index % DECODED_CHUNK]
+    }
+}
+
+impl<F: PrimeField> EvalStore<F> for FileEvals<F> {
+    fn len(&self) -> usize {
+        1 << self.num_vars
+    }
+
+    fn read_chunks(&self, chunk_size: usize) -> Box<dyn Iterator<Item = io::Result<Vec<F>>> + '_> {
+        let len = self.len();
+        let chunk_size = chunk_size.max(1);
+
+        Box::new(
+            (0..len)
+                .step_by(chunk_size)
+                .map(move |start| self.decode(start, len.min(start + chunk_size))),
+        )
+    }
+}
+
+/// A multilinear polynomial whose table stays in a store, built by
+/// `MultilinearPoly::over_store`. Binding its first variable streams the
+/// store once and leaves an in-memory polynomial of half the size.
+#[derive(Debug)]
+pub struct StoredMultilinearPoly<'a, F: PrimeField, S: EvalStore<F>> {
+    store: &'a S,
+    num_vars: usize,
+    _field: PhantomData<F>,
+}
+
+impl<'a, F: PrimeField, S: EvalStore<F>> StoredMultilinearPoly<'a, F, S> {
+    pub fn num_vars(&self) -> usize {
+        self.num_vars
+    }
+
+    pub fn store(&self) -> &'a S {
+        self.store
+    }
+
+    /// Binds variable 0 to `value` in one pass over the store.
+    pub fn bind_first(&self, value: F) -> io::Result<MultilinearPoly<F>> {
+        if self.num_vars == 0 {
+            return Err(invalid_input(MultilinearError::VariableOutOfRange {
+                bit: 0,
+                num_of_vars: 0,
+            }));
+        }
+
+        let half = self.store.len() >> 1;
+        let mut folded = Vec::with_capacity(half);
+        let mut high = 0;
+
+        for chunk in self.store.read_chunks(DECODED_CHUNK) {
+            for eval in chunk? {
+                if folded.len() < half {
+                    folded.push(eval);
+                } else {
+                    let low = &mut folded[high];
+                    *low += value * (eval - *low);
+                    high += 1;
+                }
+            }
+        }
+
+        Ok(MultilinearPoly::new(folded))
+    }
+
+    /// The polynomial at `point`, binding the first variable from the store
+    /// and the rest in memory.
+    pub fn evaluate(&self, point: &[F]) -> io::Result<F> {
+        if point.len() != self.num_vars {
+            return Err(invalid_input(MultilinearError::WrongNumberOfValues {
+                expected: self.num_vars,
+                got: point.len(),
+            }));
+        }
+
+        match point.split_first() {
+            Some((first, rest)) => Ok(self.bind_first(*first)?.evaluate(rest)),
+            None => Ok(self.store[0]),
+        }
+    }
+}
+
+impl<F: PrimeField, S: EvalStore<F>> Index<usize> for StoredMultilinearPoly<'_, F, S> {
+    type Output = F;
+
+    fn index(&self, index: usize) -> &F {
+        &self.store[index]
+    }
+}
+
+impl<F: PrimeField> MultilinearPoly<F> {
+    /// Dumps the table in the format `FileEvals::open` reads.
+    pub fn write_evals_to_file(&self, path: impl AsRef<Path>) -> io::Result<()> {
+        let mut writer = BufWriter::new(File::create(path)?);
+        writer.write_all(&(self.num_of_vars as u64).to_le_bytes())?;
+
+        let mut bytes = Vec::with_capacity(element_size::<F>());
+        for eval in &self.evaluation {
+            bytes.clear();
+            eval.serialize_compressed(&mut bytes)
+                .map_err(|error| invalid_data(error.to_string()))?;
+            writer.write_all(&bytes)?;
+        }
+
+        writer.flush()
+    }
+
+    /// The polynomial over the table in `store`, which must hold a power of
+    /// two evaluations.
+    pub fn over_store<S: EvalStore<F>>(
+        store: &S,
+    ) -> Result<StoredMultilinearPoly<'_, F, S>, MultilinearError> {
+        let len = store.len();
+        if !len.is_power_of_two() {
+            return Err(MultilinearError::LengthNotPowerOfTwo { len });
+        }
+
+        Ok(StoredMultilinearPoly {
+            store,
+            num_vars: len.trailing_zeros() as usize,
+            _field: PhantomData,
+        })
+    }
+
+    /// Reads a whole dump back into memory.
+    pub fn read_evals_from_file(path: impl AsRef<Path>) -> io::Result<Self> {
+        let store = FileEvals::<F>::open(path)?;
+
+        let mut evaluation = Vec::with_capacity(store.len());
+        for chunk in store.read_chunks(1 << 12) {
+            evaluation.extend(chunk?);
+        }
+
+        Ok(Self::new(evaluation))
+    }
+}
+
+fn element_size<F: PrimeField>() -> usize {
+    F::zero().compressed_size()
+}
+
+fn invalid_data(message: String) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, message)
+}
+
+fn invalid_input(error: MultilinearError) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidInput, error)
+}
+
+#[cfg(test)]
+mod test {
+    use std::fs;
+
+    use ark_bn254::Fq;
+
+    use super::{EvalStore, FileEvals};
+    use crate::multilinear_polynomial_evaluation::{MultilinearError, MultilinearPoly};
+
+    #[test]
+    fn it_reads_back_a_dumped_table_in_chunks() {
+        let path = std::env::temp_dir().join(format!("eval_store_{}.bin", std::process::id()));
+        let polynomial = MultilinearPoly::new((0..32u64).map(|i| Fq::from(i * i)).collect());
+        polynomial.write_evals_to_file(&path).unwrap();
+
+        let store = FileEvals::<Fq>::open(&path).unwrap();
+        assert_eq!(store.num_vars(), 5);
+
+        let chunks: Vec<Vec<Fq>> = store.read_chunks(12).map(Result::unwrap).collect();
+        assert_eq!(
+            chunks.iter().map(Vec::len).collect::<Vec<_>>(),
+            vec![12, 12, 8]
+        );
+        assert_eq!(chunks.concat(), polynomial.evaluation);
+        assert_eq!(store[0], Fq::from(0));
+        assert_eq!(store[31], Fq::from(31 * 31));
+        assert_eq!(
+            MultilinearPoly::<Fq>::read_evals_from_file(&path).unwrap(),
+            polynomial
+        );
+
+        // A truncated dump no longer matches its header.
+        let bytes = fs::read(&path).unwrap();
+        fs::write(&path, &bytes[..bytes.len() - 1]).unwrap();
+        assert!(FileEvals::<Fq>::open(&path).is_err());
+
+        fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn a_polynomial_over_a_mapped_dump_matches_the_in_memory_one() {
+        let path = std::env::temp_dir().join(format!("eval_store_poly_{}.bin", std::process::id()));
+        let polynomial =
+            MultilinearPoly::new((0..1u64 << 13).map(|i| Fq::from(i * 3 + 1)).collect());
+        polynomial.write_evals_to_file(&path).unwrap();
+
+        let store = FileEvals::<Fq>::open(&path).unwrap();
+        let stored = MultilinearPoly::over_store(&store).unwrap();
+        let point: Vec<Fq> = (0..13u64).map(|i| Fq::from(i + 5)).collect();
+
+        assert_eq!(stored.num_vars(), 13);
+        assert_eq!(stored[5000], polynomial.evaluation[5000]);
+        assert_eq!(
+            stored.bind_first(point[0]).unwrap(),
+            polynomial.partial_evaluate(0, &point[0]).unwrap()
+        );
+        assert_eq!(
+            stored.evaluate(&point).unwrap(),
+            polynomial.evaluate(&point)
+        );
+        assert!(stored.evaluate(&point[1..]).is_err());
+
+        fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn a_vec_is_a_store() {
+        let evals: Vec<Fq> = (0..5u64).map(Fq::from).collect();
+        let chunks: Vec<Vec<Fq>> = evals.read_chunks(2).map(Result::unwrap).collect();
+
+        assert_eq!(EvalStore::len(&evals), 5);
+        assert_eq!(chunks.concat(), evals);
+        assert_eq!(
+            MultilinearPoly::over_store(&evals).unwrap_err(),
+            MultilinearError::LengthNotPowerOfTwo { len: 5 }
+        );
+    }
+}
@@ -0,0 +1,262 @@
+use ark_ff::Field;
+use ark_serialize::{
+    CanonicalDeserialize, CanonicalSerialize, Compress, Read, SerializationError, Valid, Validate,
+    Write,
+};
+
+use crate::multilinear_polynomial_evaluation::MultilinearPoly;
+
+/// The claim every protocol here ends in: some polynomial evaluates to
+/// `value` at `point`. Whoever holds the polynomial, or an opening of its
+/// commitment, discharges it.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct EvaluationClaim<F: Field> {
+    pub point: Vec<F>,
+    pub value: F,
+}
+
+impl<F: Field> EvaluationClaim<F> {
+    pub fn new(point: Vec<F>, value: F) -> Self {
+        Self { point, value }
+    }
+
+    pub fn num_vars(&self) -> usize {
+        self.point.len()
+    }
+
+    /// Whether `polynomial` really evaluates to `value` at `point`. A point
+    /// of the wrong length is a false claim, not a panic.
+    pub fn check_against(&self, polynomial: &MultilinearPoly<F>) -> bool {
+        polynomial.try_evaluate(&self.point) == Ok(self.value)
+    }
+
+    /// Merges this claim and `other`, both about the same polynomial, into
+    /// the single claim `P(self.point) + alpha·P(other.point)`, for a random
+    /// `alpha`. Panics if the points have different lengths.
+    pub fn fold_with(&self, other: &Self, alpha: F) -> CombinedClaim<F> {
+        assert_eq!(
+            self.num_vars(),
+            other.num_vars(),
+            "claims about the same polynomial have points of the same length"
+        );
+
+        CombinedClaim {
+            points: vec![self.point.clone(), other.point.clone()],
+            coefficients: vec![F::one(), alpha],
+            value: self.value + alpha * other.value,
+        }
+    }
+}
+
+/// A random linear combination of claims about one polynomial:
+/// `sum_i coefficients[i]·P(points[i]) == value`.
+///
+/// It is the sum over the hypercube of `P·eq_weights()`, so a sum-check
+/// reduces it back to a single `EvaluationClaim`.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct CombinedClaim<F: Field> {
+    pub points: Vec<Vec<F>>,
+    pub coefficients: Vec<F>,
+    pub value: F,
+}
+
+impl<F: Field> CombinedClaim<F> {
+    /// Whether `polynomial` satisfies the combination. False if any point
+    /// has the wrong length.
+    pub fn check_against(&self, polynomial: &MultilinearPoly<F>) -> bool {
+        let evaluations: Result<Vec<F>, _> = self
+            .points
+            .iter()
+            .map(|point| polynomial.try_evaluate(point))
+            .collect();
+
+        match evaluations {
+            Ok(evaluations) => {
+                let combined: F = evaluations
+                    .iter()
+                    .zip(&self.coefficients)
+                    .map(|(eval, coefficient)| *eval * coefficient)
+                    .sum();
+
+                self.points.len() == self.coefficients.len() && combined == self.value
+            }
+            Err(_) => false,
+        }
+    }
+
+    /// `sum_i coefficients[i]·eq(x, points[i])`, the table that turns the
+    /// combination into a sum over the hypercube. Panics if there are no
+    /// points.
+    pub fn eq_weights(&self) -> MultilinearPoly<F> {
+        self.points
+            .iter()
+            .zip(&self.coefficients)
+            .map(|(point, coefficient)| MultilinearPoly::eq_mle(point) * *coefficient)
+            .reduce(|sum, term| sum + term)
+            .expect("a combined claim has at least one point")
+    }
+}
+
+/// Written as `value` followed by `point` in the length-prefixed format of
+/// `Vec`.
+impl<F: Field> CanonicalSerialize for EvaluationClaim<F> {
+    fn serialize_with_mode<W: Write>(
+        &self,
+        mut writer: W,
+        compress: Compress,
+    ) -> Result<(), SerializationError> {
+        self.value.serialize_with_mode(&mut writer, compress)?;
+        self.point.serialize_with_mode(&mut writer, compress)
+    }
+
+    fn serialized_size(&self, compress: Compress) -> usize {
+        self.value.serialized_size(compress) + self.point.serialized_size(compress)
+    }
+}
+
+impl<F: Field> Valid for EvaluationClaim<F> {
+    fn check(&self) -> Result<(), SerializationError> {
+        Ok(())
+    }
+}
+
+impl<F: Field> CanonicalDeserialize for EvaluationClaim<F> {
+    fn deserialize_with_mode<R: Read>(
+        mut reader: R,
+        compress: Compress,
+        validate: Validate,
+    ) -> Result<Self, SerializationError> {
+        let value = F::deserialize_with_mode(&mut reader, compress, validate)?;
+        let len = u64::deserialize_with_mode(&mut reader, compress, validate)?;
+
+        // One element at a time, so a corrupted length runs out of input
+        // instead of driving a huge allocation.
+        let mut point = Vec::new();
+        for _ in 0..len {
+            point.push(F::deserialize_with_mode(&mut reader, compress, validate)?);
+        }
+
+        Ok(Self { point, value })
+    }
+}
+
+/// Field elements are written as decimal strings, as elsewhere in the
+/// workspace's JSON.
+#[cfg(feature = "serde")]
+mod serde_impl {
+    use ark_ff::PrimeField;
+    use serde::{de::Error, Deserialize, Deserializer, Serialize, Serializer};
+
+    use super::EvaluationClaim;
+
+    #[derive(Serialize, Deserialize)]
+    #[serde(deny_unknown_fields)]
+    struct EvaluationClaimJson {
+        point: Vec<String>,
+        value: String,
+    }
+
+    impl<F: PrimeField> Serialize for EvaluationClaim<F> {
+        fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+            EvaluationClaimJson {
+                point: self.point.iter().map(|x| x.to_string()).collect(),
+                value: self.value.to_string(),
+            }
+            .serialize(serializer)
+        }
+    }
+
+    impl<'de, F: PrimeField> Deserialize<'de> for EvaluationClaim<F> {
+        fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+            let json = EvaluationClaimJson::deserialize(deserializer)?;
+            let parse = |x: &String| {
+                F::from_str(x).map_err(|_| D::Error::custom(format!("invalid field element {}", x)))
+            };
+
+            Ok(Self {
+                point: json.point.iter().map(parse).collect::<Result<_, _>>()?,
+                value: parse(&json.value)?,
+            })
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use ark_bn254::Fq;
+    use ark_serialize::{CanonicalDeserialize, CanonicalSerialize};
+
+    use super::EvaluationClaim;
+    use crate::multilinear_polynomial_evaluation::MultilinearPoly;
+
+    fn polynomial() -> MultilinearPoly<Fq> {
+        MultilinearPoly::new((1..=8u64).map(Fq::from).collect())
+    }
+
+    #[test]
+    fn it_checks_a_claim_against_the_polynomial() {
+        let polynomial = polynomial();
+        let point = vec![Fq::from(2), Fq::from(3), Fq::from(5)];
+
+        let claim = EvaluationClaim::new(point.clone(), polynomial.evaluate(&point));
+        assert!(claim.check_against(&polynomial));
+
+        let wrong = EvaluationClaim::new(point, claim.value + Fq::from(1));
+        assert!(!wrong.check_against(&polynomial));
+
+        let short = EvaluationClaim::new(vec![Fq::from(2)], claim.value);
+        assert!(!short.check_against(&polynomial));
+    }
+
+    #[test]
+    fn folded_claims_hold_together() {
+        let polynomial = polynomial();
+        let a = vec![Fq::from(2), Fq::from(3), Fq::from(5)];
+        let b = vec![Fq::from(7), Fq::from(0), Fq::from(4)];
+        let alpha = Fq::from(11);
+
+        let first = EvaluationClaim::new(a.clone(), polynomial.evaluate(&a));
+        let second = EvaluationClaim::new(b.clone(), polynomial.evaluate(&b));
+
+        let combined = first.fold_with(&second, alpha);
+        assert!(combined.check_against(&polynomial));
+
+        // The combination is the hypercube sum of P times the eq weights.
+        let weighted = polynomial.clone() * combined.eq_weights();
+        assert_eq!(weighted.hypercube_sum(), combined.value);
+
+        let lie = EvaluationClaim::new(b, second.value + Fq::from(1));
+        assert!(!first.fold_with(&lie, alpha).check_against(&polynomial));
+    }
+
+    #[test]
+    fn it_round_trips_canonically() {
+        let claim = EvaluationClaim::new(vec![Fq::from(4), Fq::from(5)], Fq::from(3));
+
+        let mut bytes = Vec::new();
+        claim.serialize_compressed(&mut bytes).unwrap();
+        assert_eq!(bytes.len(), claim.compressed_size());
+        assert_eq!(
+            EvaluationClaim::<Fq>::deserialize_compressed(bytes.as_slice()).unwrap(),
+            claim
+        );
+
+        assert!(EvaluationClaim::<Fq>::deserialize_compressed(&bytes[..bytes.len() - 1]).is_err());
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn it_round_trips_through_json() {
+        let claim = EvaluationClaim::new(vec![Fq::from(4), Fq::from(5)], Fq::from(3));
+
+        let json = serde_json::to_string(&claim).unwrap();
+        assert_eq!(json, r#"{"point":["4","5"],"value":"3"}"#);
+        assert_eq!(
+            serde_json::from_str::<EvaluationClaim<Fq>>(&json).unwrap(),
+            claim
+        );
+
+        let garbage = r#"{"point":["x"],"value":"3"}"#;
+        assert!(serde_json::from_str::<EvaluationClaim<Fq>>(garbage).is_err());
+    }
+}
@@ -0,0 +1,424 @@
+use std::{
+    collections::BTreeMap,
+    fmt,
+    marker::PhantomData,
+    ops::{Add, Index, Mul},
+    sync::Arc,
+};
+
+use ark_ff::PrimeField;
+
+use crate::{
+    composed_polynomial::SumPoly,
+    multilinear_polynomial_evaluation::{checked_table_len, MultilinearPoly},
+    virtual_polynomial::VirtualPolynomial,
+};
+
+/// A polynomial expression over named multilinear inputs, e.g. a layer's
+/// `addi·(wb + wc) + muli·wb·wc`. There is no division or other operation
+/// with an `Expr` form, so every expression is polynomial in its inputs.
+#[derive(Clone, Debug, PartialEq)]
+pub enum Expr<F: PrimeField> {
+    /// The input at this position of the list the expression is built over.
+    Var(usize),
+    Const(F),
+    Add(Box<Expr<F>>, Box<Expr<F>>),
+    Mul(Box<Expr<F>>, Box<Expr<F>>),
+}
+
+/// Terms as `(coefficient, indices of the inputs multiplied)`, the form
+/// `VirtualPolynomial::products` uses.
+pub type Products<F> = Vec<(F, Vec<usize>)>;
+
+impl<F: PrimeField> Expr<F> {
+    /// The expression expanded into `Σ coefficient·Π inputs[index]`, each
+    /// term's indices sorted, with like terms collected and zero ones
+    /// dropped. A constant is a term with no indices.
+    pub fn sum_of_products(&self) -> Products<F> {
+        self.expand()
+            .into_iter()
+            .filter(|(_, coefficient)| !coefficient.is_zero())
+            .map(|(indices, coefficient)| (coefficient, indices))
+            .collect()
+    }
+
+    fn expand(&self) -> BTreeMap<Vec<usize>, F> {
+        match self {
+            Expr::Var(index) => BTreeMap::from([(vec![*index], F::one())]),
+            Expr::Const(value) => BTreeMap::from([(Vec::new(), *value)]),
+            Expr::Add(left, right) => {
+                let mut terms = left.expand();
+                for (indices, coefficient) in right.expand() {
+                    *terms.entry(indices).or_insert_with(F::zero) += coefficient;
+                }
+
+                terms
+            }
+            Expr::Mul(left, right) => {
+                let (left, right) = (left.expand(), right.expand());
+                let mut terms = BTreeMap::new();
+                for (left_indices, left_coefficient) in &left {
+                    for (right_indices, right_coefficient) in &right {
+                        let mut indices = [left_indices.as_slice(), right_indices].concat();
+                        indices.sort_unstable();
+                        *terms.entry(indices).or_insert_with(F::zero) +=
+                            *left_coefficient * right_coefficient;
+                    }
+                }
+
+                terms
+            }
+        }
+    }
+}
+
+impl<F: PrimeField> From<F> for Expr<F> {
+    fn from(value: F) -> Self {
+        Expr::Const(value)
+    }
+}
+
+impl<F: PrimeField> From<Input<F>> for Expr<F> {
+    fn from(input: Input<F>) -> Self {
+        Expr::Var(input.index)
+    }
+}
+
+impl<F: PrimeField, R: Into<Expr<F>>> Add<R> for Expr<F> {
+    type Output = Expr<F>;
+
+    fn add(self, other: R) -> Expr<F> {
+        Expr::Add(Box::new(self), Box::new(other.into()))
+    }
+}
+
+impl<F: PrimeField, R: Into<Expr<F>>> Mul<R> for Expr<F> {
+    type Output = Expr<F>;
+
+    fn mul(self, other: R) -> Expr<F> {
+        Expr::Mul(Box::new(self), Box::new(other.into()))
+    }
+}
+
+/// A named input inside a `from_expr` closure. It is `Copy`, so `v["wb"]`
+/// can be used as often as the formula needs.
+#[derive(Debug, PartialEq, Eq)]
+pub struct Input<F: PrimeField> {
+    index: usize,
+    _field: PhantomData<F>,
+}
+
+impl<F: PrimeField> Clone for Input<F> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<F: PrimeField> Copy for Input<F> {}
+
+impl<F: PrimeField, R: Into<Expr<F>>> Add<R> for Input<F> {
+    type Output = Expr<F>;
+
+    fn add(self, other: R) -> Expr<F> {
+        Expr::from(self) + other
+    }
+}
+
+impl<F: PrimeField, R: Into<Expr<F>>> Mul<R> for Input<F> {
+    type Output = Expr<F>;
+
+    fn mul(self, other: R) -> Expr<F> {
+        Expr::from(self) * other
+    }
+}
+
+/// The inputs of a `from_expr` closure, looked up by name. Indexing with a
+/// name that is not one of them panics, as a `HashMap` does.
+pub struct Inputs<'a, F: PrimeField> {
+    inputs: Vec<(&'a str, Input<F>)>,
+}
+
+impl<F: PrimeField> Index<&str> for Inputs<'_, F> {
+    type Output = Input<F>;
+
+    fn index(&self, name: &str) -> &Input<F> {
+        self.inputs
+            .iter()
+            .find(|(input_name, _)| *input_name == name)
+            .map(|(_, input)| input)
+            .unwrap_or_else(|| panic!("no input named {}", name))
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ExprError {
+    NoInputs,
+    DuplicateInput(String),
+    MismatchedNumVars {
+        input: String,
+        expected: usize,
+        got: usize,
+    },
+    UnknownInput {
+        index: usize,
+        num_inputs: usize,
+    },
+}
+
+impl fmt::Display for ExprError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ExprError::NoInputs => write!(f, "an expression needs at least one input"),
+            ExprError::DuplicateInput(name) => write!(f, "input {} is named twice", name),
+            ExprError::MismatchedNumVars {
+                input,
+                expected,
+                got,
+            } => write!(
+                f,
+                "input {} has {} variables, expected {}",
+                input, got, expected
+            ),
+            ExprError::UnknownInput { index, num_inputs } => write!(
+                f,
+                "the expression uses input {} of {} inputs",
+                index, num_inputs
+            ),
+        }
+    }
+}
+
+impl std::error::Error for ExprError {}
+
+/// Builds `build`'s expression over `inputs` and expands it, checking that
+/// the inputs are distinctly named, share their variables and cover every
+/// index the expression uses. Returns the common number of variables too.
+fn expand_over<F: PrimeField>(
+    inputs: &[(&str, MultilinearPoly<F>)],
+    build: impl FnOnce(&Inputs<F>) -> Expr<F>,
+) -> Result<(usize, Products<F>), ExprError> {
+    let (_, first) = inputs.first().ok_or(ExprError::NoInputs)?;
+    let num_vars = first.num_vars();
+
+    for (position, (name, poly)) in inputs.iter().enumerate() {
+        if inputs[..position].iter().any(|(other, _)| other == name) {
+            return Err(ExprError::DuplicateInput(name.to_string()));
+        }
+
+        if poly.num_vars() != num_vars {
+            return Err(ExprError::MismatchedNumVars {
+                input: name.to_string(),
+                expected: num_vars,
+                got: poly.num_vars(),
+            });
+        }
+    }
+
+    let named = Inputs {
+        inputs: inputs
+            .iter()
+            .enumerate()
+            .map(|(index, (name, _))| {
+                let input = Input {
+                    index,
+                    _field: PhantomData,
+                };
+
+                (*name, input)
+            })
+            .collect(),
+    };
+    let products = build(&named).sum_of_products();
+
+    if let Some(index) = products
+        .iter()
+        .flat_map(|(_, indices)| indices)
+        .find(|index| **index >= inputs.len())
+    {
+        return Err(ExprError::UnknownInput {
+            index: *index,
+            num_inputs: inputs.len(),
+        });
+    }
+
+    Ok((num_vars, products))
+}
+
+impl<F: PrimeField> SumPoly<F> {
+    /// The `SumPoly` of the expression `build` makes from the named
+    /// `inputs`, expanded into products of them. A coefficient other than
+    /// one scales the term's first factor, and a constant term is a constant
+    /// table. Fails if there are no inputs, two share a name, they range
+    /// over different variables, or the expression uses an input past them.
+    pub fn from_expr(
+        inputs: &[(&str, MultilinearPoly<F>)],
+        build: impl FnOnce(&Inputs<F>) -> Expr<F>,
+    ) -> Result<Self, ExprError> {
+        let (num_vars, products) = expand_over(inputs, build)?;
+
+        let sum_poly = products.into_iter().fold(
+            SumPoly::zero(num_vars),
+            |sum_poly, (coefficient, indices)| {
+                let mut factors: Vec<MultilinearPoly<F>> = indices
+                    .iter()
+                    .map(|index| inputs[*index].1.clone())
+                    .collect();
+                match factors.first_mut() {
+                    Some(first) if !coefficient.is_one() => *first = first.scale(coefficient),
+                    Some(_) => {}
+                    None => factors.push(constant(num_vars, coefficient)),
+                }
+
+                sum_poly
+                    .add_product(factors)
+                    .expect("every input has the common number of variables")
+            },
+        );
+
+        Ok(sum_poly)
+    }
+}
+
+impl<F: PrimeField> VirtualPolynomial<F> {
+    /// `SumPoly::from_expr`, storing each input once however many terms use
+    /// it. A constant term is its coefficient times a table of ones.
+    pub fn from_expr(
+        inputs: &[(&str, MultilinearPoly<F>)],
+        build: impl FnOnce(&Inputs<F>) -> Expr<F>,
+    ) -> Result<Self, ExprError> {
+        let (num_vars, products) = expand_over(inputs, build)?;
+
+        let shared: Vec<Arc<MultilinearPoly<F>>> = inputs
+            .iter()
+            .map(|(_, poly)| Arc::new(poly.clone()))
+            .collect();
+        let ones = Arc::new(constant(num_vars, F::one()));
+
+        let virtual_poly = products.into_iter().fold(
+            VirtualPolynomial::new(num_vars),
+            |virtual_poly, (coefficient, indices)| {
+                let factors = match indices.is_empty() {
+                    true => vec![ones.clone()],
+                    false => indices.iter().map(|index| shared[*index].clone()).collect(),
+                };
+
+                virtual_poly
+                    .add_product(coefficient, factors)
+                    .expect("every input has the common number of variables")
+            },
+        );
+
+        Ok(virtual_poly)
+    }
+}
+
+fn constant<F: PrimeField>(num_vars: usize, value: F) -> MultilinearPoly<F> {
+    MultilinearPoly::new(vec![value; checked_table_len(num_vars)])
+}
+
+#[cfg(test)]
+mod test {
+    use ark_bn254::Fq;
+    use ark_ff::UniformRand;
+    use rand::{rngs::StdRng, Rng, SeedableRng};
+
+    use super::{Expr, ExprError};
+    use crate::{
+        composed_polynomial::SumPoly, multilinear_polynomial_evaluation::MultilinearPoly,
+        virtual_polynomial::VirtualPolynomial,
+    };
+
+    fn layer_inputs(rng: &mut StdRng) -> Vec<(&'static str, MultilinearPoly<Fq>)> {
+        ["addi", "muli", "wb", "wc"]
+            .into_iter()
+            .map(|name| (name, MultilinearPoly::rand(4, rng)))
+            .collect()
+    }
+
+    #[test]
+    fn a_gate_expression_expands_into_its_formula() {
+        let mut rng = StdRng::seed_from_u64(95);
+        let inputs = layer_inputs(&mut rng);
+
+        let sum_poly = SumPoly::from_expr(&inputs, |v| {
+            v["addi"] * (v["wb"] + v["wc"]) + v["muli"] * v["wb"] * v["wc"]
+        })
+        .unwrap();
+        let virtual_poly = VirtualPolynomial::from_expr(&inputs, |v| {
+            v["addi"] * (v["wb"] + v["wc"]) + v["muli"] * v["wb"] * v["wc"]
+        })
+        .unwrap();
+        assert_eq!(sum_poly.polys.len(), 3);
+        assert_eq!(sum_poly.degree_in_bound_variable(), 3);
+        assert_eq!(virtual_poly.multilinears().len(), 4);
+
+        for _ in 0..8 {
+            let point: Vec<Fq> = (0..4).map(|_| Fq::from(rng.gen_range(0..2u64))).collect();
+            let [addi, muli, wb, wc] = [0, 1, 2, 3].map(|index| inputs[index].1.evaluate(&point));
+            let formula = addi * (wb + wc) + muli * wb * wc;
+
+            assert_eq!(sum_poly.evaluate(&point), formula);
+            assert_eq!(virtual_poly.evaluate(&point), formula);
+        }
+    }
+
+    #[test]
+    fn like_terms_and_constants_are_collected() {
+        let mut rng = StdRng::seed_from_u64(96);
+        let inputs = vec![("a", MultilinearPoly::<Fq>::rand(3, &mut rng))];
+        let c = Fq::rand(&mut rng);
+
+        // (a + c)·(a + 1) - a·a = (c + 1)·a + c
+        let sum_poly = SumPoly::from_expr(&inputs, |v| {
+            (v["a"] + c) * (v["a"] + Fq::from(1)) + v["a"] * v["a"] * -Fq::from(1)
+        })
+        .unwrap();
+        assert_eq!(sum_poly.polys.len(), 2);
+
+        let point: Vec<Fq> = (0..3).map(|_| Fq::rand(&mut rng)).collect();
+        let a = inputs[0].1.evaluate(&point);
+        assert_eq!(sum_poly.evaluate(&point), (c + Fq::from(1)) * a + c);
+
+        // Everything cancels.
+        let zero = SumPoly::from_expr(&inputs, |v| v["a"] + v["a"] * -Fq::from(1)).unwrap();
+        assert!(zero.reduce().is_zero());
+    }
+
+    #[test]
+    fn malformed_inputs_are_rejected() {
+        let mut rng = StdRng::seed_from_u64(97);
+        let a = MultilinearPoly::<Fq>::rand(3, &mut rng);
+        let b = MultilinearPoly::<Fq>::rand(2, &mut rng);
+
+        assert_eq!(
+            SumPoly::<Fq>::from_expr(&[], |_| Expr::Const(Fq::from(1))),
+            Err(ExprError::NoInputs)
+        );
+        assert_eq!(
+            SumPoly::from_expr(&[("a", a.clone()), ("a", a.clone())], |v| v["a"] * v["a"]),
+            Err(ExprError::DuplicateInput("a".to_string()))
+        );
+        assert_eq!(
+            SumPoly::from_expr(&[("a", a.clone()), ("b", b)], |v| v["a"] * v["b"]),
+            Err(ExprError::MismatchedNumVars {
+                input: "b".to_string(),
+                expected: 3,
+                got: 2
+            })
+        );
+        assert_eq!(
+            VirtualPolynomial::from_expr(&[("a", a)], |v| v["a"] * Expr::Var(1)),
+            Err(ExprError::UnknownInput {
+                index: 1,
+                num_inputs: 1
+            })
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "no input named b")]
+    fn an_unknown_name_panics() {
+        let a = MultilinearPoly::<Fq>::zero(2);
+        let _ = SumPoly::from_expr(&[("a", a)], |v| v["a"] * v["b"]);
+    }
+}
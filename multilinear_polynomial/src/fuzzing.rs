@@ -0,0 +1,108 @@
+use arbitrary::{Arbitrary, Result, Unstructured};
+use ark_ff::PrimeField;
+
+use crate::{
+    composed_polynomial::{Factor, ProductPoly, SumPoly},
+    multilinear_polynomial_evaluation::MultilinearPoly,
+};
+
+/// The largest number of variables a generated polynomial ranges over, so
+/// that a sum-check over any generated polynomial runs in milliseconds.
+pub const MAX_VARS: usize = 8;
+
+/// The largest number of terms, and of factors per term, in a generated
+/// `SumPoly`.
+pub const MAX_TERMS: usize = 3;
+
+/// The bytes read per field element. Reducing 48 bytes keeps the bias from
+/// the modular reduction negligible for 256-bit fields.
+pub const FIELD_BYTES: usize = 48;
+
+/// Enough bytes for the largest value the impls below build.
+pub const MAX_ARBITRARY_BYTES: usize = MAX_TERMS * MAX_TERMS * (FIELD_BYTES << MAX_VARS) + 64;
+
+/// A field element reduced from `FIELD_BYTES` of input.
+pub fn arbitrary_field<F: PrimeField>(u: &mut Unstructured<'_>) -> Result<F> {
+    Ok(F::from_le_bytes_mod_order(
+        &u.arbitrary::<[u8; FIELD_BYTES]>()?,
+    ))
+}
+
+/// A polynomial over exactly `num_vars` variables.
+pub fn arbitrary_poly_over<F: PrimeField>(
+    u: &mut Unstructured<'_>,
+    num_vars: usize,
+) -> Result<MultilinearPoly<F>> {
+    let evaluations = (0..1 << num_vars)
+        .map(|_| arbitrary_field(u))
+        .collect::<Result<_>>()?;
+
+    Ok(MultilinearPoly::new(evaluations))
+}
+
+/// Ranges over 1 to `MAX_VARS` variables.
+impl<'a, F: PrimeField> Arbitrary<'a> for MultilinearPoly<F> {
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        let num_vars = u.int_in_range(1..=MAX_VARS)?;
+
+        arbitrary_poly_over(u, num_vars)
+    }
+}
+
+/// A sum of 1 to `MAX_TERMS` products of 1 to `MAX_TERMS` dense factors,
+/// over 1 to `MAX_VARS` variables.
+impl<'a, F: PrimeField> Arbitrary<'a> for SumPoly<F> {
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        let num_vars = u.int_in_range(1..=MAX_VARS)?;
+        let num_terms = u.int_in_range(1..=MAX_TERMS)?;
+        let degree = u.int_in_range(1..=MAX_TERMS)?;
+
+        let terms = (0..num_terms)
+            .map(|_| {
+                let factors = (0..degree)
+                    .map(|_| arbitrary_poly_over(u, num_vars).map(Factor::Dense))
+                    .collect::<Result<_>>()?;
+
+                Ok(ProductPoly::from_factors(factors))
+            })
+            .collect::<Result<_>>()?;
+
+        Ok(SumPoly::new(terms))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use arbitrary::{Arbitrary, Unstructured};
+    use ark_bn254::Fq;
+    use rand::{rngs::StdRng, Rng, SeedableRng};
+
+    use super::{MAX_ARBITRARY_BYTES, MAX_TERMS, MAX_VARS};
+    use crate::{composed_polynomial::SumPoly, multilinear_polynomial_evaluation::MultilinearPoly};
+
+    #[test]
+    fn generated_polynomials_stay_within_the_bounds() {
+        let mut rng = StdRng::seed_from_u64(74);
+
+        for _ in 0..8 {
+            let mut bytes = vec![0u8; MAX_ARBITRARY_BYTES];
+            rng.fill(bytes.as_mut_slice());
+            let mut u = Unstructured::new(&bytes);
+
+            let poly = MultilinearPoly::<Fq>::arbitrary(&mut u).unwrap();
+            assert!((1..=MAX_VARS).contains(&poly.num_vars()));
+
+            let sum_poly = SumPoly::<Fq>::arbitrary(&mut u).unwrap();
+            assert!((1..=MAX_VARS).contains(&sum_poly.num_vars().unwrap()));
+            assert!((1..=MAX_TERMS).contains(&sum_poly.get_degree()));
+        }
+    }
+
+    #[test]
+    fn exhausted_input_still_builds_a_polynomial() {
+        let poly = MultilinearPoly::<Fq>::arbitrary(&mut Unstructured::new(&[])).unwrap();
+
+        assert_eq!(poly.num_vars(), 1);
+        assert!(poly.is_zero());
+    }
+}
@@ -0,0 +1,101 @@
+use std::marker::PhantomData;
+
+use ark_ff::PrimeField;
+
+use crate::multilinear_polynomial_evaluation::{checked_table_len, MultilinearPoly};
+
+/// The points of `{0, 1}^num_vars` in table order: the `k`-th point spells
+/// `k` in binary with the first variable most significant, so it is the
+/// point at which `evaluation[k]` is taken.
+#[derive(Clone, Debug)]
+pub struct BooleanHypercube<F: PrimeField> {
+    num_vars: usize,
+    next: usize,
+    _field: PhantomData<F>,
+}
+
+impl<F: PrimeField> BooleanHypercube<F> {
+    /// Panics past `MAX_SUPPORTED_VARS` variables.
+    pub fn new(num_vars: usize) -> Self {
+        checked_table_len(num_vars);
+
+        Self {
+            num_vars,
+            next: 0,
+            _field: PhantomData,
+        }
+    }
+
+    fn remaining(&self) -> usize {
+        (1 << self.num_vars) - self.next
+    }
+}
+
+impl<F: PrimeField> Iterator for BooleanHypercube<F> {
+    type Item = Vec<F>;
+
+    fn next(&mut self) -> Option<Vec<F>> {
+        if self.remaining() == 0 {
+            return None;
+        }
+
+        let index = self.next;
+        self.next += 1;
+
+        Some(
+            (0..self.num_vars)
+                .map(|i| F::from(((index >> (self.num_vars - 1 - i)) & 1) as u64))
+                .collect(),
+        )
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.remaining(), Some(self.remaining()))
+    }
+}
+
+impl<F: PrimeField> ExactSizeIterator for BooleanHypercube<F> {}
+
+impl<F: PrimeField> MultilinearPoly<F> {
+    /// Every hypercube point together with the polynomial's value there, in
+    /// table order.
+    pub fn hypercube_iter(&self) -> impl Iterator<Item = (Vec<F>, F)> + '_ {
+        BooleanHypercube::new(self.num_of_vars).zip(self.evaluation.iter().copied())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use ark_bn254::Fq;
+    use ark_ff::UniformRand;
+    use rand::{rngs::StdRng, SeedableRng};
+
+    use super::BooleanHypercube;
+    use crate::multilinear_polynomial_evaluation::MultilinearPoly;
+
+    #[test]
+    fn it_walks_the_hypercube_in_table_order() {
+        let points: Vec<Vec<Fq>> = BooleanHypercube::new(2).collect();
+        assert_eq!(
+            points,
+            vec![
+                vec![Fq::from(0), Fq::from(0)],
+                vec![Fq::from(0), Fq::from(1)],
+                vec![Fq::from(1), Fq::from(0)],
+                vec![Fq::from(1), Fq::from(1)],
+            ]
+        );
+        assert_eq!(BooleanHypercube::<Fq>::new(0).count(), 1);
+        assert_eq!(BooleanHypercube::<Fq>::new(5).len(), 32);
+
+        let mut rng = StdRng::seed_from_u64(68);
+        let polynomial = MultilinearPoly::new((0..16).map(|_| Fq::rand(&mut rng)).collect());
+
+        for (point, value) in polynomial.hypercube_iter() {
+            assert_eq!(polynomial.evaluate(&point), value);
+        }
+
+        let naive: Fq = polynomial.hypercube_iter().map(|(_, value)| value).sum();
+        assert_eq!(polynomial.hypercube_sum(), naive);
+    }
+}
@@ -1,2 +1,12 @@
 pub mod composed_polynomial;
+pub mod display;
+pub mod eval_store;
+pub mod evaluation_claim;
+pub mod expression;
+#[cfg(feature = "arbitrary")]
+pub mod fuzzing;
+pub mod hypercube;
 pub mod multilinear_polynomial_evaluation;
+mod serialization;
+pub mod sparse_multilinear_polynomial;
+pub mod virtual_polynomial;
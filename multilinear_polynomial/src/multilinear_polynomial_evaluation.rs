@@ -1,64 +1,768 @@
-use ark_ff::PrimeField;
-use std::ops::{Add, Mul};
+use ark_ff::{BigInteger, Field, PrimeField};
+use ark_std::rand::RngCore;
+#[cfg(feature = "parallel")]
+use rayon::prelude::*;
+use sha3::{Digest, Keccak256};
+use std::{
+    fmt,
+    hash::{Hash, Hasher},
+    ops::{Add, AddAssign, Mul, MulAssign, Neg, Sub, SubAssign},
+    sync::OnceLock,
+};
+use univariate_polynomial::univariate_polynomial_dense::UnivariatePoly;
 
-#[derive(Clone, Debug, PartialEq)]
-pub struct MultilinearPoly<F: PrimeField> {
-    pub evaluation: Vec<F>,
-    pub num_of_vars: usize,
+/// A multilinear polynomial given by its evaluations over the hypercube.
+///
+/// Variable `i` is bit `num_vars() - 1 - i` of the table index, so variable
+/// 0 is the most significant bit and `evaluations()[index]` is the value at
+/// the point whose coordinates spell `index` in binary from the left. Tables
+/// laid out least significant variable first can be brought into this order
+/// with `reverse_variable_order`.
+///
+/// The table always holds `2^num_vars()` entries; outside this crate it is
+/// only reachable through the accessors, so that invariant cannot be broken.
+///
+/// Equality and hashing compare the tables, so they include the number of
+/// variables: the constant `c` over no variables is not equal to `c` over
+/// two variables, even though both take the value `c` everywhere. Compare
+/// `is_constant()` to test that instead.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct MultilinearPoly<F: Field> {
+    pub(crate) evaluation: Vec<F>,
+    pub(crate) num_of_vars: usize,
+    pub(crate) zero: ZeroCache,
 }
 
-impl<F: PrimeField> MultilinearPoly<F> {
-    pub fn new(evaluations: Vec<F>) -> Self {
-        let num_of_vars: usize = evaluations.len().ilog2() as usize;
+/// Whether a table is all zeros, found on the first `is_zero` and kept until
+/// the table changes. It follows from the table, so equality and hashing
+/// ignore it.
+#[derive(Clone, Debug, Default)]
+pub(crate) struct ZeroCache(OnceLock<bool>);
+
+impl ZeroCache {
+    fn known_zero() -> Self {
+        Self(OnceLock::from(true))
+    }
 
-        if evaluations.len() != 1 << num_of_vars {
-            panic!("Invalid evaluations");
+    /// For a change that keeps a zero table zero but may zero out others.
+    fn forget_unless_zero(&mut self) {
+        if self.0.get() != Some(&true) {
+            self.0 = OnceLock::new();
         }
+    }
 
-        Self {
-            evaluation: evaluations,
+    fn forget(&mut self) {
+        self.0 = OnceLock::new();
+    }
+}
+
+impl PartialEq for ZeroCache {
+    fn eq(&self, _: &Self) -> bool {
+        true
+    }
+}
+
+impl Eq for ZeroCache {}
+
+impl Hash for ZeroCache {
+    fn hash<H: Hasher>(&self, _: &mut H) {}
+}
+
+/// The most variables a table may have, 2^32 entries; past it the
+/// allocation alone would exhaust any machine this runs on. Everything that
+/// builds a table from a variable count checks it through `table_len`.
+pub const MAX_SUPPORTED_VARS: usize = 32;
+
+/// `2^num_vars`, the length of a table of `num_vars` variables. Fails past
+/// `MAX_SUPPORTED_VARS` or where the length does not fit in a `usize`, as on
+/// 32-bit targets, instead of letting the shift wrap.
+pub fn table_len(num_vars: usize) -> Result<usize, MultilinearError> {
+    u32::try_from(num_vars)
+        .ok()
+        .filter(|_| num_vars <= MAX_SUPPORTED_VARS)
+        .and_then(|shift| 1usize.checked_shl(shift))
+        .ok_or(MultilinearError::TooManyVariables {
+            num_of_vars: num_vars,
+        })
+}
+
+/// `table_len` for constructors that panic on bad input.
+pub(crate) fn checked_table_len(num_vars: usize) -> usize {
+    table_len(num_vars).unwrap_or_else(|error| panic!("{}", error))
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum MultilinearError {
+    VariableOutOfRange {
+        bit: usize,
+        num_of_vars: usize,
+    },
+    WrongNumberOfValues {
+        expected: usize,
+        got: usize,
+    },
+    LengthNotPowerOfTwo {
+        len: usize,
+    },
+    IndexOutOfRange {
+        index: usize,
+        num_of_vars: usize,
+    },
+    DuplicateIndex {
+        index: usize,
+    },
+    MismatchedNumVars {
+        left: usize,
+        right: usize,
+    },
+    InvalidPermutation {
+        num_of_vars: usize,
+    },
+    RepeatedVariable {
+        bit: usize,
+    },
+    TooManyVariables {
+        num_of_vars: usize,
+    },
+    RaggedMatrix {
+        row: usize,
+        expected: usize,
+        got: usize,
+    },
+}
+
+impl fmt::Display for MultilinearError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            MultilinearError::VariableOutOfRange { bit, num_of_vars } => write!(
+                f,
+                "no variable {} to bind in a polynomial of {} variables",
+                bit, num_of_vars
+            ),
+            MultilinearError::WrongNumberOfValues { expected, got } => write!(
+                f,
+                "a point of {} values for a polynomial of {} variables",
+                got, expected
+            ),
+            MultilinearError::LengthNotPowerOfTwo { len } => write!(
+                f,
+                "{} evaluations do not fill a hypercube, which needs a power of two",
+                len
+            ),
+            MultilinearError::IndexOutOfRange { index, num_of_vars } => write!(
+                f,
+                "index {} is outside a hypercube of {} variables",
+                index, num_of_vars
+            ),
+            MultilinearError::DuplicateIndex { index } => {
+                write!(f, "index {} is given more than once", index)
+            }
+            MultilinearError::MismatchedNumVars { left, right } => write!(
+                f,
+                "cannot combine polynomials of {} and {} variables",
+                left, right
+            ),
+            MultilinearError::InvalidPermutation { num_of_vars } => {
+                write!(f, "not a permutation of the {} variables", num_of_vars)
+            }
+            MultilinearError::RepeatedVariable { bit } => {
+                write!(f, "variable {} is assigned more than once", bit)
+            }
+            MultilinearError::TooManyVariables { num_of_vars } => write!(
+                f,
+                "a table of {} variables is over the limit of {}",
+                num_of_vars, MAX_SUPPORTED_VARS
+            ),
+            MultilinearError::RaggedMatrix { row, expected, got } => write!(
+                f,
+                "matrix row {} has {} entries, expected {}",
+                row, got, expected
+            ),
+        }
+    }
+}
+
+impl std::error::Error for MultilinearError {}
+
+impl<F: Field> MultilinearPoly<F> {
+    /// The polynomial with these evaluations, of which there must be a
+    /// nonzero power of two.
+    pub fn try_new(evaluations: Vec<F>) -> Result<Self, MultilinearError> {
+        if !evaluations.len().is_power_of_two() {
+            return Err(MultilinearError::LengthNotPowerOfTwo {
+                len: evaluations.len(),
+            });
+        }
+
+        let num_of_vars = evaluations.len().ilog2() as usize;
+        table_len(num_of_vars)?;
+
+        Ok(Self {
             num_of_vars,
+            zero: ZeroCache::default(),
+            evaluation: evaluations,
+        })
+    }
+
+    /// `try_new` for a table the caller knows has a power-of-two length.
+    /// Panics otherwise.
+    pub fn new(evaluations: Vec<F>) -> Self {
+        Self::try_new(evaluations).unwrap_or_else(|error| panic!("{}", error))
+    }
+
+    pub fn num_vars(&self) -> usize {
+        self.num_of_vars
+    }
+
+    /// The number of evaluations, `2^num_vars()`.
+    pub fn len(&self) -> usize {
+        self.evaluation.len()
+    }
+
+    /// Always `false`: even a polynomial of no variables has one evaluation.
+    pub fn is_empty(&self) -> bool {
+        self.evaluation.is_empty()
+    }
+
+    /// The evaluations over the hypercube, in table order.
+    pub fn evaluations(&self) -> &[F] {
+        &self.evaluation
+    }
+
+    pub fn into_evaluations(self) -> Vec<F> {
+        self.evaluation
+    }
+
+    /// The polynomial's value if it takes the same value everywhere on the
+    /// hypercube, which makes it that constant everywhere.
+    pub fn is_constant(&self) -> Option<F> {
+        let first = self.evaluation[0];
+
+        self.evaluation
+            .iter()
+            .all(|eval| *eval == first)
+            .then_some(first)
+    }
+
+    /// The polynomial with these signed evaluations, a negative value `-v`
+    /// standing for `p - v`. Panics unless there is a power of two of them.
+    pub fn from_i64s(values: &[i64]) -> Self {
+        Self::new(values.iter().map(|value| F::from(*value)).collect())
+    }
+
+    /// The multilinear extension of `values`, padded with zeros up to the
+    /// next power of two so any data vector can be encoded.
+    pub fn mle_of(values: &[u64]) -> Self {
+        let len = values.len().max(1).next_power_of_two();
+        let mut evaluation: Vec<F> = values.iter().map(|value| F::from(*value)).collect();
+        evaluation.resize(len, F::zero());
+
+        Self::new(evaluation)
+    }
+
+    /// The polynomial whose value at hypercube index `index` is `f(index)`.
+    pub fn from_fn(num_vars: usize, f: impl Fn(usize) -> F) -> Self {
+        Self {
+            evaluation: (0..checked_table_len(num_vars)).map(f).collect(),
+            num_of_vars: num_vars,
+            zero: ZeroCache::default(),
+        }
+    }
+
+    /// The polynomial that is `value` at each given `index` and zero
+    /// elsewhere. Every index must fit in `num_vars` bits and appear once.
+    pub fn from_entries(num_vars: usize, entries: &[(usize, F)]) -> Result<Self, MultilinearError> {
+        let len = table_len(num_vars)?;
+        let mut evaluation = vec![F::zero(); len];
+        let mut seen = vec![false; len];
+
+        for &(index, value) in entries {
+            if index >> num_vars != 0 {
+                return Err(MultilinearError::IndexOutOfRange {
+                    index,
+                    num_of_vars: num_vars,
+                });
+            }
+
+            if std::mem::replace(&mut seen[index], true) {
+                return Err(MultilinearError::DuplicateIndex { index });
+            }
+
+            evaluation[index] = value;
+        }
+
+        Ok(Self {
+            evaluation,
+            num_of_vars: num_vars,
+            zero: ZeroCache::default(),
+        })
+    }
+
+    /// Binds variable `bit` to `value`, leaving a polynomial in the other
+    /// variables in their original order.
+    pub fn partial_evaluate(&self, bit: usize, value: &F) -> Result<Self, MultilinearError> {
+        self.check_variable(bit)?;
+
+        Ok(self.bind(bit, value))
+    }
+
+    /// Same as `partial_evaluate`, but writes the folded table into the front
+    /// of the existing buffer and truncates it instead of allocating.
+    pub fn partial_evaluate_in_place(
+        &mut self,
+        bit: usize,
+        value: &F,
+    ) -> Result<(), MultilinearError> {
+        self.check_variable(bit)?;
+
+        self.bind_in_place(bit, value);
+
+        Ok(())
+    }
+
+    fn check_variable(&self, bit: usize) -> Result<(), MultilinearError> {
+        if bit >= self.num_of_vars {
+            return Err(MultilinearError::VariableOutOfRange {
+                bit,
+                num_of_vars: self.num_of_vars,
+            });
         }
+
+        Ok(())
     }
 
-    fn pair_points(bit: usize, num_of_vars: usize) -> Vec<(usize, usize)> {
-        let mut result = vec![];
-        let target_hc = num_of_vars - 1;
+    /// `partial_evaluate` for a `bit` the caller knows is in range.
+    pub(crate) fn bind(&self, bit: usize, value: &F) -> Self {
+        let inverted_index = self.num_of_vars - bit - 1;
+        let half = 1 << (self.num_of_vars - 1);
 
-        for val in 0..(1 << target_hc) {
-            let inverted_index = num_of_vars - bit - 1;
+        // Each output entry folds the pair of entries whose indices differ
+        // only in the bound variable's bit.
+        let fold = |val: usize| {
             let insert_zero = insert_bit(val, inverted_index);
             let insert_one = insert_zero | (1 << inverted_index);
-            result.push((insert_zero, insert_one));
+
+            let a = self.evaluation[insert_zero];
+            let b = self.evaluation[insert_one];
+
+            a + *value * (b - a)
+        };
+
+        #[cfg(feature = "parallel")]
+        let result = (0..half).into_par_iter().map(fold).collect();
+
+        #[cfg(not(feature = "parallel"))]
+        let result = (0..half).map(fold).collect();
+
+        Self::new(result)
+    }
+
+    /// Whether the table changes along variable `bit`, which the caller
+    /// knows is in range. A table that does not is constant in it.
+    pub(crate) fn depends_on(&self, bit: usize) -> bool {
+        let stride = 1 << (self.num_of_vars - bit - 1);
+
+        self.evaluation
+            .chunks(2 * stride)
+            .any(|pair| pair[..stride] != pair[stride..])
+    }
+
+    /// `partial_evaluate_in_place` for a `bit` the caller knows is in range.
+    pub(crate) fn bind_in_place(&mut self, bit: usize, value: &F) {
+        let inverted_index = self.num_of_vars - bit - 1;
+        let half = 1 << (self.num_of_vars - 1);
+
+        if bit == 0 {
+            let (zeros, ones) = self.evaluation.split_at_mut(half);
+
+            #[cfg(feature = "parallel")]
+            zeros
+                .par_iter_mut()
+                .zip(ones.par_iter())
+                .for_each(|(a, b)| *a += *value * (*b - *a));
+
+            #[cfg(not(feature = "parallel"))]
+            zeros
+                .iter_mut()
+                .zip(ones.iter())
+                .for_each(|(a, b)| *a += *value * (*b - *a));
+        } else {
+            // Entry `val` only reads from indices at or above `val`, so
+            // writing in increasing order never clobbers an unread entry.
+            for val in 0..half {
+                let insert_zero = insert_bit(val, inverted_index);
+                let insert_one = insert_zero | (1 << inverted_index);
+
+                let a = self.evaluation[insert_zero];
+                let b = self.evaluation[insert_one];
+
+                self.evaluation[val] = a + *value * (b - a);
+            }
         }
-        result
+
+        self.evaluation.truncate(half);
+        self.num_of_vars -= 1;
+        self.zero.forget_unless_zero();
     }
 
-    pub fn partial_evaluate(&self, bit: usize, value: &F) -> Self {
-        let mut result = vec![];
+    /// The polynomial's value at `point`, which must have one value per
+    /// variable.
+    pub fn try_evaluate(&self, point: &[F]) -> Result<F, MultilinearError> {
+        if point.len() != self.num_of_vars {
+            return Err(MultilinearError::WrongNumberOfValues {
+                expected: self.num_of_vars,
+                got: point.len(),
+            });
+        }
 
-        for (a, b) in MultilinearPoly::<F>::pair_points(bit, self.num_of_vars).into_iter() {
-            let a = self.evaluation[a];
-            let b = self.evaluation[b];
+        Ok(self.fold_streaming(point, &mut Vec::with_capacity(point.len() + 1)))
+    }
 
-            result.push(a + *value * (b - a));
+    /// Binds every variable to `point` in place and returns the value, leaving
+    /// a table of one entry behind. Panics if `point` has the wrong length.
+    pub fn fold_all_in_place(&mut self, point: &[F]) -> F {
+        if point.len() != self.num_of_vars {
+            panic!(
+                "{}",
+                MultilinearError::WrongNumberOfValues {
+                    expected: self.num_of_vars,
+                    got: point.len(),
+                }
+            );
         }
 
-        Self::new(result)
+        for value in point {
+            self.bind_in_place(0, value);
+        }
+
+        self.evaluation[0]
+    }
+
+    /// Evaluates the polynomial at every point, sharing one scratch stack
+    /// per thread. Panics if a point has the wrong length, like `evaluate`.
+    pub fn evaluate_batch(&self, points: &[Vec<F>]) -> Vec<F> {
+        if let Some(point) = points.iter().find(|point| point.len() != self.num_of_vars) {
+            panic!(
+                "{}",
+                MultilinearError::WrongNumberOfValues {
+                    expected: self.num_of_vars,
+                    got: point.len(),
+                }
+            );
+        }
+
+        let scratch = || Vec::with_capacity(self.num_of_vars + 1);
+
+        #[cfg(feature = "parallel")]
+        let evals = points
+            .par_iter()
+            .map_init(scratch, |stack, point| self.fold_streaming(point, stack))
+            .collect();
+
+        #[cfg(not(feature = "parallel"))]
+        let evals = {
+            let mut stack = scratch();
+
+            points
+                .iter()
+                .map(|point| self.fold_streaming(point, &mut stack))
+                .collect()
+        };
+
+        evals
+    }
+
+    /// Folds the table in one pass, the last variable first, keeping only
+    /// the partial fold of each completed block on `stack`. Takes time
+    /// linear in the table and room for one value per variable.
+    fn fold_streaming(&self, point: &[F], stack: &mut Vec<F>) -> F {
+        stack.clear();
+
+        for (index, value) in self.evaluation.iter().enumerate() {
+            let mut folded = *value;
+
+            // Every trailing one bit of `index` completes a block whose left
+            // half is already on the stack.
+            let mut level = 0;
+            while index >> level & 1 == 1 {
+                let left = stack.pop().unwrap();
+                folded = left + point[self.num_of_vars - 1 - level] * (folded - left);
+                level += 1;
+            }
+
+            stack.push(folded);
+        }
+
+        stack[0]
+    }
+
+    /// `try_evaluate` for a point the caller knows has the right length.
+    /// Panics otherwise.
+    pub fn evaluate(&self, point: &[F]) -> F {
+        self.try_evaluate(point)
+            .unwrap_or_else(|error| panic!("{}", error))
+    }
+
+    /// `Σ_x f(x)·eq(x, point)` in one pass over the table, walking the
+    /// hypercube in order and keeping `eq(x, point)` as running products of
+    /// the per-variable factors `1 - r_i` and `r_i`. Only the changed low
+    /// bits of each index are recomputed, so the walk costs about two
+    /// multiplications per entry and `n + 1` values of memory. Panics if
+    /// `point` has the wrong length.
+    pub fn evaluate_streaming(&self, point: &[F]) -> F {
+        if point.len() != self.num_of_vars {
+            panic!(
+                "{}",
+                MultilinearError::WrongNumberOfValues {
+                    expected: self.num_of_vars,
+                    got: point.len(),
+                }
+            );
+        }
+
+        let n = self.num_of_vars;
+        let factors: Vec<[F; 2]> = point.iter().map(|r| [F::one() - r, *r]).collect();
+
+        // `prefix[k]` is the product of the factors of the first `k`
+        // variables at the current index.
+        let mut prefix = vec![F::one(); n + 1];
+        for k in 0..n {
+            prefix[k + 1] = prefix[k] * factors[k][0];
+        }
+
+        let mut sum = self.evaluation[0] * prefix[n];
+        for (index, value) in self.evaluation.iter().enumerate().skip(1) {
+            // Stepping to `index` sets bit `trailing_zeros` and clears the
+            // bits below it, which belong to the last variables.
+            let first_changed = n - 1 - index.trailing_zeros() as usize;
+            for k in first_changed..n {
+                prefix[k + 1] = prefix[k] * factors[k][index >> (n - 1 - k) & 1];
+            }
+
+            sum += *value * prefix[n];
+        }
+
+        sum
+    }
+
+    /// The coefficients of the polynomial as a sum of monomials. Entry `m` is
+    /// the coefficient of the product of the variables whose bits are set
+    /// in `m`, with the same bit order as the evaluation table.
+    pub fn to_coefficients(&self) -> Vec<F> {
+        let mut coefficients = self.evaluation.clone();
+
+        // Möbius transform: each pass strips one variable's contribution
+        // from the entries that have its bit set.
+        for bit in 0..self.num_of_vars {
+            for index in 0..coefficients.len() {
+                if index >> bit & 1 == 1 {
+                    let without = coefficients[index ^ (1 << bit)];
+                    coefficients[index] -= without;
+                }
+            }
+        }
+
+        coefficients
+    }
+
+    /// The polynomial with these monomial coefficients, laid out like the
+    /// output of `to_coefficients`.
+    pub fn from_coefficients(coefficients: Vec<F>) -> Self {
+        let mut polynomial = Self::new(coefficients);
+
+        for bit in 0..polynomial.num_of_vars {
+            for index in 0..polynomial.evaluation.len() {
+                if index >> bit & 1 == 1 {
+                    let without = polynomial.evaluation[index ^ (1 << bit)];
+                    polynomial.evaluation[index] += without;
+                }
+            }
+        }
+
+        polynomial
+    }
+
+    /// The degree of the polynomial in variable `bit`: one if the variable
+    /// appears and zero if the polynomial does not depend on it.
+    pub fn degree_in_variable(&self, bit: usize) -> Result<usize, MultilinearError> {
+        self.check_variable(bit)?;
+
+        let stride = 1 << (self.num_of_vars - 1 - bit);
+        let appears = (0..self.evaluation.len())
+            .filter(|index| index & stride == 0)
+            .any(|index| self.evaluation[index] != self.evaluation[index | stride]);
+
+        Ok(appears as usize)
+    }
+
+    /// The polynomial `g` with variable `i` of `g` standing for variable
+    /// `perm[i]` of `self`, so that `g(y) = self(x)` whenever
+    /// `y[i] = x[perm[i]]`. Fails unless `perm` holds each variable once.
+    pub fn permute_variables(&self, perm: &[usize]) -> Result<Self, MultilinearError> {
+        let n = self.num_of_vars;
+
+        let mut seen = vec![false; n];
+        if perm.len() != n
+            || perm
+                .iter()
+                .any(|&old| old >= n || std::mem::replace(&mut seen[old], true))
+        {
+            return Err(MultilinearError::InvalidPermutation { num_of_vars: n });
+        }
+
+        // Variable `i` is bit `n - 1 - i`, so new bit `n - 1 - i` of an index
+        // moves to old bit `n - 1 - perm[i]`.
+        let old_index = |index: usize| {
+            perm.iter().enumerate().fold(0, |old, (new, &var)| {
+                old | ((index >> (n - 1 - new)) & 1) << (n - 1 - var)
+            })
+        };
+
+        Ok(Self {
+            evaluation: (0..self.evaluation.len())
+                .map(|index| self.evaluation[old_index(index)])
+                .collect(),
+            num_of_vars: n,
+            zero: ZeroCache::default(),
+        })
+    }
+
+    /// The same polynomial with its variables in the opposite order, which
+    /// converts between this crate's most-significant-first tables and
+    /// least-significant-first ones.
+    pub fn reverse_variable_order(&self) -> Self {
+        let perm: Vec<usize> = (0..self.num_of_vars).rev().collect();
+
+        self.permute_variables(&perm)
+            .expect("reversal is a permutation")
+    }
+
+    /// The table of `eq(x, point)` over the hypercube, which is one at the
+    /// vertex `point` spells when `point` is boolean. Each variable doubles
+    /// the table with one multiplication per entry.
+    pub fn eq_mle(point: &[F]) -> Self {
+        let mut evaluation = vec![F::zero(); checked_table_len(point.len())];
+        evaluation[0] = F::one();
+
+        for (filled, r) in point.iter().enumerate() {
+            // Entry `j` splits into `2j` and `2j + 1`, so going down from the
+            // top never overwrites an entry before it is split.
+            for j in (0..1 << filled).rev() {
+                let one = evaluation[j] * r;
+                evaluation[2 * j + 1] = one;
+                evaluation[2 * j] = evaluation[j] - one;
+            }
+        }
+
+        Self {
+            evaluation,
+            num_of_vars: point.len(),
+            zero: ZeroCache::default(),
+        }
+    }
+
+    /// Encodes a matrix with a power-of-two number of rows and columns.
+    /// Entries are laid out row-major, so the row index occupies the first
+    /// variables and the column index the last ones.
+    pub fn from_matrix(rows: &[Vec<F>]) -> Result<Self, MultilinearError> {
+        let num_cols = rows.first().map_or(0, Vec::len);
+
+        if let Some(row) = rows.iter().position(|row| row.len() != num_cols) {
+            return Err(MultilinearError::RaggedMatrix {
+                row,
+                expected: num_cols,
+                got: rows[row].len(),
+            });
+        }
+
+        Self::try_new(rows.concat())
     }
 
-    pub fn evaluate(&self, values: Vec<F>) -> F {
-        if values.len() != self.num_of_vars {
-            panic!("Invalid number of values");
+    /// Binds each variable `bit` in `assignments` to its value at once,
+    /// leaving a polynomial in the other variables in their original order.
+    /// Takes a single pass over the table.
+    pub fn restrict(&self, assignments: &[(usize, F)]) -> Result<Self, MultilinearError> {
+        let n = self.num_of_vars;
+        let mut assignments = assignments.to_vec();
+        assignments.sort_unstable_by_key(|(bit, _)| *bit);
+
+        for (position, (bit, _)) in assignments.iter().enumerate() {
+            self.check_variable(*bit)?;
+
+            if position > 0 && assignments[position - 1].0 == *bit {
+                return Err(MultilinearError::RepeatedVariable { bit: *bit });
+            }
         }
 
-        let mut result = self.clone();
+        // Variable `i` is bit `n - 1 - i` of an index. The bound bits of an
+        // index pick its weight in `eq`, the free ones its output entry.
+        let bound_mask = assignments
+            .iter()
+            .fold(0, |mask, (bit, _)| mask | 1 << (n - 1 - bit));
+        let values: Vec<F> = assignments.iter().map(|(_, value)| *value).collect();
+        let eq = Self::eq_mle(&values).evaluation;
+
+        let mut evaluation = vec![F::zero(); 1 << (n - assignments.len())];
+        for (index, eval) in self.evaluation.iter().enumerate() {
+            let bound = gather_bits(index, bound_mask);
+            let free = gather_bits(index, !bound_mask & ((1 << n) - 1));
 
-        for value in values.iter() {
-            result = result.partial_evaluate(0, value);
+            evaluation[free] += eq[bound] * eval;
         }
 
-        result.evaluation[0]
+        Ok(Self {
+            evaluation,
+            num_of_vars: n - assignments.len(),
+            zero: ZeroCache::default(),
+        })
+    }
+
+    /// Binds the first `point.len()` variables to `point`. Those are the
+    /// high bits of a table index, so entry `j` of the result folds the
+    /// entries whose low `num_of_vars - point.len()` bits spell `j`.
+    ///
+    /// The first variable folds into the one half-size table the result
+    /// lives in, and the rest fold within it. Panics if `point` has more
+    /// values than the polynomial has variables.
+    pub fn fix_prefix(&self, point: &[F]) -> Self {
+        self.fix_in_order(point, |_| 0)
+    }
+
+    /// Binds the last `point.len()` variables to `point`, in order. Those are
+    /// the low bits of a table index, so entry `j` of the result folds the
+    /// entries whose high bits spell `j`. Allocates like `fix_prefix`.
+    pub fn fix_suffix(&self, point: &[F]) -> Self {
+        let first_bound = self.num_of_vars.saturating_sub(point.len());
+
+        // Binding the last variable first leaves the others in place.
+        let values: Vec<F> = point.iter().rev().copied().collect();
+        let result = self.fix_in_order(&values, |num_of_vars| num_of_vars - 1);
+        debug_assert_eq!(result.num_of_vars, first_bound);
+
+        result
+    }
+
+    /// Binds `values` one at a time, each to variable `bit(num_of_vars)` of
+    /// what is left.
+    fn fix_in_order(&self, values: &[F], bit: impl Fn(usize) -> usize) -> Self {
+        if values.len() > self.num_of_vars {
+            panic!(
+                "cannot fix {} variables of a polynomial of {}",
+                values.len(),
+                self.num_of_vars
+            );
+        }
+
+        let Some((first, rest)) = values.split_first() else {
+            return self.clone();
+        };
+
+        let mut result = self.bind(bit(self.num_of_vars), first);
+        for value in rest {
+            result.bind_in_place(bit(result.num_of_vars), value);
+        }
+
+        result
     }
 
     pub fn scale(&self, value: F) -> Self {
@@ -66,38 +770,384 @@ impl<F: PrimeField> MultilinearPoly<F> {
 
         Self::new(result)
     }
-}
 
-impl<F: PrimeField> Add for MultilinearPoly<F> {
-    type Output = Self;
+    /// The sum of the polynomial over the hypercube, which is the sum of its
+    /// table, split into chunks across threads under `parallel`.
+    pub fn hypercube_sum(&self) -> F {
+        #[cfg(feature = "parallel")]
+        let sum = self.evaluation.par_iter().with_min_len(1 << 10).sum();
+
+        #[cfg(not(feature = "parallel"))]
+        let sum = self.evaluation.iter().sum();
+
+        sum
+    }
+
+    /// `h(x, y) = self(x)·other(y)` over `self`'s variables followed by
+    /// `other`'s, so `h` at `p ++ q` is `self(p)·other(q)`. Its table is the
+    /// outer product: entry `i << other.num_of_vars | j` is
+    /// `self[i]·other[j]`. Fails rather than allocate a table of more than
+    /// `MAX_SUPPORTED_VARS` variables.
+    pub fn tensor(&self, other: &Self) -> Result<Self, MultilinearError> {
+        let num_of_vars = self.num_of_vars.saturating_add(other.num_of_vars);
+        table_len(num_of_vars)?;
 
-    fn add(self, other: Self) -> Self {
-        let result = self
+        let evaluation = self
             .evaluation
             .iter()
-            .zip(other.evaluation.iter())
-            .map(|(a, b)| *a + *b)
+            .flat_map(|a| other.evaluation.iter().map(move |b| *a * b))
             .collect();
 
-        MultilinearPoly::new(result)
+        Ok(Self {
+            evaluation,
+            num_of_vars,
+            zero: ZeroCache::default(),
+        })
+    }
+
+    /// `h` with a new first variable selecting between the two: `h(0, x) =
+    /// f(x)` and `h(1, x) = g(x)`. The first variable is the high bit of the
+    /// index, so `h`'s table is `f`'s followed by `g`'s.
+    pub fn concat(f: &Self, g: &Self) -> Result<Self, MultilinearError> {
+        f.check_num_vars(g)?;
+        table_len(f.num_of_vars + 1)?;
+
+        Ok(Self {
+            evaluation: [f.evaluation.as_slice(), g.evaluation.as_slice()].concat(),
+            num_of_vars: f.num_of_vars + 1,
+            zero: ZeroCache::default(),
+        })
+    }
+
+    /// The inverse of `concat`: the polynomial with its first variable bound
+    /// to 0, then to 1. Panics on a polynomial of no variables.
+    pub fn split(&self) -> (Self, Self) {
+        if let Err(error) = self.check_variable(0) {
+            panic!("{}", error);
+        }
+
+        let (zeros, ones) = self.evaluation.split_at(self.evaluation.len() / 2);
+        let half = |evaluation: &[F]| Self {
+            evaluation: evaluation.to_vec(),
+            num_of_vars: self.num_of_vars - 1,
+            zero: ZeroCache::default(),
+        };
+
+        (half(zeros), half(ones))
+    }
+
+    /// The zero polynomial over `num_vars` variables.
+    pub fn zero(num_vars: usize) -> Self {
+        Self {
+            evaluation: vec![F::zero(); checked_table_len(num_vars)],
+            num_of_vars: num_vars,
+            zero: ZeroCache::known_zero(),
+        }
+    }
+
+    /// A polynomial over `num_vars` variables with uniformly random
+    /// evaluations, for tests and benchmarks.
+    pub fn rand<R: RngCore + ?Sized>(num_vars: usize, rng: &mut R) -> Self {
+        Self {
+            evaluation: (0..checked_table_len(num_vars))
+                .map(|_| F::rand(rng))
+                .collect(),
+            num_of_vars: num_vars,
+            zero: ZeroCache::default(),
+        }
+    }
+
+    /// Whether every evaluation is zero. The table is scanned once and the
+    /// answer kept until it changes, so repeated checks are free.
+    pub fn is_zero(&self) -> bool {
+        *self
+            .zero
+            .0
+            .get_or_init(|| self.evaluation.iter().all(|eval| eval.is_zero()))
+    }
+
+    /// `self + other`, failing if they range over different variables.
+    pub fn try_add(&self, other: &Self) -> Result<Self, MultilinearError> {
+        self.zip_with(other, |a, b| a + b)
+    }
+
+    /// `self - other`, failing if they range over different variables.
+    pub fn try_sub(&self, other: &Self) -> Result<Self, MultilinearError> {
+        self.zip_with(other, |a, b| a - b)
+    }
+
+    /// Combines the two tables entry by entry.
+    fn zip_with(&self, other: &Self, op: impl Fn(F, F) -> F) -> Result<Self, MultilinearError> {
+        self.check_num_vars(other)?;
+
+        Ok(Self {
+            evaluation: self
+                .evaluation
+                .iter()
+                .zip(&other.evaluation)
+                .map(|(a, b)| op(*a, *b))
+                .collect(),
+            num_of_vars: self.num_of_vars,
+            zero: ZeroCache::default(),
+        })
+    }
+
+    fn check_num_vars(&self, other: &Self) -> Result<(), MultilinearError> {
+        if self.num_of_vars != other.num_of_vars {
+            return Err(MultilinearError::MismatchedNumVars {
+                left: self.num_of_vars,
+                right: other.num_of_vars,
+            });
+        }
+
+        Ok(())
+    }
+
+    /// Applies `op` to each of `self`'s entries and the matching one of
+    /// `other`'s, panicking if they range over different variables.
+    fn zip_in_place(&mut self, other: &Self, op: impl Fn(&mut F, F)) {
+        self.check_num_vars(other)
+            .unwrap_or_else(|error| panic!("{}", error));
+
+        self.evaluation
+            .iter_mut()
+            .zip(&other.evaluation)
+            .for_each(|(a, b)| op(a, *b));
+        self.zero.forget();
     }
 }
 
-impl<F: PrimeField> Mul for MultilinearPoly<F> {
-    type Output = Self;
+/// What needs a prime field: the digest, which hashes canonical integers, and
+/// folding a table over a small base field at challenges from an extension
+/// `E` of it.
+impl<F: PrimeField> MultilinearPoly<F> {
+    /// A Keccak-256 hash of the variable count and the evaluation table, used
+    /// to bind transcripts to the polynomial without absorbing the table.
+    pub fn digest(&self) -> [u8; 32] {
+        Self::digest_evaluations(self.num_of_vars, self.evaluation.iter().copied())
+    }
 
-    fn mul(self, other: Self) -> Self {
-        let result = self
-            .evaluation
-            .iter()
-            .zip(other.evaluation.iter())
-            .map(|(a, b)| *a * *b)
+    /// Computes `digest` for a table that is never materialized.
+    pub fn digest_evaluations(
+        num_of_vars: usize,
+        evaluations: impl Iterator<Item = F>,
+    ) -> [u8; 32] {
+        let mut hasher = Keccak256::new();
+        hasher.update((num_of_vars as u64).to_le_bytes());
+
+        for eval in evaluations {
+            hasher.update(eval.into_bigint().to_bytes_le());
+        }
+
+        hasher.finalize().into()
+    }
+
+    /// `partial_evaluate` at a `value` in an extension of `F`. The table is
+    /// lifted into `E` by this first fold, so the remaining variables fold
+    /// with the plain `E` methods. With `E = F` this is `partial_evaluate`.
+    pub fn partial_evaluate_ext<E: Field<BasePrimeField = F>>(
+        &self,
+        bit: usize,
+        value: &E,
+    ) -> Result<MultilinearPoly<E>, MultilinearError> {
+        self.check_variable(bit)?;
+
+        let inverted_index = self.num_of_vars - bit - 1;
+        let evaluation = (0..1 << (self.num_of_vars - 1))
+            .map(|val| {
+                let insert_zero = insert_bit(val, inverted_index);
+                let a = self.evaluation[insert_zero];
+                let b = self.evaluation[insert_zero | (1 << inverted_index)];
+
+                // Only the challenge is in `E`, so the difference stays in
+                // `F` and the product is by a base field element.
+                E::from_base_prime_field(a) + value.mul_by_base_prime_field(&(b - a))
+            })
             .collect();
 
-        MultilinearPoly::new(result)
+        Ok(MultilinearPoly {
+            evaluation,
+            num_of_vars: self.num_of_vars - 1,
+            zero: ZeroCache::default(),
+        })
+    }
+
+    /// `evaluate` at a point in an extension of `F`.
+    pub fn try_evaluate_ext<E: Field<BasePrimeField = F>>(
+        &self,
+        point: &[E],
+    ) -> Result<E, MultilinearError> {
+        if point.len() != self.num_of_vars {
+            return Err(MultilinearError::WrongNumberOfValues {
+                expected: self.num_of_vars,
+                got: point.len(),
+            });
+        }
+
+        let Some((first, rest)) = point.split_first() else {
+            return Ok(E::from_base_prime_field(self.evaluation[0]));
+        };
+
+        let mut lifted = self.partial_evaluate_ext(0, first)?;
+        Ok(lifted.fold_all_in_place(rest))
+    }
+
+    /// The restriction `t ↦ f(a + t·(b - a))` of the polynomial to the line
+    /// through `a` and `b`, of degree at most `num_vars()`: it is `f(a)` at
+    /// `t = 0` and `f(b)` at `t = 1`. Found by evaluating at
+    /// `t = 0, 1, ..., num_vars()` and interpolating.
+    pub fn restrict_to_line(
+        &self,
+        a: &[F],
+        b: &[F],
+    ) -> Result<UnivariatePoly<F>, MultilinearError> {
+        for point in [a, b] {
+            if point.len() != self.num_of_vars {
+                return Err(MultilinearError::WrongNumberOfValues {
+                    expected: self.num_of_vars,
+                    got: point.len(),
+                });
+            }
+        }
+
+        let direction: Vec<F> = a.iter().zip(b).map(|(a, b)| *b - a).collect();
+        let mut point = a.to_vec();
+        let mut buffer = Vec::with_capacity(self.num_of_vars + 1);
+
+        let mut samples = Vec::with_capacity(self.num_of_vars + 1);
+        for t in 0..=self.num_of_vars {
+            samples.push((F::from(t as u64), self.fold_streaming(&point, &mut buffer)));
+
+            point
+                .iter_mut()
+                .zip(&direction)
+                .for_each(|(x, step)| *x += step);
+        }
+
+        Ok(UnivariatePoly::interpolate(samples).expect("the samples are taken at distinct points"))
+    }
+}
+
+/// The polynomial with these evaluations, which must number a power of two.
+impl<F: Field> From<Vec<u64>> for MultilinearPoly<F> {
+    fn from(values: Vec<u64>) -> Self {
+        Self::new(values.into_iter().map(F::from).collect())
+    }
+}
+
+/// The operators panic when the polynomials range over different variables;
+/// `try_add` and `try_sub` report it instead.
+impl<F: Field> Add for MultilinearPoly<F> {
+    type Output = Self;
+
+    fn add(mut self, other: Self) -> Self {
+        self += other;
+
+        self
+    }
+}
+
+impl<F: Field> AddAssign for MultilinearPoly<F> {
+    fn add_assign(&mut self, other: Self) {
+        self.zip_in_place(&other, |a, b| *a += b);
+    }
+}
+
+impl<F: Field> Sub for MultilinearPoly<F> {
+    type Output = Self;
+
+    fn sub(mut self, other: Self) -> Self {
+        self -= other;
+
+        self
+    }
+}
+
+impl<F: Field> SubAssign for MultilinearPoly<F> {
+    fn sub_assign(&mut self, other: Self) {
+        self.zip_in_place(&other, |a, b| *a -= b);
+    }
+}
+
+impl<F: Field> Neg for MultilinearPoly<F> {
+    type Output = Self;
+
+    fn neg(mut self) -> Self {
+        self.evaluation.iter_mut().for_each(|eval| *eval = -*eval);
+
+        self
+    }
+}
+
+/// The entrywise product of the tables, whose extension agrees with the
+/// product of the polynomials only on the hypercube.
+impl<F: Field> Mul for MultilinearPoly<F> {
+    type Output = Self;
+
+    fn mul(mut self, other: Self) -> Self {
+        self.zip_in_place(&other, |a, b| *a *= b);
+
+        self
+    }
+}
+
+impl<F: Field> Mul<F> for MultilinearPoly<F> {
+    type Output = Self;
+
+    fn mul(mut self, value: F) -> Self {
+        self *= value;
+
+        self
     }
 }
 
+impl<F: Field> MulAssign<F> for MultilinearPoly<F> {
+    fn mul_assign(&mut self, value: F) {
+        self.evaluation.iter_mut().for_each(|eval| *eval *= value);
+        self.zero.forget_unless_zero();
+    }
+}
+
+/// `eq(a, b) = Π (aᵢbᵢ + (1 - aᵢ)(1 - bᵢ))`, for points of the same length.
+pub fn eq_eval<F: Field>(a: &[F], b: &[F]) -> F {
+    assert_eq!(a.len(), b.len(), "eq of points of different lengths");
+
+    a.iter()
+        .zip(b)
+        .map(|(x, y)| *x * y + (F::one() - x) * (F::one() - y))
+        .product()
+}
+
+/// `eq(point, index)` at the hypercube point whose bits spell `index`, the
+/// first variable most significant: one entry of `eq_mle(point)` without
+/// building the table.
+pub fn eq_at_index<F: Field>(point: &[F], index: usize) -> F {
+    point
+        .iter()
+        .rev()
+        .enumerate()
+        .map(|(bit, r)| match (index >> bit) & 1 {
+            1 => *r,
+            _ => F::one() - r,
+        })
+        .product()
+}
+
+/// The bits of `value` under `mask`, packed together in their order.
+fn gather_bits(value: usize, mut mask: usize) -> usize {
+    let mut gathered = 0;
+    let mut position = 0;
+
+    while mask != 0 {
+        let bit = mask.trailing_zeros();
+        gathered |= (value >> bit & 1) << position;
+        position += 1;
+        mask &= mask - 1;
+    }
+
+    gathered
+}
+
 fn insert_bit(value: usize, bit: usize) -> usize {
     let high = value >> bit;
     let mask = (1 << bit) - 1;
@@ -110,29 +1160,745 @@ fn insert_bit(value: usize, bit: usize) -> usize {
 mod test {
     use super::*;
     use ark_bn254::Fq;
+    use ark_ff::UniformRand;
+    use rand::{rngs::StdRng, seq::SliceRandom, SeedableRng};
 
     #[test]
     fn it_partially_evaluates_any_multilinear() {
-        let evaluations = vec![Fq::from(0), Fq::from(0), Fq::from(3), Fq::from(10)];
-        let polynomial = MultilinearPoly::new(evaluations);
+        let polynomial = MultilinearPoly::<Fq>::from(vec![0, 0, 3, 10]);
 
         let value_a = Fq::from(5);
         let bit_a = 0;
 
-        let result = polynomial.partial_evaluate(bit_a, &value_a);
+        let result = polynomial.partial_evaluate(bit_a, &value_a).unwrap();
 
-        assert_eq!(result.evaluation, vec![Fq::from(15), Fq::from(50)]);
+        assert_eq!(result, MultilinearPoly::from(vec![15, 50]));
     }
 
     #[test]
     fn it_fully_evaluates_any_multilinear() {
-        let evaluations = vec![Fq::from(0), Fq::from(0), Fq::from(3), Fq::from(10)];
-        let polynomial = MultilinearPoly::new(evaluations);
+        let polynomial = MultilinearPoly::<Fq>::from(vec![0, 0, 3, 10]);
 
         let values = vec![Fq::from(5), Fq::from(1)];
 
-        let result = polynomial.evaluate(values);
+        let result = polynomial.evaluate(&values);
 
         assert_eq!(result, Fq::from(50));
     }
+
+    #[test]
+    fn it_partially_evaluates_in_place() {
+        let evaluations: Vec<Fq> = (0..16u64).map(|i| Fq::from(i * i + 1)).collect();
+        let polynomial = MultilinearPoly::new(evaluations);
+        let value = Fq::from(7);
+
+        for bit in 0..polynomial.num_of_vars {
+            let mut in_place = polynomial.clone();
+            in_place.partial_evaluate_in_place(bit, &value).unwrap();
+
+            assert_eq!(in_place, polynomial.partial_evaluate(bit, &value).unwrap());
+        }
+    }
+
+    #[test]
+    fn binding_any_variable_agrees_with_evaluate() {
+        let mut rng = StdRng::seed_from_u64(57);
+
+        for num_of_vars in 1..=5 {
+            let polynomial =
+                MultilinearPoly::new((0..1 << num_of_vars).map(|_| Fq::rand(&mut rng)).collect());
+
+            for _ in 0..4 {
+                let point: Vec<Fq> = (0..num_of_vars).map(|_| Fq::rand(&mut rng)).collect();
+                let expected = polynomial.evaluate(&point);
+
+                for bit in 0..num_of_vars {
+                    let mut rest = point.clone();
+                    let value = rest.remove(bit);
+
+                    let bound = polynomial.partial_evaluate(bit, &value).unwrap();
+                    assert_eq!(bound.evaluate(&rest), expected);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn it_checks_the_length_of_the_point() {
+        let polynomial =
+            MultilinearPoly::new(vec![Fq::from(0), Fq::from(0), Fq::from(3), Fq::from(10)]);
+
+        assert_eq!(
+            polynomial.try_evaluate(&[Fq::from(5)]),
+            Err(MultilinearError::WrongNumberOfValues {
+                expected: 2,
+                got: 1
+            })
+        );
+        assert_eq!(
+            polynomial.try_evaluate(&[Fq::from(5), Fq::from(1), Fq::from(2)]),
+            Err(MultilinearError::WrongNumberOfValues {
+                expected: 2,
+                got: 3
+            })
+        );
+        assert_eq!(
+            polynomial.try_evaluate(&[Fq::from(5), Fq::from(1)]),
+            Ok(Fq::from(50))
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "a point of 1 values for a polynomial of 2 variables")]
+    fn evaluate_panics_on_a_short_point() {
+        MultilinearPoly::new(vec![Fq::from(1); 4]).evaluate(&[Fq::from(5)]);
+    }
+
+    #[test]
+    fn streaming_evaluation_agrees_with_evaluate() {
+        let mut rng = StdRng::seed_from_u64(72);
+
+        for num_vars in (0..=16).step_by(4) {
+            let polynomial =
+                MultilinearPoly::new((0..1 << num_vars).map(|_| Fq::rand(&mut rng)).collect());
+            let point: Vec<Fq> = (0..num_vars).map(|_| Fq::rand(&mut rng)).collect();
+
+            assert_eq!(
+                polynomial.evaluate_streaming(&point),
+                polynomial.evaluate(&point)
+            );
+        }
+    }
+
+    #[test]
+    fn tensor_evaluates_to_the_product_of_its_factors() {
+        let mut rng = StdRng::seed_from_u64(75);
+
+        for (left, right) in [(0, 0), (0, 3), (3, 0), (2, 3), (4, 1)] {
+            let f = MultilinearPoly::<Fq>::rand(left, &mut rng);
+            let g = MultilinearPoly::<Fq>::rand(right, &mut rng);
+            let h = f.tensor(&g).unwrap();
+            assert_eq!(h.num_of_vars, left + right);
+
+            let p: Vec<Fq> = (0..left).map(|_| Fq::rand(&mut rng)).collect();
+            let q: Vec<Fq> = (0..right).map(|_| Fq::rand(&mut rng)).collect();
+            assert_eq!(
+                h.evaluate(&[p.clone(), q.clone()].concat()),
+                f.evaluate(&p) * g.evaluate(&q)
+            );
+        }
+
+        // A single value just scales the other table.
+        let constant = MultilinearPoly::new(vec![Fq::from(3)]);
+        let g = MultilinearPoly::new(vec![Fq::from(1), Fq::from(5)]);
+        assert_eq!(constant.tensor(&g).unwrap(), g.clone() * Fq::from(3));
+        assert_eq!(g.tensor(&constant).unwrap(), g * Fq::from(3));
+    }
+
+    #[test]
+    fn tensor_refuses_an_oversized_table() {
+        let f = MultilinearPoly::<Fq>::zero(17);
+        let g = MultilinearPoly::<Fq>::zero(16);
+
+        assert_eq!(
+            f.tensor(&g),
+            Err(MultilinearError::TooManyVariables { num_of_vars: 33 })
+        );
+    }
+
+    #[test]
+    fn table_sizes_fail_past_the_limit() {
+        assert_eq!(table_len(0), Ok(1));
+        assert_eq!(table_len(20), Ok(1 << 20));
+        for num_of_vars in [MAX_SUPPORTED_VARS + 1, 64, usize::MAX] {
+            assert_eq!(
+                table_len(num_of_vars),
+                Err(MultilinearError::TooManyVariables { num_of_vars })
+            );
+        }
+
+        assert_eq!(
+            MultilinearPoly::<Fq>::from_entries(64, &[]),
+            Err(MultilinearError::TooManyVariables { num_of_vars: 64 })
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "over the limit")]
+    fn a_zero_table_past_the_limit_panics() {
+        let _ = MultilinearPoly::<Fq>::zero(65);
+    }
+
+    #[test]
+    fn it_reports_its_size_and_whether_it_is_constant() {
+        let polynomial = MultilinearPoly::new(vec![Fq::from(7); 8]);
+        assert_eq!(polynomial.num_vars(), 3);
+        assert_eq!(polynomial.len(), 8);
+        assert!(!polynomial.is_empty());
+        assert_eq!(polynomial.is_constant(), Some(Fq::from(7)));
+        assert_eq!(
+            MultilinearPoly::new(vec![Fq::from(2)]).is_constant(),
+            Some(Fq::from(2))
+        );
+
+        let polynomial =
+            MultilinearPoly::new(vec![Fq::from(7), Fq::from(7), Fq::from(1), Fq::from(7)]);
+        assert_eq!(polynomial.is_constant(), None);
+        assert_eq!(polynomial.evaluations()[2], Fq::from(1));
+    }
+
+    #[test]
+    fn it_builds_a_table_from_a_function_or_entries() {
+        // 2·x0·x1 + 3·x2, with x0 the most significant bit.
+        let table = MultilinearPoly::new(vec![
+            Fq::from(0),
+            Fq::from(3),
+            Fq::from(0),
+            Fq::from(3),
+            Fq::from(0),
+            Fq::from(3),
+            Fq::from(2),
+            Fq::from(5),
+        ]);
+        let point = [Fq::from(4), Fq::from(7), Fq::from(9)];
+
+        let from_fn = MultilinearPoly::from_fn(3, |index| {
+            Fq::from((2 * (index >> 2) * ((index >> 1) & 1) + 3 * (index & 1)) as u64)
+        });
+        assert_eq!(from_fn, table);
+        assert_eq!(from_fn.evaluate(&point), Fq::from(83));
+
+        let entries: Vec<_> = [(1, 3), (3, 3), (5, 3), (6, 2), (7, 5)]
+            .into_iter()
+            .map(|(index, value)| (index, Fq::from(value)))
+            .collect();
+        let from_entries = MultilinearPoly::from_entries(3, &entries).unwrap();
+        assert_eq!(from_entries, table);
+        assert_eq!(from_entries.evaluate(&point), Fq::from(83));
+
+        assert_eq!(
+            MultilinearPoly::from_entries(3, &[(8, Fq::from(1))]),
+            Err(MultilinearError::IndexOutOfRange {
+                index: 8,
+                num_of_vars: 3
+            })
+        );
+        assert_eq!(
+            MultilinearPoly::from_entries(3, &[(2, Fq::from(1)), (2, Fq::from(4))]),
+            Err(MultilinearError::DuplicateIndex { index: 2 })
+        );
+    }
+
+    #[test]
+    fn eq_mle_is_the_indicator_of_a_boolean_point() {
+        let point = [Fq::from(1), Fq::from(0), Fq::from(1)];
+        let eq = MultilinearPoly::eq_mle(&point);
+
+        for index in 0..8 {
+            let expected = if index == 0b101 { 1 } else { 0 };
+            assert_eq!(eq.evaluation[index], Fq::from(expected));
+        }
+    }
+
+    #[test]
+    fn eq_eval_matches_the_eq_table() {
+        let mut rng = StdRng::seed_from_u64(62);
+
+        for num_vars in 0..6 {
+            let r: Vec<Fq> = (0..num_vars).map(|_| Fq::rand(&mut rng)).collect();
+            let s: Vec<Fq> = (0..num_vars).map(|_| Fq::rand(&mut rng)).collect();
+
+            let eq = MultilinearPoly::eq_mle(&r);
+            assert_eq!(eq.evaluate(&s), eq_eval(&r, &s));
+            assert!((0..1 << num_vars).all(|index| eq.evaluation[index] == eq_at_index(&r, index)));
+            assert_eq!(eq.evaluation.iter().sum::<Fq>(), Fq::from(1));
+        }
+    }
+
+    #[test]
+    fn arithmetic_commutes_with_evaluation() {
+        let mut rng = StdRng::seed_from_u64(63);
+
+        for num_vars in 0..5 {
+            let random = |rng: &mut StdRng| {
+                MultilinearPoly::new((0..1 << num_vars).map(|_| Fq::rand(rng)).collect())
+            };
+            let f = random(&mut rng);
+            let g = random(&mut rng);
+            let c = Fq::rand(&mut rng);
+            let point: Vec<Fq> = (0..num_vars).map(|_| Fq::rand(&mut rng)).collect();
+
+            let (f_eval, g_eval) = (f.evaluate(&point), g.evaluate(&point));
+            assert_eq!((f.clone() + g.clone()).evaluate(&point), f_eval + g_eval);
+            assert_eq!((f.clone() - g.clone()).evaluate(&point), f_eval - g_eval);
+            assert_eq!((-f.clone()).evaluate(&point), -f_eval);
+            assert_eq!((f.clone() * c).evaluate(&point), c * f_eval);
+            assert_eq!(f.try_add(&g), Ok(f.clone() + g.clone()));
+
+            let mut h = f.clone();
+            h += g.clone();
+            h -= g.clone();
+            h *= c;
+            assert_eq!(h, f.scale(c));
+            assert!((f.clone() - f).is_zero());
+        }
+
+        assert!(MultilinearPoly::<Fq>::zero(3).is_zero());
+        assert_eq!(
+            MultilinearPoly::zero(2).try_sub(&MultilinearPoly::<Fq>::zero(1)),
+            Err(MultilinearError::MismatchedNumVars { left: 2, right: 1 })
+        );
+    }
+
+    #[test]
+    fn is_zero_follows_in_place_changes() {
+        let mut f = MultilinearPoly::new(vec![Fq::from(0); 8]);
+        assert!(f.is_zero());
+
+        f += MultilinearPoly::new([Fq::from(0), Fq::from(0), Fq::from(0), Fq::from(1)].repeat(2));
+        assert!(!f.is_zero());
+
+        // Binding the first variable to 1 keeps only the second half.
+        let mut g = MultilinearPoly::new([vec![Fq::from(0); 4], vec![Fq::from(7); 4]].concat());
+        assert!(!g.is_zero());
+        g.bind_in_place(0, &Fq::from(0));
+        assert!(g.is_zero());
+        g.bind_in_place(0, &Fq::from(3));
+        assert!(g.is_zero());
+
+        f *= Fq::from(0);
+        assert!(f.is_zero());
+    }
+
+    #[test]
+    #[should_panic(expected = "cannot combine polynomials of 2 and 1 variables")]
+    fn adding_mismatched_polynomials_panics() {
+        let f = MultilinearPoly::new(vec![Fq::from(1); 4]);
+        let g = MultilinearPoly::new(vec![Fq::from(1); 2]);
+
+        let _ = f + g;
+    }
+
+    #[test]
+    fn it_round_trips_through_coefficient_form() {
+        let mut rng = StdRng::seed_from_u64(64);
+
+        for _ in 0..5 {
+            let polynomial =
+                MultilinearPoly::new((0..16).map(|_| Fq::rand(&mut rng)).collect::<Vec<_>>());
+            let coefficients = polynomial.to_coefficients();
+            assert_eq!(
+                MultilinearPoly::from_coefficients(coefficients.clone()),
+                polynomial
+            );
+
+            let point: Vec<Fq> = (0..4).map(|_| Fq::rand(&mut rng)).collect();
+            let expanded: Fq = coefficients
+                .iter()
+                .enumerate()
+                .map(|(monomial, coefficient)| {
+                    (0..4)
+                        .filter(|i| monomial >> (3 - i) & 1 == 1)
+                        .map(|i| point[i])
+                        .product::<Fq>()
+                        * coefficient
+                })
+                .sum();
+            assert_eq!(expanded, polynomial.evaluate(&point));
+        }
+
+        // 2·x0·x1 + 3·x2
+        let coefficients = MultilinearPoly::new(vec![
+            Fq::from(0),
+            Fq::from(3),
+            Fq::from(0),
+            Fq::from(3),
+            Fq::from(0),
+            Fq::from(3),
+            Fq::from(2),
+            Fq::from(5),
+        ])
+        .to_coefficients();
+        let mut expected = vec![Fq::from(0); 8];
+        expected[0b001] = Fq::from(3);
+        expected[0b110] = Fq::from(2);
+        assert_eq!(coefficients, expected);
+    }
+
+    #[test]
+    fn it_reports_which_variables_appear() {
+        // 2·x0·x2 + 1, which does not depend on x1.
+        let polynomial = MultilinearPoly::from_fn(3, |index| {
+            Fq::from((2 * (index >> 2) * (index & 1) + 1) as u64)
+        });
+
+        let degrees: Vec<_> = (0..3)
+            .map(|bit| polynomial.degree_in_variable(bit).unwrap())
+            .collect();
+        assert_eq!(degrees, vec![1, 0, 1]);
+        assert_eq!(
+            polynomial.degree_in_variable(3),
+            Err(MultilinearError::VariableOutOfRange {
+                bit: 3,
+                num_of_vars: 3
+            })
+        );
+    }
+
+    #[test]
+    fn it_folds_in_place_like_the_allocating_version() {
+        let mut rng = StdRng::seed_from_u64(66);
+        let polynomial = MultilinearPoly::new((0..32).map(|_| Fq::rand(&mut rng)).collect());
+        let point: Vec<Fq> = (0..5).map(|_| Fq::rand(&mut rng)).collect();
+
+        let mut expected = polynomial.clone();
+        let mut folded = polynomial.clone();
+        for (round, value) in point.iter().enumerate() {
+            let bit = round % expected.num_of_vars;
+            expected = expected.partial_evaluate(bit, value).unwrap();
+            folded.partial_evaluate_in_place(bit, value).unwrap();
+
+            assert_eq!(folded, expected);
+        }
+
+        let mut consumed = polynomial.clone();
+        assert_eq!(
+            consumed.fold_all_in_place(&point),
+            polynomial.evaluate(&point)
+        );
+        assert_eq!(consumed.num_of_vars, 0);
+        assert_eq!(consumed.evaluation.len(), 1);
+    }
+
+    #[test]
+    fn permuting_variables_permutes_the_point() {
+        let mut rng = StdRng::seed_from_u64(67);
+        let polynomial = MultilinearPoly::new((0..32).map(|_| Fq::rand(&mut rng)).collect());
+
+        for _ in 0..10 {
+            let mut perm: Vec<usize> = (0..5).collect();
+            perm.shuffle(&mut rng);
+            let x: Vec<Fq> = (0..5).map(|_| Fq::rand(&mut rng)).collect();
+            let y: Vec<Fq> = perm.iter().map(|&old| x[old]).collect();
+
+            let permuted = polynomial.permute_variables(&perm).unwrap();
+            assert_eq!(permuted.evaluate(&y), polynomial.evaluate(&x));
+        }
+
+        // x0 alone, reversed, is the last variable: the odd entries.
+        let x0 = MultilinearPoly::from_fn(3, |index| Fq::from((index >> 2) as u64));
+        assert_eq!(
+            x0.reverse_variable_order(),
+            MultilinearPoly::from_fn(3, |index| Fq::from((index & 1) as u64))
+        );
+        assert_eq!(x0.reverse_variable_order().reverse_variable_order(), x0);
+
+        for perm in [vec![0, 1], vec![0, 0, 1], vec![0, 1, 3]] {
+            assert_eq!(
+                x0.permute_variables(&perm),
+                Err(MultilinearError::InvalidPermutation { num_of_vars: 3 })
+            );
+        }
+    }
+
+    #[test]
+    fn restricting_agrees_with_evaluating_and_with_stages() {
+        let mut rng = StdRng::seed_from_u64(69);
+        let polynomial = MultilinearPoly::new((0..64).map(|_| Fq::rand(&mut rng)).collect());
+
+        for _ in 0..5 {
+            let point: Vec<Fq> = (0..6).map(|_| Fq::rand(&mut rng)).collect();
+            let mut all: Vec<(usize, Fq)> = point.iter().copied().enumerate().collect();
+            all.shuffle(&mut rng);
+
+            let restricted = polynomial.restrict(&all).unwrap();
+            assert_eq!(restricted.num_of_vars, 0);
+            assert_eq!(restricted.evaluation[0], polynomial.evaluate(&point));
+
+            // Variables 1 and 4 first; 0, 2, 3 and 5 are then 0, 1, 2 and 3.
+            let first = polynomial
+                .restrict(&[(4, point[4]), (1, point[1])])
+                .unwrap();
+            let second = first.restrict(&[(1, point[2]), (3, point[5])]).unwrap();
+            let once = polynomial
+                .restrict(&[(1, point[1]), (2, point[2]), (4, point[4]), (5, point[5])])
+                .unwrap();
+            assert_eq!(second, once);
+            assert_eq!(
+                once.evaluate(&[point[0], point[3]]),
+                polynomial.evaluate(&point)
+            );
+        }
+
+        let prefix = [Fq::from(3), Fq::from(8)];
+        assert_eq!(
+            polynomial
+                .restrict(&[(0, prefix[0]), (1, prefix[1])])
+                .unwrap(),
+            polynomial.fix_prefix(&prefix)
+        );
+        assert_eq!(
+            polynomial.restrict(&[(6, Fq::from(1))]),
+            Err(MultilinearError::VariableOutOfRange {
+                bit: 6,
+                num_of_vars: 6
+            })
+        );
+        assert_eq!(
+            polynomial.restrict(&[(2, Fq::from(1)), (2, Fq::from(5))]),
+            Err(MultilinearError::RepeatedVariable { bit: 2 })
+        );
+    }
+
+    #[test]
+    fn it_evaluates_a_batch_like_repeated_evaluate() {
+        let mut rng = StdRng::seed_from_u64(65);
+        let polynomial = MultilinearPoly::new((0..64).map(|_| Fq::rand(&mut rng)).collect());
+        let points: Vec<Vec<Fq>> = (0..10)
+            .map(|_| (0..6).map(|_| Fq::rand(&mut rng)).collect())
+            .collect();
+
+        let expected: Vec<Fq> = points
+            .iter()
+            .map(|point| polynomial.fix_prefix(point).evaluation[0])
+            .collect();
+
+        assert_eq!(polynomial.evaluate_batch(&points), expected);
+        assert_eq!(
+            points
+                .iter()
+                .map(|point| polynomial.evaluate(point))
+                .collect::<Vec<_>>(),
+            expected
+        );
+        assert_eq!(polynomial.evaluate_batch(&[]), Vec::<Fq>::new());
+    }
+
+    #[test]
+    fn it_accepts_only_power_of_two_tables() {
+        for len in [0, 3, 6] {
+            assert_eq!(
+                MultilinearPoly::try_new(vec![Fq::from(1); len]),
+                Err(MultilinearError::LengthNotPowerOfTwo { len })
+            );
+        }
+
+        assert_eq!(
+            MultilinearPoly::try_new(vec![Fq::from(4)])
+                .unwrap()
+                .num_of_vars,
+            0
+        );
+        assert_eq!(
+            MultilinearPoly::try_new(vec![Fq::from(4); 8])
+                .unwrap()
+                .num_of_vars,
+            3
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "0 evaluations do not fill a hypercube")]
+    fn new_panics_on_an_empty_table() {
+        MultilinearPoly::<Fq>::new(Vec::new());
+    }
+
+    #[test]
+    fn it_rejects_binding_a_variable_out_of_range() {
+        let mut polynomial = MultilinearPoly::new(vec![Fq::from(1); 8]);
+        let error = MultilinearError::VariableOutOfRange {
+            bit: 3,
+            num_of_vars: 3,
+        };
+
+        assert_eq!(
+            polynomial.partial_evaluate(3, &Fq::from(2)),
+            Err(error.clone())
+        );
+        assert_eq!(
+            polynomial.partial_evaluate_in_place(3, &Fq::from(2)),
+            Err(error)
+        );
+        assert_eq!(polynomial.evaluation.len(), 8);
+    }
+
+    #[test]
+    fn it_encodes_a_matrix_row_major() {
+        let matrix = vec![
+            vec![Fq::from(1), Fq::from(2)],
+            vec![Fq::from(3), Fq::from(4)],
+        ];
+
+        let polynomial = MultilinearPoly::from_matrix(&matrix).unwrap();
+
+        assert_eq!(
+            polynomial.evaluate(&[Fq::from(1), Fq::from(0)]),
+            Fq::from(3)
+        );
+        assert_eq!(
+            polynomial.evaluate(&[Fq::from(0), Fq::from(1)]),
+            Fq::from(2)
+        );
+    }
+
+    #[test]
+    fn it_rejects_a_malformed_matrix() {
+        let ragged = vec![vec![Fq::from(1), Fq::from(2)], vec![Fq::from(3)]];
+        let three_columns = vec![vec![Fq::from(1); 3], vec![Fq::from(2); 3]];
+
+        assert_eq!(
+            MultilinearPoly::from_matrix(&ragged),
+            Err(MultilinearError::RaggedMatrix {
+                row: 1,
+                expected: 2,
+                got: 1,
+            })
+        );
+        assert_eq!(
+            MultilinearPoly::from_matrix(&three_columns),
+            Err(MultilinearError::LengthNotPowerOfTwo { len: 6 })
+        );
+        assert_eq!(
+            MultilinearPoly::<Fq>::from_matrix(&[]),
+            Err(MultilinearError::LengthNotPowerOfTwo { len: 0 })
+        );
+    }
+
+    #[test]
+    fn it_fixes_a_prefix_and_a_suffix() {
+        let mut rng = StdRng::seed_from_u64(73);
+        let polynomial = MultilinearPoly::new((0..64).map(|_| Fq::rand(&mut rng)).collect());
+
+        for split in 0..=6 {
+            let point: Vec<Fq> = (0..6).map(|_| Fq::rand(&mut rng)).collect();
+            let (rs, rest) = point.split_at(split);
+            let expected = polynomial.evaluate(&point);
+
+            let prefix_fixed = polynomial.fix_prefix(rs);
+            assert_eq!(prefix_fixed.num_of_vars, 6 - split);
+            assert_eq!(prefix_fixed.evaluate(rest), expected);
+
+            let suffix_fixed = polynomial.fix_suffix(rest);
+            assert_eq!(suffix_fixed.num_of_vars, split);
+            assert_eq!(suffix_fixed.evaluate(rs), expected);
+        }
+    }
+
+    #[test]
+    #[should_panic]
+    fn fixing_too_many_variables_panics() {
+        MultilinearPoly::new(vec![Fq::from(1), Fq::from(2)]).fix_prefix(&[Fq::from(1); 2]);
+    }
+
+    #[test]
+    fn concat_and_split_follow_the_binding_order() {
+        let mut rng = StdRng::seed_from_u64(80);
+
+        for num_vars in 0..5 {
+            let f = MultilinearPoly::<Fq>::rand(num_vars, &mut rng);
+            let g = MultilinearPoly::<Fq>::rand(num_vars, &mut rng);
+            let h = MultilinearPoly::concat(&f, &g).unwrap();
+
+            assert_eq!(h.num_vars(), num_vars + 1);
+            assert_eq!(h.partial_evaluate(0, &Fq::from(0)).unwrap(), f);
+            assert_eq!(h.partial_evaluate(0, &Fq::from(1)).unwrap(), g);
+            assert_eq!(h.split(), (f, g));
+        }
+
+        assert_eq!(
+            MultilinearPoly::concat(&MultilinearPoly::<Fq>::zero(2), &MultilinearPoly::zero(3)),
+            Err(MultilinearError::MismatchedNumVars { left: 2, right: 3 })
+        );
+    }
+
+    #[test]
+    #[should_panic]
+    fn splitting_a_constant_panics() {
+        MultilinearPoly::new(vec![Fq::from(1)]).split();
+    }
+
+    #[test]
+    // The zero cache is interior mutable, but hashing ignores it.
+    #[allow(clippy::mutable_key_type)]
+    fn equality_includes_the_number_of_variables() {
+        use std::collections::HashSet;
+
+        let unpadded = MultilinearPoly::new(vec![Fq::from(4)]);
+        let padded = MultilinearPoly::new(vec![Fq::from(4); 4]);
+
+        assert_ne!(unpadded, padded);
+        assert_eq!(unpadded.is_constant(), padded.is_constant());
+        assert_eq!(padded.evaluate(&[Fq::from(9), Fq::from(2)]), Fq::from(4));
+
+        let cached: HashSet<_> = [unpadded.clone(), padded.clone(), padded]
+            .into_iter()
+            .collect();
+        assert_eq!(cached.len(), 2);
+        assert!(cached.contains(&unpadded));
+    }
+
+    #[test]
+    fn it_builds_tables_from_integers() {
+        let polynomial = MultilinearPoly::<Fq>::from(vec![0, 0, 3, 10]);
+        assert_eq!(
+            polynomial,
+            MultilinearPoly::new(vec![Fq::from(0), Fq::from(0), Fq::from(3), Fq::from(10)])
+        );
+
+        assert_eq!(
+            MultilinearPoly::<Fq>::from_i64s(&[-1, 2]).evaluations(),
+            [-Fq::from(1), Fq::from(2)]
+        );
+
+        let padded = MultilinearPoly::<Fq>::mle_of(&[4, 5, 6]);
+        assert_eq!(padded, MultilinearPoly::from(vec![4, 5, 6, 0]));
+        assert_eq!(MultilinearPoly::<Fq>::mle_of(&[]), MultilinearPoly::zero(0));
+        assert_eq!(MultilinearPoly::<Fq>::mle_of(&[9]).num_vars(), 0);
+    }
+
+    #[test]
+    #[should_panic]
+    fn converting_a_table_of_the_wrong_length_panics() {
+        let _ = MultilinearPoly::<Fq>::from(vec![1, 2, 3]);
+    }
+
+    #[test]
+    fn it_restricts_to_a_line() {
+        let mut rng = StdRng::seed_from_u64(84);
+        let polynomial = MultilinearPoly::<Fq>::rand(4, &mut rng);
+        let a: Vec<Fq> = (0..4).map(|_| Fq::rand(&mut rng)).collect();
+        let b: Vec<Fq> = (0..4).map(|_| Fq::rand(&mut rng)).collect();
+
+        let mut line = polynomial.restrict_to_line(&a, &b).unwrap();
+        assert!(line.degree() <= 4);
+        assert_eq!(line.evaluate(Fq::from(0)), polynomial.evaluate(&a));
+        assert_eq!(line.evaluate(Fq::from(1)), polynomial.evaluate(&b));
+
+        let t = Fq::rand(&mut rng);
+        let point: Vec<Fq> = a.iter().zip(&b).map(|(a, b)| *a + t * (*b - a)).collect();
+        assert_eq!(line.evaluate(t), polynomial.evaluate(&point));
+
+        let constant = MultilinearPoly::new(vec![Fq::from(7)]);
+        assert_eq!(
+            constant.restrict_to_line(&[], &[]).unwrap(),
+            UnivariatePoly::new(vec![Fq::from(7)])
+        );
+
+        assert_eq!(
+            polynomial.restrict_to_line(&a[..3], &b),
+            Err(MultilinearError::WrongNumberOfValues {
+                expected: 4,
+                got: 3
+            })
+        );
+    }
+
+    #[test]
+    fn it_digests_the_evaluation_table() {
+        let polynomial = MultilinearPoly::<Fq>::from(vec![0, 0, 3, 10]);
+        let other_polynomial = MultilinearPoly::<Fq>::from(vec![0, 0, 3, 11]);
+
+        assert_eq!(polynomial.digest(), polynomial.clone().digest());
+        assert_ne!(polynomial.digest(), other_polynomial.digest());
+    }
 }
@@ -0,0 +1,601 @@
+use ark_ff::PrimeField;
+use ark_serialize::{
+    CanonicalDeserialize, CanonicalSerialize, Compress, Read, SerializationError, Valid, Validate,
+    Write,
+};
+
+use crate::{
+    composed_polynomial::{Factor, ProductPoly, SumPoly},
+    multilinear_polynomial_evaluation::{self, MultilinearPoly, ZeroCache},
+    sparse_multilinear_polynomial::SparseMultilinearPoly,
+};
+
+/// Written as `num_of_vars` as a `u64` followed by the evaluation table in
+/// the length-prefixed format of `Vec`.
+impl<F: PrimeField> CanonicalSerialize for MultilinearPoly<F> {
+    fn serialize_with_mode<W: Write>(
+        &self,
+        mut writer: W,
+        compress: Compress,
+    ) -> Result<(), SerializationError> {
+        (self.num_of_vars as u64).serialize_with_mode(&mut writer, compress)?;
+        self.evaluation.serialize_with_mode(&mut writer, compress)
+    }
+
+    fn serialized_size(&self, compress: Compress) -> usize {
+        (self.num_of_vars as u64).serialized_size(compress)
+            + self.evaluation.serialized_size(compress)
+    }
+}
+
+impl<F: PrimeField> Valid for MultilinearPoly<F> {
+    /// The table must fill the hypercube of `num_of_vars` variables.
+    fn check(&self) -> Result<(), SerializationError> {
+        match table_len(self.num_of_vars as u64) {
+            Some(len) if len == self.evaluation.len() => Ok(()),
+            _ => Err(SerializationError::InvalidData),
+        }
+    }
+}
+
+impl<F: PrimeField> CanonicalDeserialize for MultilinearPoly<F> {
+    fn deserialize_with_mode<R: Read>(
+        mut reader: R,
+        compress: Compress,
+        validate: Validate,
+    ) -> Result<Self, SerializationError> {
+        let num_of_vars = u64::deserialize_with_mode(&mut reader, compress, validate)?;
+        let len = u64::deserialize_with_mode(&mut reader, compress, validate)?;
+
+        // The length has to match before anything is read, so a corrupted
+        // prefix never drives a huge allocation.
+        if table_len(num_of_vars) != Some(len as usize) {
+            return Err(SerializationError::InvalidData);
+        }
+
+        let mut evaluation = Vec::new();
+        for _ in 0..len {
+            evaluation.push(F::deserialize_with_mode(&mut reader, compress, validate)?);
+        }
+
+        Ok(Self {
+            evaluation,
+            num_of_vars: num_of_vars as usize,
+            zero: ZeroCache::default(),
+        })
+    }
+}
+
+/// A tag byte, `0` for a dense factor and `1` for a sparse one. A dense
+/// factor is written as a `MultilinearPoly`; a sparse one as `num_vars` as a
+/// `u64` followed by its entries, each a `u64` index and a value.
+impl<F: PrimeField> CanonicalSerialize for Factor<F> {
+    fn serialize_with_mode<W: Write>(
+        &self,
+        mut writer: W,
+        compress: Compress,
+    ) -> Result<(), SerializationError> {
+        match self {
+            Factor::Dense(poly) => {
+                0u8.serialize_with_mode(&mut writer, compress)?;
+                poly.serialize_with_mode(&mut writer, compress)
+            }
+            Factor::Sparse(poly) => {
+                1u8.serialize_with_mode(&mut writer, compress)?;
+                (poly.num_vars as u64).serialize_with_mode(&mut writer, compress)?;
+                sparse_entries(poly).serialize_with_mode(&mut writer, compress)
+            }
+        }
+    }
+
+    fn serialized_size(&self, compress: Compress) -> usize {
+        1 + match self {
+            Factor::Dense(poly) => poly.serialized_size(compress),
+            Factor::Sparse(poly) => {
+                (poly.num_vars as u64).serialized_size(compress)
+                    + sparse_entries(poly).serialized_size(compress)
+            }
+        }
+    }
+}
+
+impl<F: PrimeField> Valid for Factor<F> {
+    fn check(&self) -> Result<(), SerializationError> {
+        match self {
+            Factor::Dense(poly) => poly.check(),
+            Factor::Sparse(_) => Ok(()),
+        }
+    }
+}
+
+impl<F: PrimeField> CanonicalDeserialize for Factor<F> {
+    fn deserialize_with_mode<R: Read>(
+        mut reader: R,
+        compress: Compress,
+        validate: Validate,
+    ) -> Result<Self, SerializationError> {
+        match u8::deserialize_with_mode(&mut reader, compress, validate)? {
+            0 => Ok(Factor::Dense(MultilinearPoly::deserialize_with_mode(
+                reader, compress, validate,
+            )?)),
+            1 => {
+                let num_vars = u64::deserialize_with_mode(&mut reader, compress, validate)?;
+                let len = table_len(num_vars).ok_or(SerializationError::InvalidData)?;
+                let count = u64::deserialize_with_mode(&mut reader, compress, validate)?;
+
+                // Sorted, distinct, in range and nonzero, as `new` would
+                // leave them, so decoding never changes the polynomial.
+                let mut entries: Vec<(usize, F)> = Vec::new();
+                for _ in 0..count {
+                    let index = u64::deserialize_with_mode(&mut reader, compress, validate)?;
+                    let value = F::deserialize_with_mode(&mut reader, compress, validate)?;
+                    let index =
+                        usize::try_from(index).map_err(|_| SerializationError::InvalidData)?;
+
+                    let after_last = entries.last().is_none_or(|(last, _)| *last < index);
+                    if index >= len || !after_last || value.is_zero() {
+                        return Err(SerializationError::InvalidData);
+                    }
+                    entries.push((index, value));
+                }
+
+                Ok(Factor::Sparse(SparseMultilinearPoly::new(
+                    num_vars as usize,
+                    entries,
+                )))
+            }
+            _ => Err(SerializationError::InvalidData),
+        }
+    }
+}
+
+fn sparse_entries<F: PrimeField>(poly: &SparseMultilinearPoly<F>) -> Vec<(u64, F)> {
+    poly.entries()
+        .iter()
+        .map(|(index, value)| (*index as u64, *value))
+        .collect()
+}
+
+/// The factors in the length-prefixed format of `Vec`.
+impl<F: PrimeField> CanonicalSerialize for ProductPoly<F> {
+    fn serialize_with_mode<W: Write>(
+        &self,
+        writer: W,
+        compress: Compress,
+    ) -> Result<(), SerializationError> {
+        self.evaluation.serialize_with_mode(writer, compress)
+    }
+
+    fn serialized_size(&self, compress: Compress) -> usize {
+        self.evaluation.serialized_size(compress)
+    }
+}
+
+impl<F: PrimeField> Valid for ProductPoly<F> {
+    fn check(&self) -> Result<(), SerializationError> {
+        Ok(())
+    }
+}
+
+impl<F: PrimeField> CanonicalDeserialize for ProductPoly<F> {
+    /// Rejects a term with no factors, or with factors over different
+    /// variables.
+    fn deserialize_with_mode<R: Read>(
+        reader: R,
+        compress: Compress,
+        validate: Validate,
+    ) -> Result<Self, SerializationError> {
+        let product = Self {
+            evaluation: deserialize_list(reader, compress, validate)?,
+        };
+        let mut sum_poly = check_shape(vec![product])?;
+
+        Ok(sum_poly.polys.remove(0))
+    }
+}
+
+/// The terms in the length-prefixed format of `Vec`.
+impl<F: PrimeField> CanonicalSerialize for SumPoly<F> {
+    fn serialize_with_mode<W: Write>(
+        &self,
+        writer: W,
+        compress: Compress,
+    ) -> Result<(), SerializationError> {
+        self.polys.serialize_with_mode(writer, compress)
+    }
+
+    fn serialized_size(&self, compress: Compress) -> usize {
+        self.polys.serialized_size(compress)
+    }
+}
+
+impl<F: PrimeField> Valid for SumPoly<F> {
+    fn check(&self) -> Result<(), SerializationError> {
+        Ok(())
+    }
+}
+
+impl<F: PrimeField> CanonicalDeserialize for SumPoly<F> {
+    /// Rejects a polynomial with no terms, an empty term, or factors over
+    /// different variables.
+    fn deserialize_with_mode<R: Read>(
+        reader: R,
+        compress: Compress,
+        validate: Validate,
+    ) -> Result<Self, SerializationError> {
+        check_shape(deserialize_list(reader, compress, validate)?)
+    }
+}
+
+/// A length-prefixed list read one element at a time, so a corrupted length
+/// runs out of input instead of driving a huge allocation.
+fn deserialize_list<T: CanonicalDeserialize, R: Read>(
+    mut reader: R,
+    compress: Compress,
+    validate: Validate,
+) -> Result<Vec<T>, SerializationError> {
+    let len = u64::deserialize_with_mode(&mut reader, compress, validate)?;
+
+    let mut list = Vec::new();
+    for _ in 0..len {
+        list.push(T::deserialize_with_mode(&mut reader, compress, validate)?);
+    }
+
+    Ok(list)
+}
+
+fn check_shape<F: PrimeField>(
+    polys: Vec<ProductPoly<F>>,
+) -> Result<SumPoly<F>, SerializationError> {
+    let sum_poly = SumPoly { polys };
+
+    match sum_poly.num_vars() {
+        Ok(_) => Ok(sum_poly),
+        Err(_) => Err(SerializationError::InvalidData),
+    }
+}
+
+/// `2^num_of_vars`, if the table is within `MAX_SUPPORTED_VARS`.
+fn table_len(num_of_vars: u64) -> Option<usize> {
+    multilinear_polynomial_evaluation::table_len(usize::try_from(num_of_vars).ok()?).ok()
+}
+
+/// Field elements are written as decimal strings, as elsewhere in the
+/// workspace's JSON.
+#[cfg(feature = "serde")]
+mod serde_impl {
+    use ark_ff::PrimeField;
+    use serde::{de::Error, Deserialize, Deserializer, Serialize, Serializer};
+
+    use super::table_len;
+    use crate::{
+        composed_polynomial::{Factor, ProductPoly, SumPoly},
+        multilinear_polynomial_evaluation::{MultilinearPoly, ZeroCache},
+        sparse_multilinear_polynomial::SparseMultilinearPoly,
+    };
+
+    #[derive(Serialize, Deserialize)]
+    #[serde(deny_unknown_fields)]
+    struct MultilinearPolyJson {
+        num_of_vars: usize,
+        evaluation: Vec<String>,
+    }
+
+    impl<F: PrimeField> Serialize for MultilinearPoly<F> {
+        fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+            MultilinearPolyJson {
+                num_of_vars: self.num_of_vars,
+                evaluation: self
+                    .evaluation
+                    .iter()
+                    .map(|eval| eval.to_string())
+                    .collect(),
+            }
+            .serialize(serializer)
+        }
+    }
+
+    impl<'de, F: PrimeField> Deserialize<'de> for MultilinearPoly<F> {
+        fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+            let json = MultilinearPolyJson::deserialize(deserializer)?;
+
+            if table_len(json.num_of_vars as u64) != Some(json.evaluation.len()) {
+                return Err(D::Error::custom(format!(
+                    "{} evaluations for a polynomial of {} variables",
+                    json.evaluation.len(),
+                    json.num_of_vars
+                )));
+            }
+
+            let evaluation = json
+                .evaluation
+                .iter()
+                .map(|eval| {
+                    F::from_str(eval)
+                        .map_err(|_| D::Error::custom(format!("invalid field element {}", eval)))
+                })
+                .collect::<Result<_, _>>()?;
+
+            Ok(Self {
+                evaluation,
+                num_of_vars: json.num_of_vars,
+                zero: ZeroCache::default(),
+            })
+        }
+    }
+
+    /// `{"terms": [[factor, ...], ...]}`, where a factor is `{"dense": ...}`
+    /// holding a `MultilinearPoly`, or `{"sparse": {"num_vars": ...,
+    /// "entries": [[index, "value"], ...]}}`.
+    #[derive(Serialize, Deserialize)]
+    #[serde(deny_unknown_fields)]
+    #[serde(bound(
+        serialize = "MultilinearPoly<F>: Serialize",
+        deserialize = "MultilinearPoly<F>: Deserialize<'de>"
+    ))]
+    struct SumPolyJson<F: PrimeField> {
+        terms: Vec<Vec<FactorJson<F>>>,
+    }
+
+    #[derive(Serialize, Deserialize)]
+    #[serde(rename_all = "snake_case", deny_unknown_fields)]
+    #[serde(bound(
+        serialize = "MultilinearPoly<F>: Serialize",
+        deserialize = "MultilinearPoly<F>: Deserialize<'de>"
+    ))]
+    enum FactorJson<F: PrimeField> {
+        Dense(MultilinearPoly<F>),
+        Sparse {
+            num_vars: usize,
+            entries: Vec<(usize, String)>,
+        },
+    }
+
+    impl<F: PrimeField> Serialize for SumPoly<F> {
+        fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+            let factor = |factor: &Factor<F>| match factor {
+                Factor::Dense(poly) => FactorJson::Dense(poly.clone()),
+                Factor::Sparse(poly) => FactorJson::Sparse {
+                    num_vars: poly.num_vars,
+                    entries: poly
+                        .entries()
+                        .iter()
+                        .map(|(index, value)| (*index, value.to_string()))
+                        .collect(),
+                },
+            };
+
+            SumPolyJson {
+                terms: self
+                    .polys
+                    .iter()
+                    .map(|product| product.evaluation.iter().map(factor).collect())
+                    .collect(),
+            }
+            .serialize(serializer)
+        }
+    }
+
+    impl<'de, F: PrimeField> Deserialize<'de> for SumPoly<F> {
+        /// Rejects what canonical deserialization rejects, except that sparse
+        /// entries may come in any order and are normalized.
+        fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+            let json = SumPolyJson::<F>::deserialize(deserializer)?;
+
+            let factor = |factor: FactorJson<F>| match factor {
+                FactorJson::Dense(poly) => Ok(Factor::Dense(poly)),
+                FactorJson::Sparse { num_vars, entries } => {
+                    let len = table_len(num_vars as u64).ok_or_else(|| {
+                        D::Error::custom(format!("{} variables is too many", num_vars))
+                    })?;
+
+                    let entries = entries
+                        .into_iter()
+                        .map(|(index, value)| match (index < len, F::from_str(&value)) {
+                            (true, Ok(value)) => Ok((index, value)),
+                            (false, _) => Err(D::Error::custom(format!(
+                                "index {} is outside a hypercube of {} variables",
+                                index, num_vars
+                            ))),
+                            (_, Err(_)) => {
+                                Err(D::Error::custom(format!("invalid field element {}", value)))
+                            }
+                        })
+                        .collect::<Result<_, _>>()?;
+
+                    Ok(Factor::Sparse(SparseMultilinearPoly::new(
+                        num_vars, entries,
+                    )))
+                }
+            };
+
+            let polys = json
+                .terms
+                .into_iter()
+                .map(|term| {
+                    Ok(ProductPoly {
+                        evaluation: term.into_iter().map(factor).collect::<Result<_, _>>()?,
+                    })
+                })
+                .collect::<Result<_, D::Error>>()?;
+
+            let sum_poly = SumPoly { polys };
+            sum_poly.num_vars().map_err(D::Error::custom)?;
+
+            Ok(sum_poly)
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use ark_bn254::Fq;
+    use ark_serialize::{CanonicalDeserialize, CanonicalSerialize, SerializationError};
+
+    use crate::{
+        composed_polynomial::{Factor, ProductPoly, SumPoly},
+        multilinear_polynomial_evaluation::MultilinearPoly,
+        sparse_multilinear_polynomial::SparseMultilinearPoly,
+    };
+
+    fn sample() -> MultilinearPoly<Fq> {
+        MultilinearPoly::new((1..=8u64).map(Fq::from).collect())
+    }
+
+    /// `sample()·S + sample()·sample()·sample()`, with `S` sparse.
+    fn sample_sum_poly() -> SumPoly<Fq> {
+        let sparse = SparseMultilinearPoly::new(3, vec![(1, Fq::from(4)), (6, Fq::from(9))]);
+
+        SumPoly::new(vec![
+            ProductPoly::from_factors(vec![Factor::Dense(sample()), Factor::Sparse(sparse)]),
+            ProductPoly::from_factors(vec![Factor::Dense(sample()); 3]),
+        ])
+    }
+
+    fn encode(sum_poly: &SumPoly<Fq>) -> Vec<u8> {
+        let mut bytes = Vec::new();
+        sum_poly.serialize_compressed(&mut bytes).unwrap();
+
+        bytes
+    }
+
+    #[test]
+    fn it_round_trips_canonically() {
+        let polynomial = sample();
+
+        let mut bytes = Vec::new();
+        polynomial.serialize_compressed(&mut bytes).unwrap();
+        assert_eq!(bytes.len(), polynomial.compressed_size());
+
+        let decoded = MultilinearPoly::<Fq>::deserialize_compressed(bytes.as_slice()).unwrap();
+        assert_eq!(decoded, polynomial);
+    }
+
+    #[test]
+    fn it_rejects_a_corrupted_length() {
+        let mut bytes = Vec::new();
+        sample().serialize_compressed(&mut bytes).unwrap();
+
+        // num_of_vars, then the table length.
+        for (offset, value) in [(0, 2u64), (0, u64::MAX), (8, 7), (8, u64::MAX)] {
+            let mut corrupted = bytes.clone();
+            corrupted[offset..offset + 8].copy_from_slice(&value.to_le_bytes());
+
+            assert!(matches!(
+                MultilinearPoly::<Fq>::deserialize_compressed(corrupted.as_slice()),
+                Err(SerializationError::InvalidData)
+            ));
+        }
+
+        assert!(MultilinearPoly::<Fq>::deserialize_compressed(&bytes[..bytes.len() - 1]).is_err());
+    }
+
+    #[test]
+    fn a_sum_poly_round_trips_canonically() {
+        let sum_poly = sample_sum_poly();
+
+        let bytes = encode(&sum_poly);
+        assert_eq!(bytes.len(), sum_poly.compressed_size());
+        assert_eq!(
+            SumPoly::<Fq>::deserialize_compressed(bytes.as_slice()).unwrap(),
+            sum_poly
+        );
+
+        let mut uncompressed = Vec::new();
+        sum_poly.serialize_uncompressed(&mut uncompressed).unwrap();
+        assert_eq!(
+            SumPoly::<Fq>::deserialize_uncompressed(uncompressed.as_slice()).unwrap(),
+            sum_poly
+        );
+    }
+
+    #[test]
+    fn a_malformed_sum_poly_is_rejected() {
+        let decode = |bytes: &[u8]| SumPoly::<Fq>::deserialize_compressed(bytes);
+        let bytes = encode(&sample_sum_poly());
+
+        assert!(decode(&bytes[..bytes.len() - 1]).is_err());
+
+        // The first factor's tag, then the sparse factor's first index.
+        let dense_len = 1 + sample().compressed_size();
+        for (offset, byte) in [(16, 7u8), (16 + dense_len + 1 + 8 + 8, 8)] {
+            let mut corrupted = bytes.clone();
+            corrupted[offset] = byte;
+            assert!(matches!(
+                decode(&corrupted),
+                Err(SerializationError::InvalidData)
+            ));
+        }
+
+        let malformed = [
+            SumPoly { polys: vec![] },
+            SumPoly {
+                polys: vec![ProductPoly { evaluation: vec![] }],
+            },
+            SumPoly {
+                polys: vec![ProductPoly {
+                    evaluation: vec![
+                        Factor::Dense(sample()),
+                        Factor::Dense(MultilinearPoly::zero(2)),
+                    ],
+                }],
+            },
+        ];
+        for sum_poly in malformed {
+            assert!(matches!(
+                decode(&encode(&sum_poly)),
+                Err(SerializationError::InvalidData)
+            ));
+        }
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn a_sum_poly_round_trips_through_json() {
+        let sum_poly = sample_sum_poly();
+
+        let json = serde_json::to_string(&sum_poly).unwrap();
+        assert!(json.contains(r#"{"sparse":{"num_vars":3,"entries":[[1,"4"],[6,"9"]]}}"#));
+        assert_eq!(
+            serde_json::from_str::<SumPoly<Fq>>(&json).unwrap(),
+            sum_poly
+        );
+
+        let mismatched = r#"{"terms":[[
+            {"dense":{"num_of_vars":1,"evaluation":["1","2"]}},
+            {"sparse":{"num_vars":2,"entries":[]}}
+        ]]}"#;
+        assert!(serde_json::from_str::<SumPoly<Fq>>(mismatched).is_err());
+
+        let out_of_range = r#"{"terms":[[{"sparse":{"num_vars":1,"entries":[[2,"1"]]}}]]}"#;
+        assert!(serde_json::from_str::<SumPoly<Fq>>(out_of_range).is_err());
+
+        assert!(serde_json::from_str::<SumPoly<Fq>>(r#"{"terms":[]}"#).is_err());
+        assert!(serde_json::from_str::<SumPoly<Fq>>(r#"{"terms":[[]]}"#).is_err());
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn it_round_trips_through_json() {
+        let polynomial = sample();
+
+        let json = serde_json::to_string(&polynomial).unwrap();
+        assert_eq!(
+            json,
+            r#"{"num_of_vars":3,"evaluation":["1","2","3","4","5","6","7","8"]}"#
+        );
+        assert_eq!(
+            serde_json::from_str::<MultilinearPoly<Fq>>(&json).unwrap(),
+            polynomial
+        );
+
+        let short = r#"{"num_of_vars":3,"evaluation":["1","2","3"]}"#;
+        assert!(serde_json::from_str::<MultilinearPoly<Fq>>(short).is_err());
+
+        let huge = r#"{"num_of_vars":200,"evaluation":[]}"#;
+        assert!(serde_json::from_str::<MultilinearPoly<Fq>>(huge).is_err());
+
+        let garbage = r#"{"num_of_vars":0,"evaluation":["x"]}"#;
+        assert!(serde_json::from_str::<MultilinearPoly<Fq>>(garbage).is_err());
+    }
+}
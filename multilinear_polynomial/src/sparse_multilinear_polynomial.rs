@@ -0,0 +1,272 @@
+use ark_ff::PrimeField;
+
+use crate::{
+    composed_polynomial::Factor,
+    multilinear_polynomial_evaluation::{
+        checked_table_len, eq_at_index, table_len, MultilinearError, MultilinearPoly,
+    },
+};
+
+/// Once at least this fraction of a table is nonzero, binding a variable
+/// returns a dense table instead.
+const DENSE_FRACTION: usize = 4;
+
+/// A multilinear polynomial given by its nonzero evaluations over the
+/// hypercube, indexed like `MultilinearPoly::evaluation`.
+///
+/// The entries are kept sorted by index, with no repeated indices and no
+/// zero values.
+#[derive(Clone, Debug, PartialEq)]
+pub struct SparseMultilinearPoly<F: PrimeField> {
+    entries: Vec<(usize, F)>,
+    pub num_vars: usize,
+}
+
+impl<F: PrimeField> SparseMultilinearPoly<F> {
+    /// The polynomial over `num_vars` variables that is `value` at `index`
+    /// for every given pair and zero elsewhere. Values at a repeated index
+    /// are added up. Panics if an index does not fit in `num_vars` bits.
+    pub fn new(num_vars: usize, entries: Vec<(usize, F)>) -> Self {
+        let out_of_range = |index: usize| num_vars < usize::BITS as usize && index >> num_vars != 0;
+        if let Some((index, _)) = entries.iter().find(|(index, _)| out_of_range(*index)) {
+            panic!(
+                "index {} is outside a hypercube of {} variables",
+                index, num_vars
+            );
+        }
+
+        Self {
+            entries: normalize(entries),
+            num_vars,
+        }
+    }
+
+    /// The nonzero evaluations, sorted by index.
+    pub fn entries(&self) -> &[(usize, F)] {
+        &self.entries
+    }
+
+    /// The value at hypercube index `index`.
+    pub fn get(&self, index: usize) -> F {
+        self.entries
+            .binary_search_by_key(&index, |(index, _)| *index)
+            .map_or(F::zero(), |position| self.entries[position].1)
+    }
+
+    /// The polynomial's value at `point`, in time linear in the number of
+    /// nonzero entries.
+    pub fn try_evaluate(&self, point: &[F]) -> Result<F, MultilinearError> {
+        if point.len() != self.num_vars {
+            return Err(MultilinearError::WrongNumberOfValues {
+                expected: self.num_vars,
+                got: point.len(),
+            });
+        }
+
+        Ok(self
+            .entries
+            .iter()
+            .map(|(index, value)| *value * eq_at_index(point, *index))
+            .sum())
+    }
+
+    /// `try_evaluate` for a point the caller knows has the right length.
+    /// Panics otherwise.
+    pub fn evaluate(&self, point: &[F]) -> F {
+        self.try_evaluate(point)
+            .unwrap_or_else(|error| panic!("{}", error))
+    }
+
+    /// Binds variable `bit` to `value`. The result stays sparse while fewer
+    /// than a quarter of its entries are nonzero, and is dense otherwise.
+    pub fn partial_evaluate(&self, bit: usize, value: &F) -> Result<Factor<F>, MultilinearError> {
+        if bit >= self.num_vars {
+            return Err(MultilinearError::VariableOutOfRange {
+                bit,
+                num_of_vars: self.num_vars,
+            });
+        }
+
+        Ok(self.bind(bit, value))
+    }
+
+    /// `partial_evaluate` for a `bit` the caller knows is in range.
+    pub(crate) fn bind(&self, bit: usize, value: &F) -> Factor<F> {
+        let inverted_index = self.num_vars - bit - 1;
+        let low = (1 << inverted_index) - 1;
+
+        let entries = self
+            .entries
+            .iter()
+            .map(|(index, eval)| {
+                let folded = (index >> 1) & !low | index & low;
+
+                match (index >> inverted_index) & 1 {
+                    1 => (folded, *eval * value),
+                    _ => (folded, *eval * (F::one() - value)),
+                }
+            })
+            .collect();
+
+        let bound = Self {
+            entries: normalize(entries),
+            num_vars: self.num_vars - 1,
+        };
+
+        let dense = table_len(bound.num_vars)
+            .is_ok_and(|len| bound.entries.len().saturating_mul(DENSE_FRACTION) >= len);
+        if dense {
+            Factor::Dense(bound.to_dense())
+        } else {
+            Factor::Sparse(bound)
+        }
+    }
+
+    /// `MultilinearPoly::depends_on`, found from the entries alone.
+    pub(crate) fn depends_on(&self, bit: usize) -> bool {
+        let stride = 1 << (self.num_vars - bit - 1);
+
+        self.entries
+            .iter()
+            .any(|(index, value)| self.get(index ^ stride) != *value)
+    }
+
+    /// Panics past `MAX_SUPPORTED_VARS` variables.
+    pub fn to_dense(&self) -> MultilinearPoly<F> {
+        let mut evaluations = vec![F::zero(); checked_table_len(self.num_vars)];
+        for (index, value) in &self.entries {
+            evaluations[*index] = *value;
+        }
+
+        MultilinearPoly::new(evaluations)
+    }
+
+    pub fn from_dense(polynomial: &MultilinearPoly<F>) -> Self {
+        let entries = polynomial
+            .evaluation
+            .iter()
+            .enumerate()
+            .filter(|(_, value)| !value.is_zero())
+            .map(|(index, value)| (index, *value))
+            .collect();
+
+        Self {
+            entries,
+            num_vars: polynomial.num_of_vars,
+        }
+    }
+}
+
+impl<F: PrimeField> From<&MultilinearPoly<F>> for SparseMultilinearPoly<F> {
+    fn from(polynomial: &MultilinearPoly<F>) -> Self {
+        Self::from_dense(polynomial)
+    }
+}
+
+impl<F: PrimeField> From<&SparseMultilinearPoly<F>> for MultilinearPoly<F> {
+    fn from(polynomial: &SparseMultilinearPoly<F>) -> Self {
+        polynomial.to_dense()
+    }
+}
+
+/// Sorts the entries by index, adding up repeated indices and dropping the
+/// ones that come to zero.
+fn normalize<F: PrimeField>(mut entries: Vec<(usize, F)>) -> Vec<(usize, F)> {
+    entries.sort_unstable_by_key(|(index, _)| *index);
+
+    let mut merged: Vec<(usize, F)> = Vec::with_capacity(entries.len());
+    for (index, value) in entries {
+        match merged.last_mut() {
+            Some((last, sum)) if *last == index => *sum += value,
+            _ => merged.push((index, value)),
+        }
+    }
+
+    merged.retain(|(_, value)| !value.is_zero());
+
+    merged
+}
+
+#[cfg(test)]
+mod test {
+    use ark_bn254::Fq;
+    use ark_ff::UniformRand;
+    use rand::{rngs::StdRng, Rng, SeedableRng};
+
+    use super::SparseMultilinearPoly;
+    use crate::{composed_polynomial::Factor, multilinear_polynomial_evaluation::MultilinearPoly};
+
+    #[test]
+    fn it_evaluates_and_binds_like_the_dense_table() {
+        let mut rng = StdRng::seed_from_u64(60);
+        let entries = (0..40)
+            .map(|_| (rng.gen_range(0..1 << 10), Fq::rand(&mut rng)))
+            .collect();
+        let sparse = SparseMultilinearPoly::new(10, entries);
+        let dense = sparse.to_dense();
+
+        assert_eq!(SparseMultilinearPoly::from_dense(&dense), sparse);
+
+        for _ in 0..5 {
+            let point: Vec<Fq> = (0..10).map(|_| Fq::rand(&mut rng)).collect();
+            assert_eq!(sparse.evaluate(&point), dense.evaluate(&point));
+        }
+
+        let value = Fq::rand(&mut rng);
+        for bit in 0..10 {
+            let bound = sparse.partial_evaluate(bit, &value).unwrap();
+            let expected = dense.partial_evaluate(bit, &value).unwrap();
+
+            assert!(matches!(bound, Factor::Sparse(_)));
+            assert_eq!(bound.to_dense(), expected);
+        }
+    }
+
+    #[test]
+    fn it_turns_dense_once_the_table_fills_up() {
+        let sparse = SparseMultilinearPoly::new(
+            3,
+            vec![(0, Fq::from(1)), (5, Fq::from(2)), (5, Fq::from(3))],
+        );
+        assert_eq!(sparse.entries(), [(0, Fq::from(1)), (5, Fq::from(5))]);
+        assert_eq!(sparse.get(5), Fq::from(5));
+        assert_eq!(sparse.get(4), Fq::from(0));
+
+        // Two nonzero entries out of four.
+        let bound = sparse.partial_evaluate(0, &Fq::from(3)).unwrap();
+        assert_eq!(
+            bound,
+            Factor::Dense(MultilinearPoly::new(vec![
+                Fq::from(-2),
+                Fq::from(15),
+                Fq::from(0),
+                Fq::from(0)
+            ]))
+        );
+    }
+
+    #[test]
+    fn it_handles_a_wiring_shaped_predicate_over_twenty_variables() {
+        // add(a, 2a, 2a + 1) for 64 gates reading 128 wires: 6 + 7 + 7
+        // variables, of which 64 out of 2^20 entries are nonzero.
+        let entries = (0..64usize)
+            .map(|a| ((a << 14) | ((2 * a) << 7) | (2 * a + 1), Fq::from(1)))
+            .collect();
+        let wiring = SparseMultilinearPoly::new(20, entries);
+
+        let mut rng = StdRng::seed_from_u64(20);
+        let point: Vec<Fq> = (0..20).map(|_| Fq::rand(&mut rng)).collect();
+        let expected = wiring.evaluate(&point);
+
+        let mut bound = Factor::Sparse(wiring);
+        for (round, value) in point.iter().enumerate() {
+            bound = bound.bind(0, value);
+
+            if round < 6 {
+                assert!(matches!(bound, Factor::Sparse(_)));
+            }
+        }
+
+        assert_eq!(bound.evaluate(&[]), expected);
+    }
+}
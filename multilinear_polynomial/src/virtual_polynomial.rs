@@ -0,0 +1,409 @@
+use std::sync::Arc;
+
+use ark_ff::PrimeField;
+
+use crate::{
+    composed_polynomial::{ComposedPolynomial, FactorShape, ShapeError, SumPoly, SumPolyShape},
+    multilinear_polynomial_evaluation::MultilinearPoly,
+};
+
+#[cfg(test)]
+thread_local! {
+    /// How many tables this thread has bound, so tests can see each shared
+    /// table folded once per round.
+    static FOLDS: std::cell::Cell<usize> = const { std::cell::Cell::new(0) };
+}
+
+/// `Σ coefficient·Π multilinears[index]`, where every distinct multilinear is
+/// stored once and terms refer to it by index. A table shared by several
+/// terms, or used twice in one, is then bound once per round instead of once
+/// per use, and held in memory once.
+#[derive(Clone, Debug, PartialEq)]
+pub struct VirtualPolynomial<F: PrimeField> {
+    num_vars: usize,
+    multilinears: Vec<Arc<MultilinearPoly<F>>>,
+    products: Vec<(F, Vec<usize>)>,
+}
+
+impl<F: PrimeField> VirtualPolynomial<F> {
+    /// The zero polynomial over `num_vars` variables, with no terms.
+    pub fn new(num_vars: usize) -> Self {
+        Self {
+            num_vars,
+            multilinears: Vec::new(),
+            products: Vec::new(),
+        }
+    }
+
+    /// Adds the term `coefficient·Π factors`. A factor that is the same `Arc`
+    /// as one already stored is shared rather than stored again. Fails if
+    /// there are no factors or any ranges over different variables.
+    pub fn add_product(
+        mut self,
+        coefficient: F,
+        factors: Vec<Arc<MultilinearPoly<F>>>,
+    ) -> Result<Self, ShapeError> {
+        let term = self.products.len();
+
+        if factors.is_empty() {
+            return Err(ShapeError::EmptyProduct { term });
+        }
+
+        if let Some((factor, poly)) = factors
+            .iter()
+            .enumerate()
+            .find(|(_, poly)| poly.num_vars() != self.num_vars)
+        {
+            return Err(ShapeError::MismatchedNumVars {
+                term,
+                factor,
+                expected: self.num_vars,
+                got: poly.num_vars(),
+            });
+        }
+
+        let indices = factors
+            .into_iter()
+            .map(|poly| self.index_of(poly, Arc::ptr_eq))
+            .collect();
+        self.products.push((coefficient, indices));
+
+        Ok(self)
+    }
+
+    pub fn num_vars(&self) -> usize {
+        self.num_vars
+    }
+
+    /// The distinct multilinears the terms refer to.
+    pub fn multilinears(&self) -> &[Arc<MultilinearPoly<F>>] {
+        &self.multilinears
+    }
+
+    /// Each term as its coefficient and the indices of its factors in
+    /// `multilinears`.
+    pub fn products(&self) -> &[(F, Vec<usize>)] {
+        &self.products
+    }
+
+    /// The largest number of factors in any term, zero if there are none.
+    pub fn degree_in_bound_variable(&self) -> usize {
+        self.products
+            .iter()
+            .map(|(_, indices)| indices.len())
+            .max()
+            .unwrap_or(0)
+    }
+
+    /// The most factors of one term whose tables change along variable
+    /// `index`, leaving out terms with a zero factor as `SumPoly` does.
+    /// Panics if `index` is not one of the variables.
+    pub fn degree_in_variable(&self, index: usize) -> usize {
+        if index >= self.num_vars {
+            panic!(
+                "{}",
+                ShapeError::VariableOutOfRange {
+                    index,
+                    num_vars: self.num_vars
+                }
+            );
+        }
+
+        let depends: Vec<bool> = self
+            .multilinears
+            .iter()
+            .map(|poly| poly.depends_on(index))
+            .collect();
+
+        self.products
+            .iter()
+            .filter(|(_, indices)| {
+                !indices
+                    .iter()
+                    .any(|index| self.multilinears[*index].is_zero())
+            })
+            .map(|(_, indices)| indices.iter().filter(|index| depends[**index]).count())
+            .max()
+            .unwrap_or(0)
+    }
+
+    /// Evaluates every distinct multilinear once. Fails if `point` does not
+    /// have one value per variable.
+    pub fn try_evaluate(&self, point: &[F]) -> Result<F, ShapeError> {
+        if point.len() != self.num_vars {
+            return Err(ShapeError::WrongPointLength {
+                expected: self.num_vars,
+                got: point.len(),
+            });
+        }
+
+        let evaluations: Vec<F> = self
+            .multilinears
+            .iter()
+            .map(|poly| poly.evaluate(point))
+            .collect();
+
+        Ok(self.combine(|index| evaluations[index]))
+    }
+
+    /// `try_evaluate` for a point of the right length. Panics otherwise.
+    pub fn evaluate(&self, point: &[F]) -> F {
+        self.try_evaluate(point)
+            .unwrap_or_else(|error| panic!("{}", error))
+    }
+
+    /// The index of `poly` in `multilinears`, storing it if no entry `same`
+    /// as it is there yet.
+    fn index_of(
+        &mut self,
+        poly: Arc<MultilinearPoly<F>>,
+        same: impl Fn(&Arc<MultilinearPoly<F>>, &Arc<MultilinearPoly<F>>) -> bool,
+    ) -> usize {
+        match self
+            .multilinears
+            .iter()
+            .position(|stored| same(stored, &poly))
+        {
+            Some(index) => index,
+            None => {
+                self.multilinears.push(poly);
+                self.multilinears.len() - 1
+            }
+        }
+    }
+
+    /// `Σ coefficient·Π value(index)` over the terms.
+    fn combine(&self, value: impl Fn(usize) -> F) -> F {
+        self.products
+            .iter()
+            .map(|(coefficient, indices)| {
+                indices
+                    .iter()
+                    .fold(*coefficient, |product, index| product * value(*index))
+            })
+            .sum()
+    }
+}
+
+/// Shares factors whose tables are equal, which is how a `SumPoly` repeats
+/// one multilinear. Sparse factors become dense.
+impl<F: PrimeField> TryFrom<&SumPoly<F>> for VirtualPolynomial<F> {
+    type Error = ShapeError;
+
+    fn try_from(sum_poly: &SumPoly<F>) -> Result<Self, ShapeError> {
+        let mut virtual_poly = Self::new(sum_poly.num_vars()?);
+
+        for product in &sum_poly.polys {
+            let indices = product
+                .evaluation
+                .iter()
+                .map(|factor| virtual_poly.index_of(Arc::new(factor.to_dense()), |a, b| a == b))
+                .collect();
+            virtual_poly.products.push((F::one(), indices));
+        }
+
+        Ok(virtual_poly)
+    }
+}
+
+impl<F: PrimeField> ComposedPolynomial<F> for VirtualPolynomial<F> {
+    fn num_vars(&self) -> Result<usize, ShapeError> {
+        match self.products.is_empty() {
+            true => Err(ShapeError::EmptySumPoly),
+            false => Ok(self.num_vars),
+        }
+    }
+
+    fn degree_in_bound_variable(&self) -> usize {
+        VirtualPolynomial::degree_in_bound_variable(self)
+    }
+
+    fn degree_in_variable(&self, index: usize) -> usize {
+        VirtualPolynomial::degree_in_variable(self, index)
+    }
+
+    /// Every factor is dense, shared or not.
+    fn shape(&self) -> SumPolyShape {
+        SumPolyShape {
+            terms: self
+                .products
+                .iter()
+                .map(|(_, indices)| {
+                    indices
+                        .iter()
+                        .map(|index| FactorShape {
+                            num_vars: self.multilinears[*index].num_vars(),
+                            sparse: false,
+                            zero: self.multilinears[*index].is_zero(),
+                        })
+                        .collect()
+                })
+                .collect(),
+        }
+    }
+
+    fn hypercube_sum(&self) -> F {
+        (0..self.multilinears.first().map_or(0, |poly| poly.len()))
+            .map(|j| self.combine(|index| self.multilinears[index].evaluations()[j]))
+            .sum()
+    }
+
+    /// Each distinct multilinear's line along the bound variable is walked
+    /// once into `scratch`, then the terms multiply the stored values.
+    fn add_round_values(&self, j: usize, sums: &mut [F], scratch: &mut Vec<F>) {
+        let half = 1 << (self.num_vars - 1);
+        let points = sums.len();
+        scratch.resize(self.multilinears.len() * points, F::zero());
+
+        for (poly, values) in self.multilinears.iter().zip(scratch.chunks_mut(points)) {
+            let a = poly.evaluations()[j];
+            let step = poly.evaluations()[j + half] - a;
+            let mut value = a;
+
+            for slot in values.iter_mut() {
+                *slot = value;
+                value += step;
+            }
+        }
+
+        for (point, sum) in sums.iter_mut().enumerate() {
+            *sum += self.combine(|index| scratch[index * points + point]);
+        }
+    }
+
+    fn partial_evaluate(&self, value: &F) -> Self {
+        let multilinears = self
+            .multilinears
+            .iter()
+            .map(|poly| {
+                count_fold();
+                Arc::new(poly.bind(0, value))
+            })
+            .collect();
+
+        Self {
+            num_vars: self.num_vars - 1,
+            multilinears,
+            products: self.products.clone(),
+        }
+    }
+
+    /// Folds a table in place when nothing else holds it, and copies it
+    /// first otherwise.
+    fn fold_in_place(&mut self, value: &F) {
+        for poly in self.multilinears.iter_mut() {
+            count_fold();
+            Arc::make_mut(poly).bind_in_place(0, value);
+        }
+        self.num_vars -= 1;
+    }
+}
+
+fn count_fold() {
+    #[cfg(test)]
+    FOLDS.with(|folds| folds.set(folds.get() + 1));
+}
+
+#[cfg(test)]
+mod test {
+    use std::sync::Arc;
+
+    use ark_bn254::Fq;
+    use ark_ff::UniformRand;
+    use rand::{rngs::StdRng, SeedableRng};
+
+    use super::{VirtualPolynomial, FOLDS};
+    use crate::{
+        composed_polynomial::{ComposedPolynomial, ProductPoly, ShapeError, SumPoly},
+        multilinear_polynomial_evaluation::MultilinearPoly,
+    };
+
+    /// `a·W + b·W + c·W·W`, with `W` in four factor slots.
+    fn layer(rng: &mut StdRng) -> (SumPoly<Fq>, MultilinearPoly<Fq>) {
+        let [a, b, c, w] = [(); 4].map(|_| MultilinearPoly::<Fq>::rand(4, rng).into_evaluations());
+        let sum_poly = SumPoly::new(vec![
+            ProductPoly::new(vec![a, w.clone()]),
+            ProductPoly::new(vec![b, w.clone()]),
+            ProductPoly::new(vec![c, w.clone(), w.clone()]),
+        ]);
+
+        (sum_poly, MultilinearPoly::new(w))
+    }
+
+    #[test]
+    fn it_shares_repeated_factors() {
+        let mut rng = StdRng::seed_from_u64(90);
+        let (sum_poly, w) = layer(&mut rng);
+
+        let virtual_poly = VirtualPolynomial::try_from(&sum_poly).unwrap();
+        assert_eq!(virtual_poly.multilinears().len(), 4);
+        assert_eq!(virtual_poly.multilinears()[1].as_ref(), &w);
+        assert_eq!(virtual_poly.products()[2], (Fq::from(1), vec![3, 1, 1]));
+        assert_eq!(virtual_poly.degree_in_bound_variable(), 3);
+        assert_eq!(
+            ComposedPolynomial::hypercube_sum(&virtual_poly),
+            ComposedPolynomial::hypercube_sum(&sum_poly)
+        );
+
+        let point: Vec<Fq> = (0..4).map(|_| Fq::rand(&mut rng)).collect();
+        assert_eq!(virtual_poly.evaluate(&point), sum_poly.evaluate(&point));
+    }
+
+    #[test]
+    fn each_shared_table_is_folded_once_per_round() {
+        let mut rng = StdRng::seed_from_u64(91);
+        let (sum_poly, _) = layer(&mut rng);
+        let mut virtual_poly = VirtualPolynomial::try_from(&sum_poly).unwrap();
+        let mut dense = sum_poly;
+
+        for _ in 0..4 {
+            let challenge = Fq::rand(&mut rng);
+            let before = FOLDS.with(|folds| folds.get());
+
+            virtual_poly.fold_in_place(&challenge);
+            dense.fold_in_place(&challenge);
+
+            // Four distinct tables behind seven factor slots.
+            assert_eq!(FOLDS.with(|folds| folds.get()) - before, 4);
+            assert_eq!(
+                ComposedPolynomial::hypercube_sum(&virtual_poly),
+                ComposedPolynomial::hypercube_sum(&dense)
+            );
+        }
+    }
+
+    #[test]
+    fn it_builds_terms_from_shared_arcs() {
+        let mut rng = StdRng::seed_from_u64(92);
+        let w = Arc::new(MultilinearPoly::<Fq>::rand(3, &mut rng));
+        let a = Arc::new(MultilinearPoly::<Fq>::rand(3, &mut rng));
+
+        let virtual_poly = VirtualPolynomial::new(3)
+            .add_product(Fq::from(2), vec![a.clone(), w.clone()])
+            .unwrap()
+            .add_product(Fq::from(5), vec![w.clone(), w.clone()])
+            .unwrap();
+        assert_eq!(virtual_poly.multilinears().len(), 2);
+
+        let point: Vec<Fq> = (0..3).map(|_| Fq::rand(&mut rng)).collect();
+        let (a_eval, w_eval) = (a.evaluate(&point), w.evaluate(&point));
+        assert_eq!(
+            virtual_poly.evaluate(&point),
+            Fq::from(2) * a_eval * w_eval + Fq::from(5) * w_eval * w_eval
+        );
+
+        assert_eq!(
+            VirtualPolynomial::new(2).add_product(Fq::from(1), vec![w]),
+            Err(ShapeError::MismatchedNumVars {
+                term: 0,
+                factor: 0,
+                expected: 2,
+                got: 3
+            })
+        );
+        assert_eq!(
+            ComposedPolynomial::num_vars(&VirtualPolynomial::<Fq>::new(3)),
+            Err(ShapeError::EmptySumPoly)
+        );
+    }
+}
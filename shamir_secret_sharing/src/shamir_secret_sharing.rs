@@ -1,9 +1,13 @@
 use ark_bn254::Fq;
 use ark_std::rand::rngs::StdRng;
 use rand::{Rng, SeedableRng};
-use univariate_polynomial::univariate_polynomial_dense::UnivariatePoly;
+use univariate_polynomial::univariate_polynomial_dense::{InterpolationError, UnivariatePoly};
 
-fn create_polynomia(threshold: usize, secret_value: Fq, secret_point: Fq) -> UnivariatePoly<Fq> {
+fn create_polynomia(
+    threshold: usize,
+    secret_value: Fq,
+    secret_point: Fq,
+) -> Result<UnivariatePoly<Fq>, InterpolationError<Fq>> {
     let mut points = vec![];
     let base_point = (secret_point, secret_value);
 
@@ -18,12 +22,13 @@ fn create_polynomia(threshold: usize, secret_value: Fq, secret_point: Fq) -> Uni
         points.push((random_x_point, random_y_point));
     }
 
-    let poly = UnivariatePoly::interpolate(points);
-
-    poly
+    UnivariatePoly::interpolate(points)
 }
 
-fn recover_polynomial(points: Vec<(Fq, Fq)>, threshold: usize) -> UnivariatePoly<Fq> {
+fn recover_polynomial(
+    points: Vec<(Fq, Fq)>,
+    threshold: usize,
+) -> Result<UnivariatePoly<Fq>, InterpolationError<Fq>> {
     if points.len() < threshold {
         panic!("Not enough points to recreate polynomial");
     }
@@ -71,7 +76,7 @@ fn share_points(
 #[cfg(test)]
 mod test {
     use ark_bn254::Fq;
-    use univariate_polynomial::univariate_polynomial_dense::UnivariatePoly;
+    use univariate_polynomial::univariate_polynomial_dense::{InterpolationError, UnivariatePoly};
 
     use super::{create_polynomia, get_secret, recover_polynomial, share_points};
 
@@ -81,7 +86,7 @@ mod test {
         let secret_value = Fq::from(40);
         let secret_point = Fq::from(6);
 
-        let mut polynomial = create_polynomia(threshold, secret_value, secret_point);
+        let mut polynomial = create_polynomia(threshold, secret_value, secret_point).unwrap();
 
         let secret_evaluation = polynomial.evaluate(Fq::from(6));
 
@@ -98,7 +103,7 @@ mod test {
         ];
         let threshold = 3;
 
-        let secret_poly = recover_polynomial(points, threshold);
+        let secret_poly = recover_polynomial(points, threshold).unwrap();
 
         assert_eq!(
             secret_poly.coefficient,
@@ -134,7 +139,7 @@ mod test {
             wrong_point,
         ];
 
-        let polynomial = recover_polynomial(points, 3);
+        let polynomial = recover_polynomial(points, 3).unwrap();
 
         assert_ne!(
             polynomial.coefficient,
@@ -143,6 +148,20 @@ mod test {
         assert_ne!(polynomial.evaluate(Fq::from(0)), Fq::from(-5))
     }
 
+    #[test]
+    fn it_rejects_two_shares_at_the_same_point() {
+        let points = vec![
+            (Fq::from(1), Fq::from(-1)),
+            (Fq::from(2), Fq::from(5)),
+            (Fq::from(2), Fq::from(5)),
+        ];
+
+        assert_eq!(
+            recover_polynomial(points, 3),
+            Err(InterpolationError::DuplicateX { x: Fq::from(2) })
+        );
+    }
+
     #[test]
     #[should_panic]
     fn it_doesnt_generate_with_few_points() {
@@ -155,13 +174,13 @@ mod test {
     fn it_all_works_properly() {
         let secret_point = Fq::from(0);
         let secret_data = Fq::from(-5);
-        let secret_poly = create_polynomia(3, secret_data, secret_point);
+        let secret_poly = create_polynomia(3, secret_data, secret_point).unwrap();
 
         let shares = share_points(10, 3, &secret_poly);
 
         let collected_shares = shares[2..6].to_vec();
 
-        let recreated_poly = recover_polynomial(collected_shares, 3);
+        let recreated_poly = recover_polynomial(collected_shares, 3).unwrap();
 
         let recovered_secret = get_secret(&recreated_poly, secret_point);
 
@@ -53,10 +53,31 @@ pub fn verify_benchmark(c: &mut Criterion) {
         b.iter(|| {
             // Assuming Proof implements Clone so we can use the same proof repeatedly.
             let valid = verify(black_box(&poly), black_box(proof.clone()));
-            black_box(valid);
+            let _ = black_box(valid);
         })
     });
 }
 
-criterion_group!(benches, prove_benchmark, verify_benchmark);
+/// Benchmarks verification on a larger polynomial so the cost of the single
+/// final evaluation dominates; the rounds themselves only touch the proof.
+pub fn verify_large_benchmark(c: &mut Criterion) {
+    // An 18-variable polynomial with evaluations 0, 1, ..., 2^18 - 1.
+    let evaluations: Vec<Fq> = (0..1u64 << 18).map(Fq::from).collect();
+    let poly = MultilinearPoly::new(evaluations);
+    let proof = prove(&poly);
+
+    c.bench_function("SumCheck Verify 18 vars", |b| {
+        b.iter(|| {
+            let valid = verify(black_box(&poly), black_box(proof.clone()));
+            let _ = black_box(valid);
+        })
+    });
+}
+
+criterion_group!(
+    benches,
+    prove_benchmark,
+    verify_benchmark,
+    verify_large_benchmark
+);
 criterion_main!(benches);
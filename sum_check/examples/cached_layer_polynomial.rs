@@ -0,0 +1,58 @@
+use std::{fs::File, io::BufReader, io::BufWriter};
+
+use ark_bn254::Fq;
+use ark_serialize::{CanonicalDeserialize, CanonicalSerialize};
+use fiat_shamir::fiat_shamir_transcript::Transcript;
+use multilinear_polynomial::composed_polynomial::{ProductPoly, SumPoly};
+use sum_check::sum_check_protocol::{gkr_prove_rounds, gkr_verify_rounds};
+
+/// Builds a layer's composed polynomial once, caches it on disk, and proves
+/// from the cached copy as often as needed:
+///
+///     cargo run --release --example cached_layer_polynomial
+fn main() {
+    let path = std::env::temp_dir().join("cached_layer_polynomial.bin");
+
+    // Offline: build and store the polynomial.
+    let num_vars = 12;
+    let table = |seed: u64| -> Vec<Fq> {
+        (0..1u64 << num_vars)
+            .map(|i| Fq::from(i * seed + 1))
+            .collect()
+    };
+    let composed_polynomial = SumPoly::new(vec![
+        ProductPoly::new(vec![table(1), table(2)]),
+        ProductPoly::new(vec![table(3), table(4), table(5)]),
+    ]);
+    composed_polynomial
+        .serialize_compressed(BufWriter::new(File::create(&path).unwrap()))
+        .unwrap();
+
+    // Online: load it, which checks its shape, and prove from it.
+    let cached =
+        SumPoly::<Fq>::deserialize_compressed(BufReader::new(File::open(&path).unwrap())).unwrap();
+    assert_eq!(cached, composed_polynomial);
+
+    let claimed_sum = cached.reduce().hypercube_sum();
+    let degree = cached.degree_in_bound_variable();
+
+    for session in 0..3u8 {
+        let label = [b"session ".as_slice(), &[b'0' + session]].concat();
+
+        let rounds =
+            gkr_prove_rounds(claimed_sum, &cached, &mut Transcript::new(), Some(&label)).unwrap();
+        let claim = gkr_verify_rounds(
+            rounds.proof_polynomials,
+            claimed_sum,
+            degree,
+            &mut Transcript::new(),
+            Some(&label),
+        )
+        .unwrap();
+        assert_eq!(cached.evaluate(&claim.point), claim.value);
+
+        println!("session {}: proved from {}", session, path.display());
+    }
+
+    std::fs::remove_file(&path).unwrap();
+}
@@ -0,0 +1,121 @@
+use std::{
+    alloc::{GlobalAlloc, Layout, System},
+    sync::atomic::{AtomicUsize, Ordering},
+    time::Instant,
+};
+
+use ark_bn254::Fq;
+use fiat_shamir::fiat_shamir_transcript::Transcript;
+use multilinear_polynomial::{
+    composed_polynomial::{ProductPoly, SumPoly},
+    multilinear_polynomial_evaluation::MultilinearPoly,
+};
+use sum_check::sum_check_protocol::gkr_prove;
+
+/// Tracks the bytes currently allocated and the high-water mark since the
+/// last reset.
+struct CountingAllocator;
+
+static ALLOCATED: AtomicUsize = AtomicUsize::new(0);
+static PEAK: AtomicUsize = AtomicUsize::new(0);
+
+unsafe impl GlobalAlloc for CountingAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        let current = ALLOCATED.fetch_add(layout.size(), Ordering::Relaxed) + layout.size();
+        PEAK.fetch_max(current, Ordering::Relaxed);
+
+        System.alloc(layout)
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        ALLOCATED.fetch_sub(layout.size(), Ordering::Relaxed);
+
+        System.dealloc(ptr, layout)
+    }
+}
+
+#[global_allocator]
+static GLOBAL: CountingAllocator = CountingAllocator;
+
+/// Runs `f` and reports its wall time and the peak memory it allocated on
+/// top of what was live beforehand.
+fn measure<T>(name: &str, f: impl FnOnce() -> T) -> T {
+    let baseline = ALLOCATED.load(Ordering::Relaxed);
+    PEAK.store(baseline, Ordering::Relaxed);
+
+    let start = Instant::now();
+    let result = f();
+    let elapsed = start.elapsed();
+
+    println!(
+        "{}: {:?}, peak {} MiB",
+        name,
+        elapsed,
+        (PEAK.load(Ordering::Relaxed) - baseline) >> 20
+    );
+
+    result
+}
+
+/// The round polynomials of the earlier prover, which cloned the composed
+/// polynomial and bound a fresh copy of it at every evaluation point.
+fn round_polynomials_by_rebinding(
+    composed_polynomial: &SumPoly<Fq>,
+    challenges: &[Fq],
+) -> Vec<Vec<Fq>> {
+    let degree = composed_polynomial.get_degree();
+    let mut current_poly = composed_polynomial.clone();
+
+    challenges
+        .iter()
+        .map(|challenge| {
+            let round_poly = (0..=degree as u64)
+                .map(|i| {
+                    current_poly
+                        .partial_evaluate(&Fq::from(i))
+                        .reduce()
+                        .hypercube_sum()
+                })
+                .collect();
+            current_poly = current_poly.partial_evaluate(challenge);
+
+            round_poly
+        })
+        .collect()
+}
+
+/// Compares one GKR layer's prover against rebinding the composed polynomial
+/// at every point, over 2^18 evaluations per table:
+///
+///     cargo run --release --example gkr_prove_memory
+fn main() {
+    let num_vars = 18;
+    let table = |seed: u64| -> Vec<Fq> {
+        (0..1u64 << num_vars)
+            .map(|i| Fq::from(i * seed + 1))
+            .collect()
+    };
+    let composed_polynomial = SumPoly::new(vec![
+        ProductPoly::new(vec![table(1), table(2)]),
+        ProductPoly::new(vec![table(3), table(4)]),
+    ]);
+    let next_layer = MultilinearPoly::new((0..1u64 << (num_vars / 2)).map(Fq::from).collect());
+    let claimed_sum = composed_polynomial.reduce().hypercube_sum();
+
+    let proof = measure("single pass", || {
+        gkr_prove(
+            claimed_sum,
+            &composed_polynomial,
+            &next_layer,
+            &mut Transcript::new(),
+            None,
+        )
+        .unwrap()
+    });
+
+    let expected = measure("rebinding", || {
+        round_polynomials_by_rebinding(&composed_polynomial, &proof.random_challenges)
+    });
+
+    assert_eq!(proof.proof_polynomials, expected);
+}
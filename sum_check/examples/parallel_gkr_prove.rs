@@ -0,0 +1,49 @@
+use std::time::Instant;
+
+use ark_bn254::Fq;
+use fiat_shamir::fiat_shamir_transcript::Transcript;
+use multilinear_polynomial::{
+    composed_polynomial::{ProductPoly, SumPoly},
+    multilinear_polynomial_evaluation::MultilinearPoly,
+};
+use sum_check::sum_check_protocol::gkr_prove;
+
+/// Times one GKR layer's sum-check over a composed polynomial of 2^16
+/// evaluations. Run it with and without `--features parallel` to compare:
+///
+///     cargo run --release --example parallel_gkr_prove
+///     cargo run --release --example parallel_gkr_prove --features parallel
+fn main() {
+    let num_vars = 16;
+    let table = |seed: u64| -> Vec<Fq> {
+        (0..1u64 << num_vars)
+            .map(|i| Fq::from(i * seed + 1))
+            .collect()
+    };
+    let composed_polynomial = SumPoly::new(vec![
+        ProductPoly::new(vec![table(1), table(2)]),
+        ProductPoly::new(vec![table(3), table(4)]),
+    ]);
+    let next_layer = MultilinearPoly::new((0..1u64 << (num_vars / 2)).map(Fq::from).collect());
+    let claimed_sum = composed_polynomial.reduce().hypercube_sum();
+
+    let start = Instant::now();
+    let proof = gkr_prove(
+        claimed_sum,
+        &composed_polynomial,
+        &next_layer,
+        &mut Transcript::new(),
+        None,
+    )
+    .unwrap();
+    let elapsed = start.elapsed();
+
+    println!(
+        "proved a {}-variable layer in {:?} (parallel: {})",
+        num_vars,
+        elapsed,
+        cfg!(feature = "parallel")
+    );
+
+    assert_eq!(proof.proof_polynomials.len(), num_vars);
+}
@@ -0,0 +1,56 @@
+use std::time::Instant;
+
+use ark_bn254::Fq;
+use fiat_shamir::fiat_shamir_transcript::Transcript;
+use multilinear_polynomial::{
+    composed_polynomial::{ProductPoly, SumPoly},
+    multilinear_polynomial_evaluation::MultilinearPoly,
+};
+use sum_check::sum_check_protocol::gkr_prove;
+
+/// Times `reduce`, one `partial_evaluate` and the whole sum-check of a
+/// 2^18-evaluation layer. Run it with and without `--features parallel` to
+/// compare; the proof is the same either way:
+///
+///     cargo run --release --example parallel_layer
+///     cargo run --release --example parallel_layer --features parallel
+fn main() {
+    let num_vars = 18;
+    let table = |seed: u64| -> Vec<Fq> {
+        (0..1u64 << num_vars)
+            .map(|i| Fq::from(i * seed + 1))
+            .collect()
+    };
+    let composed_polynomial = SumPoly::new(vec![
+        ProductPoly::new(vec![table(1), table(2)]),
+        ProductPoly::new(vec![table(3), table(4)]),
+        ProductPoly::new(vec![table(5), table(2), table(4)]),
+    ]);
+    let next_layer = MultilinearPoly::new((0..1u64 << (num_vars / 2)).map(Fq::from).collect());
+
+    let start = Instant::now();
+    let claimed_sum = composed_polynomial.reduce().hypercube_sum();
+    println!("reduce: {:?}", start.elapsed());
+
+    let start = Instant::now();
+    let bound = composed_polynomial.partial_evaluate(&Fq::from(7));
+    println!("partial_evaluate: {:?}", start.elapsed());
+    assert_eq!(bound.num_vars(), Ok(num_vars - 1));
+
+    let start = Instant::now();
+    let proof = gkr_prove(
+        claimed_sum,
+        &composed_polynomial,
+        &next_layer,
+        &mut Transcript::new(),
+        None,
+    )
+    .unwrap();
+    println!(
+        "gkr_prove: {:?} (parallel: {})",
+        start.elapsed(),
+        cfg!(feature = "parallel")
+    );
+
+    assert_eq!(proof.proof_polynomials.len(), num_vars);
+}
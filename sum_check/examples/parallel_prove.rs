@@ -0,0 +1,29 @@
+use std::time::Instant;
+
+use ark_bn254::Fq;
+use multilinear_polynomial::multilinear_polynomial_evaluation::MultilinearPoly;
+use sum_check::sum_check_protocol::{prove, verify};
+
+/// Times the sum-check prover on 2^22 evaluations. Run it with and without
+/// `--features parallel` to compare:
+///
+///     cargo run --release --example parallel_prove
+///     cargo run --release --example parallel_prove --features parallel
+fn main() {
+    let num_vars = 22;
+    let evaluations: Vec<Fq> = (0..1u64 << num_vars).map(Fq::from).collect();
+    let poly = MultilinearPoly::new(evaluations);
+
+    let start = Instant::now();
+    let proof = prove(&poly);
+    let elapsed = start.elapsed();
+
+    println!(
+        "proved {} variables in {:?} (parallel: {})",
+        num_vars,
+        elapsed,
+        cfg!(feature = "parallel")
+    );
+
+    assert!(verify(&poly, proof).is_ok());
+}
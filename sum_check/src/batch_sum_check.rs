@@ -0,0 +1,249 @@
+use ark_ff::PrimeField;
+use fiat_shamir::fiat_shamir_transcript::{fq_vec_to_bytes, Transcript};
+use multilinear_polynomial::{
+    evaluation_claim::EvaluationClaim, multilinear_polynomial_evaluation::MultilinearPoly,
+};
+
+use crate::{
+    sum_check_error::SumCheckError,
+    sum_check_protocol::{prove_rounds, verify_rounds},
+};
+
+/// A single sum-check over `Σ αⁱ·fᵢ`, where α is drawn from the transcript
+/// after the individual claimed sums have been absorbed.
+#[derive(Debug, Clone)]
+pub struct BatchProof<F: PrimeField> {
+    pub proof_polynomials: Vec<Vec<F>>,
+    pub claimed_sums: Vec<F>,
+    pub final_evaluations: Vec<F>,
+}
+
+/// Fails on an empty batch or on polynomials of different numbers of
+/// variables.
+pub fn prove_batch<F: PrimeField>(
+    polys: &[MultilinearPoly<F>],
+) -> Result<BatchProof<F>, SumCheckError<F>> {
+    let num_vars = common_num_vars(polys)?;
+
+    let claimed_sums: Vec<F> = polys.iter().map(|poly| poly.hypercube_sum()).collect();
+
+    let mut transcript = Transcript::<F>::new();
+    let alpha = append_batch_statement(&mut transcript, num_vars, &claimed_sums);
+
+    let combined_poly = combine(polys, alpha);
+
+    let (proof_polynomials, random_challenges, _) = prove_rounds(&combined_poly, &mut transcript);
+
+    let final_evaluations = polys
+        .iter()
+        .map(|poly| poly.evaluate(&random_challenges))
+        .collect();
+
+    Ok(BatchProof {
+        proof_polynomials,
+        claimed_sums,
+        final_evaluations,
+    })
+}
+
+/// Verifies a batch proof and returns one evaluation claim per polynomial at
+/// the shared random point, after checking each against `polys`. Rejects
+/// the batch shapes `prove_batch` does.
+pub fn verify_batch<F: PrimeField>(
+    polys: &[MultilinearPoly<F>],
+    proof: BatchProof<F>,
+) -> Result<Vec<EvaluationClaim<F>>, SumCheckError<F>> {
+    let num_vars = common_num_vars(polys)?;
+
+    for claims in [&proof.claimed_sums, &proof.final_evaluations] {
+        if claims.len() != polys.len() {
+            return Err(SumCheckError::WrongNumberOfClaims {
+                expected: polys.len(),
+                got: claims.len(),
+            });
+        }
+    }
+
+    let mut transcript = Transcript::<F>::new();
+    let alpha = append_batch_statement(&mut transcript, num_vars, &proof.claimed_sums);
+
+    let combined_claim = linear_combination(&proof.claimed_sums, alpha);
+
+    let sub_claim = verify_rounds(
+        combined_claim,
+        num_vars,
+        &proof.proof_polynomials,
+        &mut transcript,
+    )?;
+
+    let combined_evaluation = linear_combination(&proof.final_evaluations, alpha);
+    if combined_evaluation != sub_claim.value {
+        return Err(SumCheckError::FinalEvaluationMismatch {
+            expected: sub_claim.value,
+            got: combined_evaluation,
+        });
+    }
+
+    polys
+        .iter()
+        .zip(proof.final_evaluations)
+        .map(|(poly, claimed_eval)| {
+            let eval = poly.evaluate(&sub_claim.point);
+
+            if eval != claimed_eval {
+                return Err(SumCheckError::FinalEvaluationMismatch {
+                    expected: claimed_eval,
+                    got: eval,
+                });
+            }
+
+            Ok(EvaluationClaim {
+                point: sub_claim.point.clone(),
+                value: claimed_eval,
+            })
+        })
+        .collect()
+}
+
+/// The number of variables every polynomial of a nonempty batch shares.
+pub(crate) fn common_num_vars<F: PrimeField>(
+    polys: &[MultilinearPoly<F>],
+) -> Result<usize, SumCheckError<F>> {
+    let num_vars = polys.first().ok_or(SumCheckError::EmptyBatch)?.num_vars();
+
+    match polys.iter().position(|poly| poly.num_vars() != num_vars) {
+        Some(index) => Err(SumCheckError::MismatchedNumVars {
+            index,
+            expected: num_vars,
+            got: polys[index].num_vars(),
+        }),
+        None => Ok(num_vars),
+    }
+}
+
+pub(crate) fn append_batch_statement<F: PrimeField>(
+    transcript: &mut Transcript<F>,
+    num_vars: usize,
+    claimed_sums: &[F],
+) -> F {
+    transcript.append(&fq_vec_to_bytes(&[
+        F::from(num_vars as u64),
+        F::from(claimed_sums.len() as u64),
+    ]));
+    transcript.append(&fq_vec_to_bytes(claimed_sums));
+
+    transcript.get_uniform_challenge()
+}
+
+/// `Σ alphaⁱ·polys[i]`, for a batch `common_num_vars` accepts.
+pub(crate) fn combine<F: PrimeField>(polys: &[MultilinearPoly<F>], alpha: F) -> MultilinearPoly<F> {
+    let mut power = F::one();
+    let mut combined = vec![F::zero(); polys[0].len()];
+
+    for poly in polys {
+        for (acc, eval) in combined.iter_mut().zip(poly.evaluations().iter()) {
+            *acc += power * eval;
+        }
+
+        power *= alpha;
+    }
+
+    MultilinearPoly::new(combined)
+}
+
+pub(crate) fn linear_combination<F: PrimeField>(values: &[F], alpha: F) -> F {
+    values
+        .iter()
+        .rev()
+        .fold(F::zero(), |acc, value| acc * alpha + value)
+}
+
+#[cfg(test)]
+mod test {
+    use ark_bn254::Fq;
+    use multilinear_polynomial::multilinear_polynomial_evaluation::MultilinearPoly;
+
+    use super::{prove_batch, verify_batch};
+    use crate::sum_check_error::SumCheckError;
+
+    fn sample_polys() -> Vec<MultilinearPoly<Fq>> {
+        vec![
+            MultilinearPoly::new(vec![Fq::from(0), Fq::from(3), Fq::from(2), Fq::from(5)]),
+            MultilinearPoly::new(vec![Fq::from(1), Fq::from(1), Fq::from(4), Fq::from(0)]),
+            MultilinearPoly::new(vec![Fq::from(7), Fq::from(0), Fq::from(0), Fq::from(9)]),
+        ]
+    }
+
+    #[test]
+    fn it_proves_and_verifies_a_batch() {
+        let polys = sample_polys();
+
+        let proof = prove_batch(&polys).unwrap();
+
+        assert_eq!(
+            proof.claimed_sums,
+            vec![Fq::from(10), Fq::from(6), Fq::from(16)]
+        );
+
+        let sub_claims = verify_batch(&polys, proof).unwrap();
+
+        assert_eq!(sub_claims.len(), polys.len());
+        for (poly, sub_claim) in polys.iter().zip(sub_claims.iter()) {
+            assert_eq!(poly.evaluate(&sub_claim.point), sub_claim.value);
+        }
+    }
+
+    #[test]
+    fn it_rejects_a_batch_with_one_wrong_claimed_sum() {
+        let polys = sample_polys();
+
+        let mut proof = prove_batch(&polys).unwrap();
+        proof.claimed_sums[1] += Fq::from(1);
+
+        let result = verify_batch(&polys, proof);
+
+        assert!(matches!(
+            result,
+            Err(SumCheckError::RoundSumMismatch { round: 0, .. })
+        ));
+    }
+
+    #[test]
+    fn it_rejects_an_empty_or_mismatched_batch() {
+        assert_eq!(
+            prove_batch::<Fq>(&[]).unwrap_err(),
+            SumCheckError::EmptyBatch
+        );
+
+        let mut polys = sample_polys();
+        let proof = prove_batch(&polys).unwrap();
+        polys.push(MultilinearPoly::new(vec![Fq::from(1), Fq::from(2)]));
+        let mismatch = SumCheckError::MismatchedNumVars {
+            index: 3,
+            expected: 2,
+            got: 1,
+        };
+
+        assert_eq!(prove_batch(&polys).unwrap_err(), mismatch);
+        assert_eq!(verify_batch(&polys, proof.clone()), Err(mismatch));
+        assert_eq!(verify_batch(&[], proof), Err(SumCheckError::EmptyBatch));
+    }
+
+    #[test]
+    fn it_rejects_a_batch_with_a_missing_claim() {
+        let polys = sample_polys();
+
+        let mut proof = prove_batch(&polys).unwrap();
+        proof.claimed_sums.pop();
+
+        let result = verify_batch(&polys, proof);
+
+        assert_eq!(
+            result,
+            Err(SumCheckError::WrongNumberOfClaims {
+                expected: 3,
+                got: 2
+            })
+        );
+    }
+}
@@ -0,0 +1,252 @@
+use ark_ff::{Field, PrimeField};
+use fiat_shamir::fiat_shamir_transcript::{fq_vec_to_bytes, Transcript};
+use multilinear_polynomial::multilinear_polynomial_evaluation::MultilinearPoly;
+
+use crate::{
+    sum_check_error::SumCheckError,
+    sum_check_protocol::{append_statement, get_round_partial_polynomial_proof},
+};
+
+/// A sum-check proof for a table over `F` whose challenges were drawn from
+/// an extension `E` of it, as a small base field needs for soundness: each
+/// round leaves a cheating prover a `1/|E|` chance instead of `1/|F|`.
+///
+/// The sum itself is over the table, so it stays in `F`; everything after
+/// the first challenge is in `E`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ExtensionProof<F: PrimeField, E: Field<BasePrimeField = F>> {
+    pub proof_polynomials: Vec<Vec<E>>,
+    pub claimed_sum: F,
+    pub final_evaluation: E,
+}
+
+/// `prove` with challenges from `E`. The table is lifted into `E` by the
+/// first fold, and elements of `E` are absorbed as their coordinates over
+/// `F`, so with `E = F` the transcript, and the proof, match `prove`.
+pub fn prove_ext<F: PrimeField, E: Field<BasePrimeField = F>>(
+    polynomial: &MultilinearPoly<F>,
+) -> ExtensionProof<F, E> {
+    let claimed_sum = polynomial.hypercube_sum();
+
+    let mut transcript = Transcript::<F>::new();
+    transcript.append(&polynomial.digest());
+    append_statement(&mut transcript, polynomial.num_vars(), claimed_sum);
+
+    let num_rounds = polynomial.num_vars();
+    let mut proof_polynomials = Vec::with_capacity(num_rounds);
+
+    let lift =
+        |values: Vec<F>| -> Vec<E> { values.into_iter().map(E::from_base_prime_field).collect() };
+
+    let final_evaluation = match num_rounds {
+        0 => E::from_base_prime_field(claimed_sum),
+        _ => {
+            let first_message = lift(get_round_partial_polynomial_proof(polynomial.evaluations()));
+            let challenge = next_ext_challenge(&mut transcript, &first_message);
+            proof_polynomials.push(first_message);
+
+            let mut current = polynomial
+                .partial_evaluate_ext(0, &challenge)
+                .expect("a round is left to bind");
+
+            for _ in 1..num_rounds {
+                let message = get_round_partial_polynomial_proof(current.evaluations());
+                let challenge = next_ext_challenge(&mut transcript, &message);
+                proof_polynomials.push(message);
+
+                current
+                    .partial_evaluate_in_place(0, &challenge)
+                    .expect("a round is left to bind");
+            }
+
+            current.evaluations()[0]
+        }
+    };
+    transcript.append(&ext_to_bytes(&[final_evaluation]));
+
+    ExtensionProof {
+        proof_polynomials,
+        claimed_sum,
+        final_evaluation,
+    }
+}
+
+/// Verifies a `prove_ext` proof, evaluating `polynomial` at the final point
+/// in `E`.
+pub fn verify_ext<F: PrimeField, E: Field<BasePrimeField = F>>(
+    polynomial: &MultilinearPoly<F>,
+    proof: ExtensionProof<F, E>,
+) -> Result<(), SumCheckError<E>> {
+    let num_vars = polynomial.num_vars();
+    if proof.proof_polynomials.len() != num_vars {
+        return Err(SumCheckError::WrongNumberOfRounds {
+            expected: num_vars,
+            got: proof.proof_polynomials.len(),
+        });
+    }
+
+    let mut transcript = Transcript::<F>::new();
+    transcript.append(&polynomial.digest());
+    append_statement(&mut transcript, num_vars, proof.claimed_sum);
+
+    let mut claim = E::from_base_prime_field(proof.claimed_sum);
+    let mut point = Vec::with_capacity(num_vars);
+
+    for (round, poly) in proof.proof_polynomials.iter().enumerate() {
+        if poly.len() != 2 {
+            return Err(SumCheckError::InvalidRoundPolynomialLength {
+                round,
+                expected: 2,
+                got: poly.len(),
+            });
+        }
+
+        if poly[0] + poly[1] != claim {
+            return Err(SumCheckError::RoundSumMismatch {
+                round,
+                expected: claim,
+                got: poly[0] + poly[1],
+            });
+        }
+
+        let challenge = next_ext_challenge(&mut transcript, poly);
+        claim = poly[0] + challenge * (poly[1] - poly[0]);
+        point.push(challenge);
+    }
+
+    let oracle_eval = polynomial
+        .try_evaluate_ext(&point)
+        .expect("one challenge per variable");
+
+    if proof.final_evaluation != claim || oracle_eval != claim {
+        return Err(SumCheckError::FinalEvaluationMismatch {
+            expected: claim,
+            got: oracle_eval,
+        });
+    }
+
+    Ok(())
+}
+
+/// Absorbs a round message and draws a challenge in `E` from one transcript
+/// challenge per coordinate over `F`.
+fn next_ext_challenge<F: PrimeField, E: Field<BasePrimeField = F>>(
+    transcript: &mut Transcript<F>,
+    round_message: &[E],
+) -> E {
+    transcript.append(&ext_to_bytes(round_message));
+
+    let coordinates = (0..E::extension_degree()).map(|_| transcript.get_uniform_challenge());
+
+    E::from_base_prime_field_elems(coordinates).expect("one coordinate per degree")
+}
+
+fn ext_to_bytes<F: PrimeField, E: Field<BasePrimeField = F>>(values: &[E]) -> Vec<u8> {
+    let coordinates: Vec<F> = values
+        .iter()
+        .flat_map(|value| value.to_base_prime_field_elements())
+        .collect();
+
+    fq_vec_to_bytes(&coordinates)
+}
+
+#[cfg(test)]
+mod test {
+    use ark_bn254::Fq;
+    use ark_ff::{
+        fields::{Fp2, Fp2Config, Fp64, MontBackend, MontConfig},
+        Field, MontFp,
+    };
+    use multilinear_polynomial::multilinear_polynomial_evaluation::MultilinearPoly;
+    use rand::{rngs::StdRng, SeedableRng};
+
+    use super::{prove_ext, verify_ext};
+    use crate::{
+        sum_check_error::SumCheckError,
+        sum_check_protocol::{prove, verify},
+    };
+
+    /// The 64-bit Goldilocks field, `p = 2^64 - 2^32 + 1`.
+    #[derive(MontConfig)]
+    #[modulus = "18446744069414584321"]
+    #[generator = "7"]
+    pub struct GoldilocksConfig;
+    type Goldilocks = Fp64<MontBackend<GoldilocksConfig, 1>>;
+
+    /// `Goldilocks[u] / (u^2 - 7)`; 7 generates the multiplicative group, so
+    /// it is not a square.
+    pub struct Goldilocks2Config;
+    impl Fp2Config for Goldilocks2Config {
+        type Fp = Goldilocks;
+
+        const NONRESIDUE: Goldilocks = MontFp!("7");
+        const FROBENIUS_COEFF_FP2_C1: &'static [Goldilocks] =
+            &[MontFp!("1"), MontFp!("18446744069414584320")];
+    }
+    type Goldilocks2 = Fp2<Goldilocks2Config>;
+
+    #[test]
+    fn it_proves_a_small_field_table_with_extension_challenges() {
+        let mut rng = StdRng::seed_from_u64(82);
+        let polynomial = MultilinearPoly::<Goldilocks>::rand(6, &mut rng);
+
+        let proof = prove_ext::<Goldilocks, Goldilocks2>(&polynomial);
+        assert_eq!(proof.claimed_sum, polynomial.hypercube_sum());
+        assert!(proof.proof_polynomials[1..]
+            .iter()
+            .flatten()
+            .any(|eval| eval.c1 != Goldilocks::from(0)));
+        assert_eq!(verify_ext(&polynomial, proof.clone()), Ok(()));
+
+        let mut tampered = proof;
+        tampered.proof_polynomials[2][0] += Goldilocks2::ONE;
+        assert!(matches!(
+            verify_ext(&polynomial, tampered),
+            Err(SumCheckError::RoundSumMismatch { round: 2, .. })
+        ));
+    }
+
+    #[test]
+    fn extension_folding_agrees_with_evaluation() {
+        let mut rng = StdRng::seed_from_u64(83);
+        let polynomial = MultilinearPoly::<Goldilocks>::rand(3, &mut rng);
+        let point = [
+            Goldilocks2::new(Goldilocks::from(3), Goldilocks::from(5)),
+            Goldilocks2::new(Goldilocks::from(2), Goldilocks::from(0)),
+            Goldilocks2::new(Goldilocks::from(9), Goldilocks::from(1)),
+        ];
+
+        // The multilinear extension is sum of table entries times eq weights.
+        let expected: Goldilocks2 = polynomial
+            .evaluations()
+            .iter()
+            .enumerate()
+            .map(|(index, eval)| {
+                let weight: Goldilocks2 = (0..3)
+                    .map(|i| match index >> (2 - i) & 1 {
+                        1 => point[i],
+                        _ => Goldilocks2::ONE - point[i],
+                    })
+                    .product();
+                weight.mul_by_base_prime_field(eval)
+            })
+            .sum();
+
+        assert_eq!(polynomial.try_evaluate_ext(&point), Ok(expected));
+    }
+
+    #[test]
+    fn over_the_base_field_it_is_the_plain_protocol() {
+        let mut rng = StdRng::seed_from_u64(84);
+        let polynomial = MultilinearPoly::<Fq>::rand(5, &mut rng);
+
+        let proof = prove_ext::<Fq, Fq>(&polynomial);
+        let plain = prove(&polynomial);
+
+        assert_eq!(proof.proof_polynomials, plain.proof_polynomials);
+        assert_eq!(proof.claimed_sum, plain.claimed_sum);
+        assert_eq!(proof.final_evaluation, plain.final_evaluation);
+        assert_eq!(verify_ext(&polynomial, proof), Ok(()));
+        assert_eq!(verify(&polynomial, plain), Ok(()));
+    }
+}
@@ -0,0 +1,398 @@
+use std::cell::OnceCell;
+
+use ark_ff::PrimeField;
+use fiat_shamir::fiat_shamir_transcript::{fq_vec_to_bytes, Transcript};
+use multilinear_polynomial::{
+    evaluation_claim::EvaluationClaim, multilinear_polynomial_evaluation::MultilinearPoly,
+};
+use rand::RngCore;
+use univariate_polynomial::barycentric::BarycentricDomain;
+
+use crate::{
+    sum_check_error::SumCheckError,
+    sum_check_protocol::{get_round_partial_polynomial_proof, BindingOrder},
+};
+
+/// Where the verifier's per-round challenges come from. The Fiat-Shamir
+/// transcript is one source; a plain RNG gives the interactive protocol.
+pub trait ChallengeSource<F: PrimeField> {
+    fn next_challenge(&mut self, round_message: &[F]) -> F;
+}
+
+impl<F: PrimeField> ChallengeSource<F> for Transcript<F> {
+    fn next_challenge(&mut self, round_message: &[F]) -> F {
+        self.append(&fq_vec_to_bytes(round_message));
+
+        self.get_uniform_challenge()
+    }
+}
+
+impl<F: PrimeField, C: ChallengeSource<F> + ?Sized> ChallengeSource<F> for &mut C {
+    fn next_challenge(&mut self, round_message: &[F]) -> F {
+        (**self).next_challenge(round_message)
+    }
+}
+
+/// Draws challenges from an RNG, ignoring the round messages.
+pub struct RngChallenges<R: RngCore>(pub R);
+
+impl<F: PrimeField, R: RngCore> ChallengeSource<F> for RngChallenges<R> {
+    fn next_challenge(&mut self, _round_message: &[F]) -> F {
+        F::rand(&mut self.0)
+    }
+}
+
+pub struct SumCheckProver<F: PrimeField> {
+    current_poly: MultilinearPoly<F>,
+    num_vars: usize,
+    // Filled from the first round message, which already sums both halves
+    // of the table, so a prover that is only run never sums it separately.
+    claimed_sum: OnceCell<F>,
+    binding_order: BindingOrder,
+}
+
+impl<F: PrimeField> SumCheckProver<F> {
+    pub fn new(polynomial: &MultilinearPoly<F>) -> Self {
+        Self::with_binding_order(polynomial, BindingOrder::MostSignificantFirst)
+    }
+
+    pub fn with_binding_order(
+        polynomial: &MultilinearPoly<F>,
+        binding_order: BindingOrder,
+    ) -> Self {
+        Self {
+            current_poly: polynomial.clone(),
+            num_vars: polynomial.num_vars(),
+            claimed_sum: OnceCell::new(),
+            binding_order,
+        }
+    }
+
+    pub fn claimed_sum(&self) -> F {
+        *self
+            .claimed_sum
+            .get_or_init(|| self.current_poly.hypercube_sum())
+    }
+
+    /// The sum of the remaining table, which the next round message must
+    /// split across its evaluations at `0` and `1`.
+    pub fn current_sum(&self) -> F {
+        self.current_poly.hypercube_sum()
+    }
+
+    pub fn rounds_remaining(&self) -> usize {
+        self.current_poly.num_vars()
+    }
+
+    pub fn round_message(&self) -> Vec<F> {
+        if self.rounds_remaining() == 0 {
+            panic!("all rounds have already been run");
+        }
+
+        let poly_proof = match self.binding_order {
+            BindingOrder::MostSignificantFirst => {
+                get_round_partial_polynomial_proof(self.current_poly.evaluations())
+            }
+            BindingOrder::LeastSignificantFirst => {
+                // The last variable is the lowest index bit, so its zero and
+                // one halves are the even and odd entries.
+                let mut poly_proof = vec![F::zero(); 2];
+
+                for pair in self.current_poly.evaluations().chunks(2) {
+                    poly_proof[0] += pair[0];
+                    poly_proof[1] += pair[1];
+                }
+
+                poly_proof
+            }
+        };
+
+        if self.rounds_remaining() == self.num_vars {
+            let _ = self.claimed_sum.set(poly_proof[0] + poly_proof[1]);
+        }
+
+        poly_proof
+    }
+
+    pub fn receive_challenge(&mut self, challenge: F) {
+        if self.rounds_remaining() == 0 {
+            panic!("all rounds have already been run");
+        }
+
+        // The table is about to lose the claimed sum, so keep it if no
+        // round message has.
+        if self.rounds_remaining() == self.num_vars {
+            self.claimed_sum();
+        }
+
+        let bit = match self.binding_order {
+            BindingOrder::MostSignificantFirst => 0,
+            BindingOrder::LeastSignificantFirst => self.current_poly.num_vars() - 1,
+        };
+
+        self.current_poly
+            .partial_evaluate_in_place(bit, &challenge)
+            .expect("a round is left to bind");
+    }
+
+    /// The polynomial's value at the challenges received so far, once every
+    /// variable has been bound.
+    pub fn final_evaluation(&self) -> F {
+        if self.rounds_remaining() != 0 {
+            panic!("rounds are still remaining");
+        }
+
+        self.current_poly.evaluations()[0]
+    }
+}
+
+pub struct SumCheckVerifier<F: PrimeField, C: ChallengeSource<F>> {
+    num_vars: usize,
+    degree: usize,
+    expected_sum: F,
+    random_challenges: Vec<F>,
+    challenge_source: C,
+}
+
+impl<F: PrimeField, C: ChallengeSource<F>> SumCheckVerifier<F, C> {
+    pub fn new(claimed_sum: F, num_vars: usize, challenge_source: C) -> Self {
+        Self::with_degree(claimed_sum, num_vars, 1, challenge_source)
+    }
+
+    /// A verifier for round polynomials of degree at most `degree`, each sent
+    /// as its evaluations at `0, 1, ..., degree`.
+    pub fn with_degree(
+        claimed_sum: F,
+        num_vars: usize,
+        degree: usize,
+        challenge_source: C,
+    ) -> Self {
+        Self {
+            num_vars,
+            degree,
+            expected_sum: claimed_sum,
+            random_challenges: Vec::with_capacity(num_vars),
+            challenge_source,
+        }
+    }
+
+    /// The claim the next round message must sum to.
+    pub fn expected_sum(&self) -> F {
+        self.expected_sum
+    }
+
+    /// Checks one round message against the running claim and answers with
+    /// the challenge for the next round.
+    pub fn receive_message(&mut self, round_message: &[F]) -> Result<F, SumCheckError<F>> {
+        let round = self.random_challenges.len();
+
+        if round == self.num_vars {
+            return Err(SumCheckError::WrongNumberOfRounds {
+                expected: self.num_vars,
+                got: round + 1,
+            });
+        }
+
+        if round_message.len() != self.degree + 1 {
+            return Err(SumCheckError::InvalidRoundPolynomialLength {
+                round,
+                expected: self.degree + 1,
+                got: round_message.len(),
+            });
+        }
+
+        let round_sum = round_message[0] + round_message[1];
+        if round_sum != self.expected_sum {
+            return Err(SumCheckError::RoundSumMismatch {
+                round,
+                expected: self.expected_sum,
+                got: round_sum,
+            });
+        }
+
+        let challenge = self.challenge_source.next_challenge(round_message);
+
+        self.expected_sum = evaluate_round_message(round_message, challenge);
+        self.random_challenges.push(challenge);
+
+        Ok(challenge)
+    }
+
+    /// Ends the rounds without querying the polynomial, leaving the final
+    /// evaluation claim to the caller.
+    pub fn into_sub_claim(self) -> Result<EvaluationClaim<F>, SumCheckError<F>> {
+        if self.random_challenges.len() != self.num_vars {
+            return Err(SumCheckError::WrongNumberOfRounds {
+                expected: self.num_vars,
+                got: self.random_challenges.len(),
+            });
+        }
+
+        Ok(EvaluationClaim {
+            point: self.random_challenges,
+            value: self.expected_sum,
+        })
+    }
+
+    /// Ends the rounds and checks the polynomial's evaluation at the sampled
+    /// point against the final claim.
+    pub fn finalize(self, oracle_eval: F) -> Result<EvaluationClaim<F>, SumCheckError<F>> {
+        let sub_claim = self.into_sub_claim()?;
+
+        if sub_claim.value != oracle_eval {
+            return Err(SumCheckError::FinalEvaluationMismatch {
+                expected: sub_claim.value,
+                got: oracle_eval,
+            });
+        }
+
+        Ok(sub_claim)
+    }
+}
+
+/// Evaluates the round polynomial given by its values at `0, 1, ..., d` at `x`.
+pub(crate) fn evaluate_round_message<F: PrimeField>(round_message: &[F], x: F) -> F {
+    if round_message.len() == 2 {
+        return round_message[0] + x * (round_message[1] - round_message[0]);
+    }
+
+    BarycentricDomain::new(round_message.len() - 1).evaluate(round_message, x)
+}
+
+#[cfg(test)]
+mod test {
+    use ark_bn254::Fq;
+    use rand::{rngs::StdRng, SeedableRng};
+
+    use super::{RngChallenges, SumCheckProver, SumCheckVerifier};
+    use crate::{
+        sum_check_error::SumCheckError, sum_check_protocol::BindingOrder, test_utils::sample_poly,
+    };
+
+    #[test]
+    fn it_runs_the_interactive_protocol_with_a_seeded_rng() {
+        let polynomial = sample_poly();
+
+        let mut prover = SumCheckProver::new(&polynomial);
+        let mut verifier = SumCheckVerifier::new(
+            prover.claimed_sum(),
+            polynomial.num_vars(),
+            RngChallenges(StdRng::seed_from_u64(7)),
+        );
+
+        while prover.rounds_remaining() > 0 {
+            let message = prover.round_message();
+            let challenge = verifier.receive_message(&message).unwrap();
+            prover.receive_challenge(challenge);
+        }
+
+        let point = verifier.random_challenges.clone();
+        let sub_claim = verifier.finalize(polynomial.evaluate(&point)).unwrap();
+
+        assert_eq!(sub_claim.point.len(), 3);
+    }
+
+    #[test]
+    fn it_reverses_the_evaluation_point_when_binding_from_the_last_variable() {
+        let polynomial = sample_poly();
+
+        let points: Vec<_> = [
+            BindingOrder::MostSignificantFirst,
+            BindingOrder::LeastSignificantFirst,
+        ]
+        .into_iter()
+        .map(|order| {
+            let mut prover = SumCheckProver::with_binding_order(&polynomial, order);
+            let mut verifier = SumCheckVerifier::new(
+                prover.claimed_sum(),
+                polynomial.num_vars(),
+                RngChallenges(StdRng::seed_from_u64(7)),
+            );
+
+            while prover.rounds_remaining() > 0 {
+                let message = prover.round_message();
+                let challenge = verifier.receive_message(&message).unwrap();
+                prover.receive_challenge(challenge);
+            }
+
+            let point = order.evaluation_point(&verifier.random_challenges);
+            assert_eq!(prover.final_evaluation(), polynomial.evaluate(&point));
+
+            verifier.finalize(polynomial.evaluate(&point)).unwrap();
+
+            point
+        })
+        .collect();
+
+        let mut reversed = points[1].clone();
+        reversed.reverse();
+        assert_eq!(points[0], reversed);
+    }
+
+    #[test]
+    fn it_keeps_the_claimed_sum_once_the_table_is_bound() {
+        let polynomial = sample_poly();
+
+        for order in [
+            BindingOrder::MostSignificantFirst,
+            BindingOrder::LeastSignificantFirst,
+        ] {
+            let mut prover = SumCheckProver::with_binding_order(&polynomial, order);
+
+            while prover.rounds_remaining() > 0 {
+                prover.round_message();
+                prover.receive_challenge(Fq::from(5));
+            }
+
+            assert_eq!(prover.claimed_sum(), Fq::from(29));
+        }
+
+        // Binding without asking for a round message first.
+        let mut prover = SumCheckProver::new(&polynomial);
+        prover.receive_challenge(Fq::from(5));
+
+        assert_eq!(prover.claimed_sum(), Fq::from(29));
+    }
+
+    #[test]
+    fn it_rejects_a_dishonest_round_message() {
+        let polynomial = sample_poly();
+
+        let prover = SumCheckProver::new(&polynomial);
+        let mut verifier = SumCheckVerifier::new(
+            prover.claimed_sum(),
+            polynomial.num_vars(),
+            RngChallenges(StdRng::seed_from_u64(7)),
+        );
+
+        let mut message = prover.round_message();
+        message[0] += Fq::from(1);
+
+        assert!(matches!(
+            verifier.receive_message(&message),
+            Err(SumCheckError::RoundSumMismatch { round: 0, .. })
+        ));
+    }
+
+    #[test]
+    fn it_rejects_finalizing_before_all_rounds() {
+        let polynomial = sample_poly();
+
+        let prover = SumCheckProver::new(&polynomial);
+        let mut verifier = SumCheckVerifier::new(
+            prover.claimed_sum(),
+            polynomial.num_vars(),
+            RngChallenges(StdRng::seed_from_u64(7)),
+        );
+
+        verifier.receive_message(&prover.round_message()).unwrap();
+
+        assert_eq!(
+            verifier.finalize(Fq::from(0)),
+            Err(SumCheckError::WrongNumberOfRounds {
+                expected: 3,
+                got: 1
+            })
+        );
+    }
+}
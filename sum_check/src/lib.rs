@@ -1 +1,17 @@
+pub mod batch_sum_check;
+pub mod extension_sum_check;
+pub mod interactive_sum_check;
+pub mod matmul_sum_check;
+pub mod multi_claim_sum_check;
+pub mod product_sum_check;
+pub mod proof_serialization;
+pub mod streaming_sum_check;
+pub mod subcube_sum_check;
+pub mod sum_check_error;
 pub mod sum_check_protocol;
+pub mod sum_check_trait;
+#[cfg(any(test, feature = "test-utils"))]
+pub mod test_utils;
+pub mod traced_sum_check;
+pub mod zero_check;
+pub mod zk_sum_check;
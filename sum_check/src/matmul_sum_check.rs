@@ -0,0 +1,225 @@
+use ark_ff::PrimeField;
+use fiat_shamir::fiat_shamir_transcript::Transcript;
+use multilinear_polynomial::multilinear_polynomial_evaluation::MultilinearPoly;
+
+use crate::{
+    product_sum_check::{
+        prove_product_with_transcript, verify_product_partial_with_transcript, ProductProof,
+    },
+    sum_check_error::SumCheckError,
+};
+
+/// Proof that `C = A·B` at a random point `(r_x, r_y)`, via a product
+/// sum-check over `Σ_j Ã(r_x, j)·B̃(j, r_y)`.
+#[derive(Debug, Clone)]
+pub struct MatmulProof<F: PrimeField> {
+    pub product_proof: ProductProof<F>,
+}
+
+/// The evaluation claims on `Ã` and `B̃` left at the end of a matmul proof.
+#[derive(Debug, Clone, PartialEq)]
+pub struct MatmulSubClaim<F: PrimeField> {
+    pub a_point: Vec<F>,
+    pub a_eval: F,
+    pub b_point: Vec<F>,
+    pub b_eval: F,
+}
+
+/// The row, inner and column variable counts of `A·B = C`, derived from the
+/// three encodings' variable counts.
+fn matmul_dimensions<F: PrimeField>(
+    a: &MultilinearPoly<F>,
+    b: &MultilinearPoly<F>,
+    c: &MultilinearPoly<F>,
+) -> Result<(usize, usize, usize), SumCheckError<F>> {
+    let (a_vars, b_vars, c_vars) = (a.num_vars(), b.num_vars(), c.num_vars());
+
+    if (a_vars + c_vars) < b_vars
+        || (a_vars + b_vars) < c_vars
+        || (b_vars + c_vars) < a_vars
+        || (a_vars + b_vars + c_vars) % 2 != 0
+    {
+        return Err(SumCheckError::InconsistentMatrixDimensions {
+            a_vars,
+            b_vars,
+            c_vars,
+        });
+    }
+
+    let row_vars = (a_vars + c_vars - b_vars) / 2;
+    let inner_vars = (a_vars + b_vars - c_vars) / 2;
+    let col_vars = (b_vars + c_vars - a_vars) / 2;
+
+    Ok((row_vars, inner_vars, col_vars))
+}
+
+fn sample_output_point<F: PrimeField>(
+    a: &MultilinearPoly<F>,
+    b: &MultilinearPoly<F>,
+    c: &MultilinearPoly<F>,
+    transcript: &mut Transcript<F>,
+) -> Result<(Vec<F>, Vec<F>), SumCheckError<F>> {
+    let (row_vars, _, col_vars) = matmul_dimensions(a, b, c)?;
+
+    transcript.append(&a.digest());
+    transcript.append(&b.digest());
+    transcript.append(&c.digest());
+
+    let r_x = (0..row_vars)
+        .map(|_| transcript.get_uniform_challenge())
+        .collect();
+    let r_y = (0..col_vars)
+        .map(|_| transcript.get_uniform_challenge())
+        .collect();
+
+    Ok((r_x, r_y))
+}
+
+/// Fails if the variable counts of `a`, `b` and `c` fit no matrix product.
+pub fn prove_matmul<F: PrimeField>(
+    a: &MultilinearPoly<F>,
+    b: &MultilinearPoly<F>,
+    c: &MultilinearPoly<F>,
+) -> Result<MatmulProof<F>, SumCheckError<F>> {
+    let mut transcript = Transcript::<F>::new();
+    let (r_x, r_y) = sample_output_point(a, b, c, &mut transcript)?;
+
+    let a_row = a.fix_prefix(&r_x);
+    let b_col = b.fix_suffix(&r_y);
+
+    Ok(MatmulProof {
+        product_proof: prove_product_with_transcript(&a_row, &b_col, &mut transcript)
+            .expect("a row of A and a column of B both range over the inner index"),
+    })
+}
+
+pub fn verify_matmul<F: PrimeField>(
+    a: &MultilinearPoly<F>,
+    b: &MultilinearPoly<F>,
+    c: &MultilinearPoly<F>,
+    proof: MatmulProof<F>,
+) -> Result<MatmulSubClaim<F>, SumCheckError<F>> {
+    let (_, inner_vars, _) = matmul_dimensions(a, b, c)?;
+
+    let mut transcript = Transcript::<F>::new();
+    let (r_x, r_y) = sample_output_point(a, b, c, &mut transcript)?;
+
+    let c_eval = c.evaluate(&[r_x.clone(), r_y.clone()].concat());
+    if proof.product_proof.claimed_sum != c_eval {
+        return Err(SumCheckError::ClaimMismatch {
+            expected: c_eval,
+            got: proof.product_proof.claimed_sum,
+        });
+    }
+
+    let product_claim =
+        verify_product_partial_with_transcript(inner_vars, proof.product_proof, &mut transcript)?;
+
+    let sub_claim = MatmulSubClaim {
+        a_point: [r_x, product_claim.point.clone()].concat(),
+        a_eval: product_claim.f_eval,
+        b_point: [product_claim.point, r_y].concat(),
+        b_eval: product_claim.g_eval,
+    };
+
+    for (poly, point, claimed_eval) in [
+        (a, &sub_claim.a_point, sub_claim.a_eval),
+        (b, &sub_claim.b_point, sub_claim.b_eval),
+    ] {
+        let eval = poly.evaluate(point);
+
+        if eval != claimed_eval {
+            return Err(SumCheckError::FinalEvaluationMismatch {
+                expected: claimed_eval,
+                got: eval,
+            });
+        }
+    }
+
+    Ok(sub_claim)
+}
+
+#[cfg(test)]
+mod test {
+    use ark_bn254::Fq;
+    use multilinear_polynomial::multilinear_polynomial_evaluation::MultilinearPoly;
+
+    use super::{prove_matmul, verify_matmul};
+    use crate::sum_check_error::SumCheckError;
+
+    fn matrix(entries: [[u64; 4]; 4]) -> Vec<Vec<Fq>> {
+        entries
+            .iter()
+            .map(|row| row.iter().map(|entry| Fq::from(*entry)).collect())
+            .collect()
+    }
+
+    fn naive_matmul(a: &[Vec<Fq>], b: &[Vec<Fq>]) -> Vec<Vec<Fq>> {
+        (0..a.len())
+            .map(|i| {
+                (0..b[0].len())
+                    .map(|j| (0..b.len()).map(|k| a[i][k] * b[k][j]).sum())
+                    .collect()
+            })
+            .collect()
+    }
+
+    fn sample_matrices() -> (Vec<Vec<Fq>>, Vec<Vec<Fq>>) {
+        let a = matrix([[1, 2, 0, 3], [4, 0, 1, 1], [2, 2, 2, 2], [0, 5, 1, 0]]);
+        let b = matrix([[3, 1, 0, 2], [0, 1, 4, 1], [7, 0, 1, 0], [1, 1, 1, 1]]);
+
+        (a, b)
+    }
+
+    #[test]
+    fn it_proves_a_correct_product() {
+        let (a, b) = sample_matrices();
+        let c = naive_matmul(&a, &b);
+
+        let a = MultilinearPoly::from_matrix(&a).unwrap();
+        let b = MultilinearPoly::from_matrix(&b).unwrap();
+        let c = MultilinearPoly::from_matrix(&c).unwrap();
+
+        let proof = prove_matmul(&a, &b, &c).unwrap();
+        let sub_claim = verify_matmul(&a, &b, &c, proof).unwrap();
+
+        assert_eq!(a.evaluate(&sub_claim.a_point), sub_claim.a_eval);
+        assert_eq!(b.evaluate(&sub_claim.b_point), sub_claim.b_eval);
+    }
+
+    #[test]
+    fn it_rejects_a_wrong_product_entry() {
+        let (a, b) = sample_matrices();
+        let mut c = naive_matmul(&a, &b);
+        c[2][1] += Fq::from(1);
+
+        let a = MultilinearPoly::from_matrix(&a).unwrap();
+        let b = MultilinearPoly::from_matrix(&b).unwrap();
+        let c = MultilinearPoly::from_matrix(&c).unwrap();
+
+        let proof = prove_matmul(&a, &b, &c).unwrap();
+
+        assert!(verify_matmul(&a, &b, &c, proof).is_err());
+    }
+
+    #[test]
+    fn it_rejects_inconsistent_dimensions() {
+        let (a, b) = sample_matrices();
+        let c = naive_matmul(&a, &b);
+
+        let a = MultilinearPoly::from_matrix(&a).unwrap();
+        let b = MultilinearPoly::from_matrix(&b).unwrap();
+        let c = MultilinearPoly::from_matrix(&c).unwrap();
+        let proof = prove_matmul(&a, &b, &c).unwrap();
+
+        let wide_c = MultilinearPoly::new(vec![Fq::from(0); 32]);
+        let mismatch = SumCheckError::InconsistentMatrixDimensions {
+            a_vars: 4,
+            b_vars: 4,
+            c_vars: 5,
+        };
+
+        assert_eq!(prove_matmul(&a, &b, &wide_c).unwrap_err(), mismatch);
+        assert_eq!(verify_matmul(&a, &b, &wide_c, proof), Err(mismatch));
+    }
+}
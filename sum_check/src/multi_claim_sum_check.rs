@@ -0,0 +1,210 @@
+use ark_ff::PrimeField;
+use fiat_shamir::fiat_shamir_transcript::Transcript;
+use multilinear_polynomial::{
+    evaluation_claim::EvaluationClaim, multilinear_polynomial_evaluation::MultilinearPoly,
+};
+
+use crate::{
+    batch_sum_check::{append_batch_statement, combine, linear_combination},
+    product_sum_check::{
+        prove_product_with_transcript, verify_product_partial_with_transcript, ProductProof,
+    },
+    sum_check_error::SumCheckError,
+};
+
+/// Proves the weighted sums `Σ f(x)·wᵢ(x)` for several public weights at once,
+/// as a single product sum-check over `f·Σ αⁱ·wᵢ`.
+#[derive(Debug, Clone)]
+pub struct MultiClaimProof<F: PrimeField> {
+    pub claimed_sums: Vec<F>,
+    pub product_proof: ProductProof<F>,
+}
+
+fn append_multi_claim_statement<F: PrimeField>(
+    f: &MultilinearPoly<F>,
+    weights: &[MultilinearPoly<F>],
+    claimed_sums: &[F],
+    transcript: &mut Transcript<F>,
+) -> F {
+    transcript.append(&f.digest());
+
+    for weight in weights {
+        transcript.append(&weight.digest());
+    }
+
+    append_batch_statement(transcript, f.num_vars(), claimed_sums)
+}
+
+fn check_weights<F: PrimeField>(
+    f: &MultilinearPoly<F>,
+    weights: &[MultilinearPoly<F>],
+) -> Result<(), SumCheckError<F>> {
+    if weights.is_empty() {
+        return Err(SumCheckError::EmptyBatch);
+    }
+
+    match weights
+        .iter()
+        .position(|weight| weight.num_vars() != f.num_vars())
+    {
+        Some(index) => Err(SumCheckError::MismatchedNumVars {
+            index,
+            expected: f.num_vars(),
+            got: weights[index].num_vars(),
+        }),
+        None => Ok(()),
+    }
+}
+
+/// Fails if there are no weights or one of them does not share the
+/// variables of `f`.
+pub fn prove_multi_claim<F: PrimeField>(
+    f: &MultilinearPoly<F>,
+    weights: &[MultilinearPoly<F>],
+) -> Result<MultiClaimProof<F>, SumCheckError<F>> {
+    check_weights(f, weights)?;
+
+    let claimed_sums: Vec<F> = weights
+        .iter()
+        .map(|weight| (f.clone() * weight.clone()).hypercube_sum())
+        .collect();
+
+    let mut transcript = Transcript::<F>::new();
+    let alpha = append_multi_claim_statement(f, weights, &claimed_sums, &mut transcript);
+
+    let combined_weight = combine(weights, alpha);
+
+    Ok(MultiClaimProof {
+        claimed_sums,
+        product_proof: prove_product_with_transcript(f, &combined_weight, &mut transcript)?,
+    })
+}
+
+/// Verifies every weighted sum in the proof and returns the evaluation claim
+/// on `f` at the shared random point, after checking it against `f`.
+/// Fails on the same malformed weights as `prove_multi_claim`.
+pub fn verify_multi_claim<F: PrimeField>(
+    f: &MultilinearPoly<F>,
+    weights: &[MultilinearPoly<F>],
+    proof: MultiClaimProof<F>,
+) -> Result<EvaluationClaim<F>, SumCheckError<F>> {
+    check_weights(f, weights)?;
+
+    if proof.claimed_sums.len() != weights.len() {
+        return Err(SumCheckError::WrongNumberOfClaims {
+            expected: weights.len(),
+            got: proof.claimed_sums.len(),
+        });
+    }
+
+    let mut transcript = Transcript::<F>::new();
+    let alpha = append_multi_claim_statement(f, weights, &proof.claimed_sums, &mut transcript);
+
+    let combined_claim = linear_combination(&proof.claimed_sums, alpha);
+    if proof.product_proof.claimed_sum != combined_claim {
+        return Err(SumCheckError::ClaimMismatch {
+            expected: combined_claim,
+            got: proof.product_proof.claimed_sum,
+        });
+    }
+
+    let sub_claim =
+        verify_product_partial_with_transcript(f.num_vars(), proof.product_proof, &mut transcript)?;
+
+    let weight_evals: Vec<F> = weights
+        .iter()
+        .map(|weight| weight.evaluate(&sub_claim.point))
+        .collect();
+
+    for (expected, got) in [
+        (f.evaluate(&sub_claim.point), sub_claim.f_eval),
+        (linear_combination(&weight_evals, alpha), sub_claim.g_eval),
+    ] {
+        if expected != got {
+            return Err(SumCheckError::FinalEvaluationMismatch { expected, got });
+        }
+    }
+
+    Ok(EvaluationClaim {
+        point: sub_claim.point,
+        value: sub_claim.f_eval,
+    })
+}
+
+#[cfg(test)]
+mod test {
+    use ark_bn254::Fq;
+    use multilinear_polynomial::multilinear_polynomial_evaluation::MultilinearPoly;
+
+    use super::{prove_multi_claim, verify_multi_claim};
+    use crate::{sum_check_error::SumCheckError, test_utils::sample_poly};
+
+    fn sample_instance() -> (MultilinearPoly<Fq>, Vec<MultilinearPoly<Fq>>) {
+        let f = sample_poly();
+        let z = [Fq::from(3), Fq::from(5), Fq::from(7)];
+        let weights = vec![
+            MultilinearPoly::new(vec![Fq::from(1); 8]),
+            MultilinearPoly::eq_mle(&z),
+        ];
+
+        (f, weights)
+    }
+
+    #[test]
+    fn it_proves_a_plain_and_a_weighted_sum_together() {
+        let (f, weights) = sample_instance();
+        let z = vec![Fq::from(3), Fq::from(5), Fq::from(7)];
+
+        let proof = prove_multi_claim(&f, &weights).unwrap();
+
+        assert_eq!(proof.claimed_sums, vec![Fq::from(29), f.evaluate(&z)]);
+        assert!(proof
+            .product_proof
+            .proof_polynomials
+            .iter()
+            .all(|poly| poly.len() == 3));
+
+        let sub_claim = verify_multi_claim(&f, &weights, proof).unwrap();
+        assert_eq!(f.evaluate(&sub_claim.point), sub_claim.value);
+    }
+
+    #[test]
+    fn it_rejects_a_corrupted_individual_claim() {
+        let (f, weights) = sample_instance();
+
+        let mut proof = prove_multi_claim(&f, &weights).unwrap();
+        proof.claimed_sums[0] += Fq::from(1);
+
+        assert!(verify_multi_claim(&f, &weights, proof).is_err());
+    }
+
+    #[test]
+    fn it_rejects_an_empty_weight_list() {
+        let (f, weights) = sample_instance();
+        let proof = prove_multi_claim(&f, &weights).unwrap();
+
+        assert_eq!(
+            prove_multi_claim(&f, &[]).unwrap_err(),
+            SumCheckError::EmptyBatch
+        );
+        assert_eq!(
+            verify_multi_claim(&f, &[], proof),
+            Err(SumCheckError::EmptyBatch)
+        );
+    }
+
+    #[test]
+    fn it_rejects_a_weight_over_other_variables() {
+        let (f, mut weights) = sample_instance();
+        let proof = prove_multi_claim(&f, &weights).unwrap();
+        weights.push(MultilinearPoly::new(vec![Fq::from(1); 4]));
+        let mismatch = SumCheckError::MismatchedNumVars {
+            index: 2,
+            expected: 3,
+            got: 2,
+        };
+
+        assert_eq!(prove_multi_claim(&f, &weights).unwrap_err(), mismatch);
+        assert_eq!(verify_multi_claim(&f, &weights, proof), Err(mismatch));
+    }
+}
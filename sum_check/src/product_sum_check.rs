@@ -0,0 +1,356 @@
+use ark_ff::PrimeField;
+use fiat_shamir::fiat_shamir_transcript::{fq_vec_to_bytes, Transcript};
+use multilinear_polynomial::multilinear_polynomial_evaluation::MultilinearPoly;
+
+use crate::{
+    interactive_sum_check::{ChallengeSource, SumCheckVerifier},
+    sum_check_error::SumCheckError,
+};
+
+/// Sum-check for `Σ f(x)·g(x)`. Each round polynomial is degree 2 and is sent
+/// as its evaluations at 0, 1 and 2.
+#[derive(Debug, Clone)]
+pub struct ProductProof<F: PrimeField> {
+    pub proof_polynomials: Vec<Vec<F>>,
+    pub claimed_sum: F,
+    pub f_evaluation: F,
+    pub g_evaluation: F,
+}
+
+/// The claims left once the rounds of a product sum-check are checked: `f`
+/// and `g` evaluate to `f_eval` and `g_eval` at `point`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ProductSubClaim<F: PrimeField> {
+    pub point: Vec<F>,
+    pub f_eval: F,
+    pub g_eval: F,
+}
+
+/// Binds the transcript to the digests of `f` and `g` before the first
+/// round. Fails unless both have the same number of variables.
+pub fn prove_product<F: PrimeField>(
+    f: &MultilinearPoly<F>,
+    g: &MultilinearPoly<F>,
+) -> Result<ProductProof<F>, SumCheckError<F>> {
+    let mut transcript = Transcript::<F>::new();
+    append_factor_digests(&mut transcript, &f.digest(), &g.digest());
+
+    prove_product_with_transcript(f, g, &mut transcript)
+}
+
+/// The prover side of `verify_product_partial_with_transcript`, leaving the
+/// transcript's binding to `f` and `g` to the caller. The final factor
+/// evaluations are absorbed after the last round.
+pub(crate) fn prove_product_with_transcript<F: PrimeField>(
+    f: &MultilinearPoly<F>,
+    g: &MultilinearPoly<F>,
+    transcript: &mut Transcript<F>,
+) -> Result<ProductProof<F>, SumCheckError<F>> {
+    check_factor_vars(f, g)?;
+
+    let claimed_sum = f
+        .evaluations()
+        .iter()
+        .zip(g.evaluations())
+        .map(|(a, b)| *a * b)
+        .sum();
+    append_product_statement(transcript, f.num_vars(), claimed_sum);
+
+    let num_rounds = f.num_vars();
+    let mut proof_polynomials = Vec::with_capacity(num_rounds);
+    let mut current_f = f.clone();
+    let mut current_g = g.clone();
+
+    for _ in 0..num_rounds {
+        let proof_poly =
+            get_product_round_polynomial(current_f.evaluations(), current_g.evaluations());
+
+        let random_challenge = transcript.next_challenge(&proof_poly);
+
+        proof_polynomials.push(proof_poly);
+
+        current_f
+            .partial_evaluate_in_place(0, &random_challenge)
+            .expect("a round is left to bind");
+        current_g
+            .partial_evaluate_in_place(0, &random_challenge)
+            .expect("a round is left to bind");
+    }
+
+    let (f_evaluation, g_evaluation) = (current_f.evaluations()[0], current_g.evaluations()[0]);
+    transcript.append(&fq_vec_to_bytes(&[f_evaluation, g_evaluation]));
+
+    Ok(ProductProof {
+        proof_polynomials,
+        claimed_sum,
+        f_evaluation,
+        g_evaluation,
+    })
+}
+
+/// Evaluations at 0, 1 and 2 of `s(t) = Σ f(t, x)·g(t, x)`.
+fn get_product_round_polynomial<F: PrimeField>(f: &[F], g: &[F]) -> Vec<F> {
+    let mid_point = f.len() / 2;
+    let (f_zeros, f_ones) = f.split_at(mid_point);
+    let (g_zeros, g_ones) = g.split_at(mid_point);
+
+    let mut poly_proof = vec![F::zero(); 3];
+
+    for i in 0..mid_point {
+        let f_two = f_ones[i].double() - f_zeros[i];
+        let g_two = g_ones[i].double() - g_zeros[i];
+
+        poly_proof[0] += f_zeros[i] * g_zeros[i];
+        poly_proof[1] += f_ones[i] * g_ones[i];
+        poly_proof[2] += f_two * g_two;
+    }
+
+    poly_proof
+}
+
+pub fn verify_product<F: PrimeField>(
+    f: &MultilinearPoly<F>,
+    g: &MultilinearPoly<F>,
+    proof: ProductProof<F>,
+) -> Result<ProductSubClaim<F>, SumCheckError<F>> {
+    check_factor_vars(f, g)?;
+
+    let sub_claim = verify_product_partial(&f.digest(), &g.digest(), f.num_vars(), proof)?;
+
+    for (poly, claimed_eval) in [(f, sub_claim.f_eval), (g, sub_claim.g_eval)] {
+        let eval = poly.evaluate(&sub_claim.point);
+
+        if eval != claimed_eval {
+            return Err(SumCheckError::FinalEvaluationMismatch {
+                expected: claimed_eval,
+                got: eval,
+            });
+        }
+    }
+
+    Ok(sub_claim)
+}
+
+/// Checks the rounds and that the claimed factor evaluations multiply to the
+/// final claim, without querying `f` or `g`; only their digests, which
+/// `prove_product` binds the transcript to.
+pub fn verify_product_partial<F: PrimeField>(
+    f_digest: &[u8; 32],
+    g_digest: &[u8; 32],
+    num_vars: usize,
+    proof: ProductProof<F>,
+) -> Result<ProductSubClaim<F>, SumCheckError<F>> {
+    let mut transcript = Transcript::<F>::new();
+    append_factor_digests(&mut transcript, f_digest, g_digest);
+
+    verify_product_partial_with_transcript(num_vars, proof, &mut transcript)
+}
+
+pub(crate) fn verify_product_partial_with_transcript<F: PrimeField>(
+    num_vars: usize,
+    proof: ProductProof<F>,
+    transcript: &mut Transcript<F>,
+) -> Result<ProductSubClaim<F>, SumCheckError<F>> {
+    append_product_statement(transcript, num_vars, proof.claimed_sum);
+
+    if proof.proof_polynomials.len() != num_vars {
+        return Err(SumCheckError::WrongNumberOfRounds {
+            expected: num_vars,
+            got: proof.proof_polynomials.len(),
+        });
+    }
+
+    let mut verifier =
+        SumCheckVerifier::with_degree(proof.claimed_sum, num_vars, 2, &mut *transcript);
+
+    for poly in proof.proof_polynomials.iter() {
+        verifier.receive_message(poly)?;
+    }
+
+    let sub_claim = verifier.finalize(proof.f_evaluation * proof.g_evaluation)?;
+    transcript.append(&fq_vec_to_bytes(&[proof.f_evaluation, proof.g_evaluation]));
+
+    Ok(ProductSubClaim {
+        point: sub_claim.point,
+        f_eval: proof.f_evaluation,
+        g_eval: proof.g_evaluation,
+    })
+}
+
+fn check_factor_vars<F: PrimeField>(
+    f: &MultilinearPoly<F>,
+    g: &MultilinearPoly<F>,
+) -> Result<(), SumCheckError<F>> {
+    if f.num_vars() != g.num_vars() {
+        return Err(SumCheckError::MismatchedNumVars {
+            index: 1,
+            expected: f.num_vars(),
+            got: g.num_vars(),
+        });
+    }
+
+    Ok(())
+}
+
+fn append_factor_digests<F: PrimeField>(
+    transcript: &mut Transcript<F>,
+    f_digest: &[u8; 32],
+    g_digest: &[u8; 32],
+) {
+    transcript.append_with_label(b"product_factors", &[*f_digest, *g_digest].concat());
+}
+
+fn append_product_statement<F: PrimeField>(
+    transcript: &mut Transcript<F>,
+    num_vars: usize,
+    claimed_sum: F,
+) {
+    transcript.append(&fq_vec_to_bytes(&[F::from(num_vars as u64), claimed_sum]));
+}
+
+#[cfg(test)]
+mod test {
+    use ark_bn254::Fq;
+    use multilinear_polynomial::multilinear_polynomial_evaluation::MultilinearPoly;
+
+    use super::{prove_product, verify_product};
+    use crate::sum_check_error::SumCheckError;
+
+    fn sample_polys() -> (MultilinearPoly<Fq>, MultilinearPoly<Fq>) {
+        let f = MultilinearPoly::new(vec![
+            Fq::from(1),
+            Fq::from(2),
+            Fq::from(3),
+            Fq::from(4),
+            Fq::from(5),
+            Fq::from(6),
+            Fq::from(7),
+            Fq::from(8),
+        ]);
+        let g = MultilinearPoly::new(vec![
+            Fq::from(8),
+            Fq::from(0),
+            Fq::from(3),
+            Fq::from(1),
+            Fq::from(0),
+            Fq::from(2),
+            Fq::from(9),
+            Fq::from(4),
+        ]);
+
+        (f, g)
+    }
+
+    #[test]
+    fn it_proves_the_hypercube_sum_of_a_product() {
+        let (f, g) = sample_polys();
+
+        let brute_force_sum: Fq = f
+            .evaluations()
+            .iter()
+            .zip(g.evaluations().iter())
+            .map(|(a, b)| *a * b)
+            .sum();
+
+        let proof = prove_product(&f, &g).unwrap();
+
+        assert_eq!(proof.claimed_sum, brute_force_sum);
+        assert_eq!(proof.claimed_sum, Fq::from(128));
+        assert!(proof.proof_polynomials.iter().all(|poly| poly.len() == 3));
+
+        let sub_claim = verify_product(&f, &g, proof).unwrap();
+
+        assert_eq!(f.evaluate(&sub_claim.point), sub_claim.f_eval);
+        assert_eq!(g.evaluate(&sub_claim.point), sub_claim.g_eval);
+    }
+
+    #[test]
+    fn it_rejects_a_wrong_claimed_sum() {
+        let (f, g) = sample_polys();
+
+        let mut proof = prove_product(&f, &g).unwrap();
+        proof.claimed_sum += Fq::from(1);
+
+        assert!(matches!(
+            verify_product(&f, &g, proof),
+            Err(SumCheckError::RoundSumMismatch { round: 0, .. })
+        ));
+    }
+
+    #[test]
+    fn it_rejects_a_wrong_factor_evaluation() {
+        let (f, g) = sample_polys();
+
+        let mut proof = prove_product(&f, &g).unwrap();
+        proof.g_evaluation += Fq::from(1);
+
+        assert!(matches!(
+            verify_product(&f, &g, proof),
+            Err(SumCheckError::FinalEvaluationMismatch { .. })
+        ));
+    }
+
+    #[test]
+    fn it_rejects_cubic_round_messages() {
+        let (f, g) = sample_polys();
+
+        let mut proof = prove_product(&f, &g).unwrap();
+        proof.proof_polynomials[0].push(Fq::from(0));
+
+        assert_eq!(
+            verify_product(&f, &g, proof),
+            Err(SumCheckError::InvalidRoundPolynomialLength {
+                round: 0,
+                expected: 3,
+                got: 4
+            })
+        );
+    }
+
+    #[test]
+    fn it_rejects_linear_round_messages() {
+        let (f, g) = sample_polys();
+
+        let mut proof = prove_product(&f, &g).unwrap();
+        proof.proof_polynomials[1].pop();
+
+        assert_eq!(
+            verify_product(&f, &g, proof),
+            Err(SumCheckError::InvalidRoundPolynomialLength {
+                round: 1,
+                expected: 3,
+                got: 2
+            })
+        );
+    }
+
+    #[test]
+    fn it_binds_the_challenges_to_both_factors() {
+        let (f, g) = sample_polys();
+
+        let proof = prove_product(&f, &g).unwrap();
+        let swapped = prove_product(&g, &f).unwrap();
+
+        // The first round is symmetric in f and g, the challenge after it is
+        // not.
+        assert_eq!(proof.proof_polynomials[0], swapped.proof_polynomials[0]);
+        assert_ne!(proof.proof_polynomials[1], swapped.proof_polynomials[1]);
+        assert!(verify_product(&g, &f, proof).is_err());
+    }
+
+    #[test]
+    fn it_rejects_factors_of_different_sizes() {
+        let (f, g) = sample_polys();
+        let short = MultilinearPoly::new(vec![Fq::from(1), Fq::from(2)]);
+        let mismatch = SumCheckError::MismatchedNumVars {
+            index: 1,
+            expected: 3,
+            got: 1,
+        };
+
+        assert_eq!(prove_product(&f, &short).unwrap_err(), mismatch);
+
+        let proof = prove_product(&f, &g).unwrap();
+        assert_eq!(verify_product(&f, &short, proof), Err(mismatch));
+    }
+}
@@ -0,0 +1,252 @@
+use ark_ff::PrimeField;
+use ark_serialize::{
+    CanonicalDeserialize, CanonicalSerialize, Compress, Read, SerializationError, Valid, Validate,
+    Write,
+};
+
+use crate::sum_check_protocol::{GkrProof, Proof};
+
+/// Reads a `Vec` written as its `u64` length followed by its elements, the
+/// format `CanonicalSerialize` uses. The length is never trusted for
+/// allocation, so a corrupted prefix runs out of input instead of attempting
+/// a huge allocation.
+pub fn deserialize_vec<T: CanonicalDeserialize, R: Read>(
+    mut reader: R,
+    compress: Compress,
+    validate: Validate,
+) -> Result<Vec<T>, SerializationError> {
+    let len = u64::deserialize_with_mode(&mut reader, compress, validate)?;
+    let mut values = Vec::new();
+
+    for _ in 0..len {
+        values.push(T::deserialize_with_mode(&mut reader, compress, validate)?);
+    }
+
+    Ok(values)
+}
+
+/// Like `deserialize_vec`, with each inner vector length-prefixed in turn.
+pub fn deserialize_nested_vec<T: CanonicalDeserialize, R: Read>(
+    mut reader: R,
+    compress: Compress,
+    validate: Validate,
+) -> Result<Vec<Vec<T>>, SerializationError> {
+    let len = u64::deserialize_with_mode(&mut reader, compress, validate)?;
+    let mut values = Vec::new();
+
+    for _ in 0..len {
+        values.push(deserialize_vec(&mut reader, compress, validate)?);
+    }
+
+    Ok(values)
+}
+
+impl<F: PrimeField> CanonicalSerialize for Proof<F> {
+    fn serialize_with_mode<W: Write>(
+        &self,
+        mut writer: W,
+        compress: Compress,
+    ) -> Result<(), SerializationError> {
+        self.proof_polynomials
+            .serialize_with_mode(&mut writer, compress)?;
+        self.claimed_sum
+            .serialize_with_mode(&mut writer, compress)?;
+        self.final_evaluation
+            .serialize_with_mode(&mut writer, compress)
+    }
+
+    fn serialized_size(&self, compress: Compress) -> usize {
+        self.proof_polynomials.serialized_size(compress)
+            + self.claimed_sum.serialized_size(compress)
+            + self.final_evaluation.serialized_size(compress)
+    }
+}
+
+impl<F: PrimeField> Valid for Proof<F> {
+    /// Applies the same shape check as `Proof::new`.
+    fn check(&self) -> Result<(), SerializationError> {
+        if self.proof_polynomials.iter().any(|poly| poly.len() != 2) {
+            return Err(SerializationError::InvalidData);
+        }
+
+        Ok(())
+    }
+}
+
+impl<F: PrimeField> CanonicalDeserialize for Proof<F> {
+    fn deserialize_with_mode<R: Read>(
+        mut reader: R,
+        compress: Compress,
+        validate: Validate,
+    ) -> Result<Self, SerializationError> {
+        let proof = Proof {
+            proof_polynomials: deserialize_nested_vec(&mut reader, compress, validate)?,
+            claimed_sum: F::deserialize_with_mode(&mut reader, compress, validate)?,
+            final_evaluation: F::deserialize_with_mode(&mut reader, compress, validate)?,
+        };
+
+        if let Validate::Yes = validate {
+            proof.check()?;
+        }
+
+        Ok(proof)
+    }
+}
+
+impl<F: PrimeField> CanonicalSerialize for GkrProof<F> {
+    fn serialize_with_mode<W: Write>(
+        &self,
+        mut writer: W,
+        compress: Compress,
+    ) -> Result<(), SerializationError> {
+        self.proof_polynomials
+            .serialize_with_mode(&mut writer, compress)?;
+        self.claimed_sum
+            .serialize_with_mode(&mut writer, compress)?;
+        self.random_challenges
+            .serialize_with_mode(&mut writer, compress)?;
+        self.w_b.serialize_with_mode(&mut writer, compress)?;
+        self.w_c.serialize_with_mode(&mut writer, compress)
+    }
+
+    fn serialized_size(&self, compress: Compress) -> usize {
+        self.proof_polynomials.serialized_size(compress)
+            + self.claimed_sum.serialized_size(compress)
+            + self.random_challenges.serialized_size(compress)
+            + self.w_b.serialized_size(compress)
+            + self.w_c.serialized_size(compress)
+    }
+}
+
+impl<F: PrimeField> Valid for GkrProof<F> {
+    fn check(&self) -> Result<(), SerializationError> {
+        Ok(())
+    }
+}
+
+impl<F: PrimeField> CanonicalDeserialize for GkrProof<F> {
+    fn deserialize_with_mode<R: Read>(
+        mut reader: R,
+        compress: Compress,
+        validate: Validate,
+    ) -> Result<Self, SerializationError> {
+        Ok(GkrProof {
+            proof_polynomials: deserialize_nested_vec(&mut reader, compress, validate)?,
+            claimed_sum: F::deserialize_with_mode(&mut reader, compress, validate)?,
+            random_challenges: deserialize_vec(&mut reader, compress, validate)?,
+            w_b: F::deserialize_with_mode(&mut reader, compress, validate)?,
+            w_c: F::deserialize_with_mode(&mut reader, compress, validate)?,
+        })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use ark_bn254::Fq;
+    use ark_serialize::{CanonicalDeserialize, CanonicalSerialize, SerializationError};
+    use multilinear_polynomial::evaluation_claim::EvaluationClaim;
+
+    use crate::{
+        sum_check_protocol::{prove, GkrProof, Proof},
+        test_utils::sample_poly,
+    };
+
+    fn sample_proof() -> Proof<Fq> {
+        prove(&sample_poly())
+    }
+
+    fn sample_gkr_proof() -> GkrProof<Fq> {
+        GkrProof {
+            proof_polynomials: vec![
+                vec![Fq::from(1), Fq::from(2), Fq::from(3)],
+                vec![Fq::from(4), Fq::from(5)],
+            ],
+            claimed_sum: Fq::from(7),
+            random_challenges: vec![Fq::from(8), Fq::from(9)],
+            w_b: Fq::from(10),
+            w_c: Fq::from(11),
+        }
+    }
+
+    #[test]
+    fn it_round_trips_a_proof() {
+        let proof = sample_proof();
+
+        let mut compressed = Vec::new();
+        proof.serialize_compressed(&mut compressed).unwrap();
+        assert_eq!(compressed.len(), proof.compressed_size());
+        assert_eq!(
+            Proof::<Fq>::deserialize_compressed(compressed.as_slice()).unwrap(),
+            proof
+        );
+
+        let mut uncompressed = Vec::new();
+        proof.serialize_uncompressed(&mut uncompressed).unwrap();
+        assert_eq!(
+            Proof::<Fq>::deserialize_uncompressed(uncompressed.as_slice()).unwrap(),
+            proof
+        );
+    }
+
+    #[test]
+    fn it_round_trips_gkr_proofs_and_results() {
+        let proof = sample_gkr_proof();
+        let mut bytes = Vec::new();
+        proof.serialize_compressed(&mut bytes).unwrap();
+        assert_eq!(
+            GkrProof::deserialize_compressed(bytes.as_slice()).unwrap(),
+            proof
+        );
+
+        let result = EvaluationClaim::new(vec![Fq::from(4), Fq::from(5)], Fq::from(3));
+        let mut bytes = Vec::new();
+        result.serialize_uncompressed(&mut bytes).unwrap();
+        assert_eq!(
+            EvaluationClaim::deserialize_uncompressed(bytes.as_slice()).unwrap(),
+            result
+        );
+    }
+
+    #[test]
+    fn it_rejects_a_corrupted_length_prefix() {
+        let mut bytes = Vec::new();
+        sample_gkr_proof().serialize_compressed(&mut bytes).unwrap();
+
+        // The first inner vector's length follows the outer length.
+        bytes[8..16].copy_from_slice(&u64::MAX.to_le_bytes());
+
+        assert!(GkrProof::<Fq>::deserialize_compressed(bytes.as_slice()).is_err());
+
+        let mut bytes = Vec::new();
+        sample_proof().serialize_compressed(&mut bytes).unwrap();
+        bytes[0..8].copy_from_slice(&(1u64 << 40).to_le_bytes());
+
+        assert!(Proof::<Fq>::deserialize_compressed(bytes.as_slice()).is_err());
+    }
+
+    #[test]
+    fn it_rejects_a_stream_truncated_mid_element() {
+        let mut bytes = Vec::new();
+        sample_proof().serialize_compressed(&mut bytes).unwrap();
+        bytes.truncate(bytes.len() - 5);
+
+        assert!(Proof::<Fq>::deserialize_compressed(bytes.as_slice()).is_err());
+    }
+
+    #[test]
+    fn it_rejects_a_malformed_round_polynomial() {
+        let malformed = Proof {
+            proof_polynomials: vec![vec![Fq::from(1), Fq::from(2), Fq::from(3)]],
+            claimed_sum: Fq::from(3),
+            final_evaluation: Fq::from(3),
+        };
+
+        let mut bytes = Vec::new();
+        malformed.serialize_compressed(&mut bytes).unwrap();
+
+        assert!(matches!(
+            Proof::<Fq>::deserialize_compressed(bytes.as_slice()),
+            Err(SerializationError::InvalidData)
+        ));
+    }
+}
@@ -0,0 +1,203 @@
+use std::io;
+
+use ark_ff::PrimeField;
+use fiat_shamir::fiat_shamir_transcript::{fq_vec_to_bytes, Transcript};
+use multilinear_polynomial::{
+    eval_store::EvalStore,
+    multilinear_polynomial_evaluation::{table_len, MultilinearPoly},
+};
+
+use crate::{
+    interactive_sum_check::ChallengeSource,
+    sum_check_error::SumCheckError,
+    sum_check_protocol::{append_statement, prove_rounds, Proof},
+};
+
+/// How many evaluations `prove_from_store` reads at a time.
+const CHUNK_SIZE: usize = 1 << 12;
+
+/// Proves the hypercube sum of a table produced by `evals` without holding
+/// the whole table. The first pass computes the digest and the first round
+/// message, the second pass folds straight into a half-size table, and the
+/// remaining rounds run in memory as usual.
+///
+/// The transcript matches `prove` on the materialized table, so the proof
+/// verifies with `verify`. Fails unless `evals` yields `2^num_vars`
+/// evaluations.
+pub fn prove_streamed<F: PrimeField, I: Iterator<Item = F> + Clone>(
+    evals: I,
+    num_vars: usize,
+) -> Result<Proof<F>, SumCheckError<F>> {
+    prove_two_passes(evals.clone(), evals, num_vars)
+}
+
+/// `prove_streamed` over a table kept in `store`, e.g. a file too large to
+/// load, read in chunks on both passes. Fails if reading fails or if the
+/// store does not hold `2^num_vars` evaluations.
+pub fn prove_from_store<F: PrimeField>(
+    store: &impl EvalStore<F>,
+    num_vars: usize,
+) -> io::Result<Proof<F>> {
+    if table_len(num_vars) != Ok(store.len()) {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidData,
+            format!("{} evaluations for {} variables", store.len(), num_vars),
+        ));
+    }
+
+    let (mut first_error, mut second_error) = (None, None);
+    let proof = prove_two_passes(
+        read_pass(store, &mut first_error),
+        read_pass(store, &mut second_error),
+        num_vars,
+    );
+
+    if let Some(error) = first_error.or(second_error) {
+        return Err(error);
+    }
+
+    Ok(proof.expect("the store holds the whole table"))
+}
+
+/// One pass over `store`, stopping at the first read error and leaving it in
+/// `error`, so a failed read can only cut the pass short.
+fn read_pass<'a, F: PrimeField>(
+    store: &'a impl EvalStore<F>,
+    error: &'a mut Option<io::Error>,
+) -> impl Iterator<Item = F> + 'a {
+    store
+        .read_chunks(CHUNK_SIZE)
+        .map_while(move |chunk| chunk.map_err(|e| *error = Some(e)).ok())
+        .flatten()
+}
+
+/// The prover shared by `prove_streamed` and `prove_from_store`, given two
+/// passes over the same table. Fails if the first pass has the wrong length,
+/// or if no table of `num_vars` variables is supported.
+fn prove_two_passes<F: PrimeField>(
+    first_pass: impl Iterator<Item = F>,
+    second_pass: impl Iterator<Item = F>,
+    num_vars: usize,
+) -> Result<Proof<F>, SumCheckError<F>> {
+    let Ok(expected_len) = table_len(num_vars) else {
+        return Err(SumCheckError::WrongNumberOfEvaluations {
+            num_vars,
+            got: first_pass.count(),
+        });
+    };
+    let half = expected_len >> 1;
+
+    let mut len = 0;
+    let mut first_round = vec![F::zero(), F::zero()];
+    let digest = MultilinearPoly::digest_evaluations(
+        num_vars,
+        first_pass.inspect(|eval| {
+            first_round[(len >= half) as usize] += eval;
+            len += 1;
+        }),
+    );
+
+    if len != expected_len {
+        return Err(SumCheckError::WrongNumberOfEvaluations { num_vars, got: len });
+    }
+
+    let claimed_sum = first_round[0] + first_round[1];
+
+    let mut transcript = Transcript::<F>::new();
+    transcript.append(&digest);
+    append_statement(&mut transcript, num_vars, claimed_sum);
+
+    if num_vars == 0 {
+        // The single evaluation is both the sum and the final evaluation.
+        transcript.append(&fq_vec_to_bytes(&[claimed_sum]));
+
+        return Ok(Proof {
+            proof_polynomials: vec![],
+            claimed_sum,
+            final_evaluation: claimed_sum,
+        });
+    }
+
+    let challenge = transcript.next_challenge(&first_round);
+
+    let mut evals = second_pass;
+    let mut folded: Vec<F> = evals.by_ref().take(half).collect();
+    for (a, b) in folded.iter_mut().zip(evals) {
+        *a += challenge * (b - *a);
+    }
+
+    let (rest, _, final_evaluation) = prove_rounds(&MultilinearPoly::new(folded), &mut transcript);
+    transcript.append(&fq_vec_to_bytes(&[final_evaluation]));
+
+    let mut proof_polynomials = Vec::with_capacity(num_vars);
+    proof_polynomials.push(first_round);
+    proof_polynomials.extend(rest);
+
+    Ok(Proof {
+        proof_polynomials,
+        claimed_sum,
+        final_evaluation,
+    })
+}
+
+#[cfg(test)]
+mod test {
+    use ark_bn254::Fq;
+    use multilinear_polynomial::multilinear_polynomial_evaluation::MultilinearPoly;
+
+    use multilinear_polynomial::eval_store::FileEvals;
+
+    use super::{prove_from_store, prove_streamed};
+    use crate::{
+        sum_check_error::SumCheckError,
+        sum_check_protocol::{prove, verify},
+    };
+
+    #[test]
+    fn it_matches_the_in_memory_prover() {
+        let num_vars = 6;
+        let generator = (0..1u64 << num_vars).map(|i| Fq::from(i * 7 + 2));
+
+        let streamed_proof = prove_streamed(generator.clone(), num_vars).unwrap();
+
+        let polynomial = MultilinearPoly::new(generator.collect());
+        let proof = prove(&polynomial);
+
+        assert_eq!(streamed_proof.proof_polynomials, proof.proof_polynomials);
+        assert_eq!(streamed_proof.claimed_sum, proof.claimed_sum);
+        assert_eq!(streamed_proof.final_evaluation, proof.final_evaluation);
+        assert_eq!(verify(&polynomial, streamed_proof), Ok(()));
+    }
+
+    #[test]
+    fn it_proves_a_table_kept_in_a_file() {
+        let num_vars = 18;
+        let polynomial =
+            MultilinearPoly::new((0..1u64 << num_vars).map(|i| Fq::from(i * 5 + 3)).collect());
+        let path = std::env::temp_dir().join(format!("sum_check_store_{}.bin", std::process::id()));
+        polynomial.write_evals_to_file(&path).unwrap();
+
+        let store = FileEvals::<Fq>::open(&path).unwrap();
+        let proof = prove_from_store(&store, num_vars).unwrap();
+        std::fs::remove_file(&path).unwrap();
+
+        assert_eq!(proof, prove(&polynomial));
+        assert_eq!(
+            prove_from_store(&polynomial.evaluations().to_vec(), num_vars).unwrap(),
+            proof
+        );
+        assert!(prove_from_store(&polynomial.evaluations().to_vec(), num_vars - 1).is_err());
+        assert_eq!(verify(&polynomial, proof), Ok(()));
+    }
+
+    #[test]
+    fn it_rejects_a_stream_of_the_wrong_length() {
+        assert_eq!(
+            prove_streamed((0..7u64).map(Fq::from), 3),
+            Err(SumCheckError::WrongNumberOfEvaluations {
+                num_vars: 3,
+                got: 7
+            })
+        );
+    }
+}
@@ -0,0 +1,213 @@
+use ark_ff::PrimeField;
+use fiat_shamir::fiat_shamir_transcript::{fq_vec_to_bytes, Transcript};
+use multilinear_polynomial::multilinear_polynomial_evaluation::MultilinearPoly;
+
+use crate::{
+    sum_check_error::SumCheckError,
+    sum_check_protocol::{
+        append_statement, compute_sum, prove_rounds, verify_final_evaluation, verify_rounds, Proof,
+    },
+};
+
+/// A sum-check over the sub-cube where each `(index, bit)` in `fixed` pins
+/// variable `index` to `bit`; the remaining variables range over {0, 1}.
+#[derive(Debug, Clone)]
+pub struct SubcubeProof<F: PrimeField> {
+    pub fixed: Vec<(usize, bool)>,
+    pub proof: Proof<F>,
+}
+
+/// Fails if a variable is fixed twice or does not exist.
+pub fn prove_subcube<F: PrimeField>(
+    polynomial: &MultilinearPoly<F>,
+    fixed: &[(usize, bool)],
+) -> Result<SubcubeProof<F>, SumCheckError<F>> {
+    let restricted = restrict(polynomial, fixed)?;
+    let claimed_sum = compute_sum(&restricted);
+
+    let mut transcript = Transcript::<F>::new();
+    append_fixed_variables(&mut transcript, fixed);
+    append_statement(&mut transcript, restricted.num_vars(), claimed_sum);
+
+    let (proof_polynomials, _, final_evaluation) = prove_rounds(&restricted, &mut transcript);
+    transcript.append(&fq_vec_to_bytes(&[final_evaluation]));
+
+    Ok(SubcubeProof {
+        fixed: fixed.to_vec(),
+        proof: Proof {
+            proof_polynomials,
+            claimed_sum,
+            final_evaluation,
+        },
+    })
+}
+
+/// Verifies a sub-cube proof for the restriction described by `fixed`,
+/// rejecting a restriction `prove_subcube` would.
+pub fn verify_subcube<F: PrimeField>(
+    polynomial: &MultilinearPoly<F>,
+    fixed: &[(usize, bool)],
+    proof: SubcubeProof<F>,
+) -> Result<(), SumCheckError<F>> {
+    if proof.fixed != fixed {
+        return Err(SumCheckError::FixedVariablesMismatch);
+    }
+
+    let restricted = restrict(polynomial, fixed)?;
+
+    let mut transcript = Transcript::<F>::new();
+    append_fixed_variables(&mut transcript, fixed);
+    append_statement(
+        &mut transcript,
+        restricted.num_vars(),
+        proof.proof.claimed_sum,
+    );
+
+    let sub_claim = verify_rounds(
+        proof.proof.claimed_sum,
+        restricted.num_vars(),
+        &proof.proof.proof_polynomials,
+        &mut transcript,
+    )?;
+    let sub_claim =
+        verify_final_evaluation(sub_claim, proof.proof.final_evaluation, &mut transcript)?;
+
+    let eval = restricted.evaluate(&sub_claim.point);
+    if eval != sub_claim.value {
+        return Err(SumCheckError::FinalEvaluationMismatch {
+            expected: sub_claim.value,
+            got: eval,
+        });
+    }
+
+    Ok(())
+}
+
+/// Fixes the given variables, highest index first so that the indices of the
+/// variables still to be fixed are unaffected by each fold. Fails before
+/// folding anything if a variable is fixed twice or does not exist.
+fn restrict<F: PrimeField>(
+    polynomial: &MultilinearPoly<F>,
+    fixed: &[(usize, bool)],
+) -> Result<MultilinearPoly<F>, SumCheckError<F>> {
+    let mut sorted = fixed.to_vec();
+    sorted.sort_by_key(|(index, _)| std::cmp::Reverse(*index));
+
+    if let Some(pair) = sorted.windows(2).find(|pair| pair[0].0 == pair[1].0) {
+        return Err(SumCheckError::RepeatedFixedVariable { index: pair[0].0 });
+    }
+
+    if let Some((index, _)) = sorted
+        .first()
+        .filter(|(index, _)| *index >= polynomial.num_vars())
+    {
+        return Err(SumCheckError::FixedVariableOutOfRange {
+            index: *index,
+            num_vars: polynomial.num_vars(),
+        });
+    }
+
+    Ok(sorted
+        .iter()
+        .fold(polynomial.clone(), |poly, (index, bit)| {
+            poly.partial_evaluate(*index, &F::from(*bit))
+                .expect("the indices were checked to be in range")
+        }))
+}
+
+fn append_fixed_variables<F: PrimeField>(transcript: &mut Transcript<F>, fixed: &[(usize, bool)]) {
+    let encoded: Vec<F> = fixed
+        .iter()
+        .flat_map(|(index, bit)| [F::from(*index as u64), F::from(*bit)])
+        .collect();
+
+    transcript.append(&fq_vec_to_bytes(&[F::from(fixed.len() as u64)]));
+    transcript.append(&fq_vec_to_bytes(&encoded));
+}
+
+#[cfg(test)]
+mod test {
+    use ark_bn254::Fq;
+    use multilinear_polynomial::multilinear_polynomial_evaluation::MultilinearPoly;
+
+    use super::{prove_subcube, verify_subcube};
+    use crate::sum_check_error::SumCheckError;
+
+    fn sample_poly() -> MultilinearPoly<Fq> {
+        MultilinearPoly::new((1..=16u64).map(|i| Fq::from(i * i)).collect())
+    }
+
+    #[test]
+    fn it_proves_a_sum_over_a_sub_cube() {
+        let polynomial = sample_poly();
+        let fixed = [(1, true), (3, false)];
+
+        // Variable i is bit (num_vars - 1 - i) of the table index.
+        let brute_force_sum: Fq = polynomial
+            .evaluations()
+            .iter()
+            .enumerate()
+            .filter(|(index, _)| (index >> 2) & 1 == 1 && index & 1 == 0)
+            .map(|(_, eval)| *eval)
+            .sum();
+
+        let proof = prove_subcube(&polynomial, &fixed).unwrap();
+
+        assert_eq!(proof.proof.claimed_sum, brute_force_sum);
+        assert_eq!(proof.proof.proof_polynomials.len(), 2);
+        assert_eq!(verify_subcube(&polynomial, &fixed, proof), Ok(()));
+    }
+
+    #[test]
+    fn it_rejects_a_tampered_restriction() {
+        let polynomial = sample_poly();
+        let fixed = [(1, true), (3, false)];
+
+        let mut proof = prove_subcube(&polynomial, &fixed).unwrap();
+        proof.fixed[0] = (1, false);
+
+        assert_eq!(
+            verify_subcube(&polynomial, &fixed, proof),
+            Err(SumCheckError::FixedVariablesMismatch)
+        );
+    }
+
+    #[test]
+    fn it_rejects_a_proof_for_another_sub_cube() {
+        let polynomial = sample_poly();
+
+        let proof = prove_subcube(&polynomial, &[(0, false), (2, true)]).unwrap();
+
+        assert!(verify_subcube(&polynomial, &[(0, true), (2, true)], proof).is_err());
+    }
+
+    #[test]
+    fn it_rejects_a_malformed_restriction() {
+        let polynomial = sample_poly();
+        let proof = prove_subcube(&polynomial, &[(1, true)]).unwrap();
+
+        assert_eq!(
+            prove_subcube(&polynomial, &[(2, true), (2, false)]).unwrap_err(),
+            SumCheckError::RepeatedFixedVariable { index: 2 }
+        );
+
+        let out_of_range = [(4, true)];
+        let mut forged = proof.clone();
+        forged.fixed = out_of_range.to_vec();
+        assert_eq!(
+            verify_subcube(&polynomial, &out_of_range, forged),
+            Err(SumCheckError::FixedVariableOutOfRange {
+                index: 4,
+                num_vars: 4
+            })
+        );
+
+        let repeated = [(1, true), (1, true)];
+        let mut forged = proof;
+        forged.fixed = repeated.to_vec();
+        assert_eq!(
+            verify_subcube(&polynomial, &repeated, forged),
+            Err(SumCheckError::RepeatedFixedVariable { index: 1 })
+        );
+    }
+}
@@ -0,0 +1,353 @@
+use ark_ff::{Field, PrimeField};
+use multilinear_polynomial::composed_polynomial::{ShapeError, SumPolyShape};
+use std::fmt;
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum SumCheckError<F: Field> {
+    RoundSumMismatch {
+        round: usize,
+        expected: F,
+        got: F,
+    },
+    FinalEvaluationMismatch {
+        expected: F,
+        got: F,
+    },
+    WrongNumberOfRounds {
+        expected: usize,
+        got: usize,
+    },
+    InvalidRoundPolynomialLength {
+        round: usize,
+        expected: usize,
+        got: usize,
+    },
+    WrongNumberOfClaims {
+        expected: usize,
+        got: usize,
+    },
+    ClaimMismatch {
+        expected: F,
+        got: F,
+    },
+    FixedVariablesMismatch,
+    ChallengeMismatch {
+        round: usize,
+    },
+    MalformedPolynomial {
+        error: ShapeError,
+        shape: SumPolyShape,
+    },
+    NextLayerMismatch {
+        num_rounds: usize,
+        got: usize,
+    },
+    EmptyBatch,
+    MismatchedNumVars {
+        index: usize,
+        expected: usize,
+        got: usize,
+    },
+    RepeatedFixedVariable {
+        index: usize,
+    },
+    FixedVariableOutOfRange {
+        index: usize,
+        num_vars: usize,
+    },
+    WrongNumberOfEvaluations {
+        num_vars: usize,
+        got: usize,
+    },
+    InconsistentMatrixDimensions {
+        a_vars: usize,
+        b_vars: usize,
+        c_vars: usize,
+    },
+}
+
+impl<F: Field> fmt::Display for SumCheckError<F> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SumCheckError::RoundSumMismatch {
+                round,
+                expected,
+                got,
+            } => write!(
+                f,
+                "round {} sum mismatch: expected {}, got {}",
+                round, expected, got
+            ),
+            SumCheckError::FinalEvaluationMismatch { expected, got } => write!(
+                f,
+                "final evaluation mismatch: expected {}, got {}",
+                expected, got
+            ),
+            SumCheckError::WrongNumberOfRounds { expected, got } => write!(
+                f,
+                "wrong number of round polynomials: expected {}, got {}",
+                expected, got
+            ),
+            SumCheckError::InvalidRoundPolynomialLength {
+                round,
+                expected,
+                got,
+            } => write!(
+                f,
+                "round {} polynomial has {} evaluations, expected {}",
+                round, got, expected
+            ),
+            SumCheckError::WrongNumberOfClaims { expected, got } => write!(
+                f,
+                "wrong number of claims: expected {}, got {}",
+                expected, got
+            ),
+            SumCheckError::ClaimMismatch { expected, got } => write!(
+                f,
+                "claimed sum {} does not match the hypercube sum {}",
+                got, expected
+            ),
+            SumCheckError::FixedVariablesMismatch => {
+                write!(f, "proof was produced for a different sub-cube")
+            }
+            SumCheckError::ChallengeMismatch { round } => {
+                write!(f, "round {} challenge does not match the transcript", round)
+            }
+            SumCheckError::MalformedPolynomial { error, shape } => {
+                write!(f, "malformed composed polynomial {}: {}", shape, error)
+            }
+            SumCheckError::NextLayerMismatch { num_rounds, got } => write!(
+                f,
+                "next layer has {} variables, expected half of the {} rounds",
+                got, num_rounds
+            ),
+            SumCheckError::EmptyBatch => write!(f, "a batch needs at least one polynomial"),
+            SumCheckError::MismatchedNumVars {
+                index,
+                expected,
+                got,
+            } => write!(
+                f,
+                "polynomial {} has {} variables, expected {}",
+                index, got, expected
+            ),
+            SumCheckError::RepeatedFixedVariable { index } => {
+                write!(f, "variable {} is fixed more than once", index)
+            }
+            SumCheckError::FixedVariableOutOfRange { index, num_vars } => write!(
+                f,
+                "cannot fix variable {} of a polynomial of {} variables",
+                index, num_vars
+            ),
+            SumCheckError::WrongNumberOfEvaluations { num_vars, got } => {
+                write!(f, "{} evaluations for {} variables", got, num_vars)
+            }
+            SumCheckError::InconsistentMatrixDimensions {
+                a_vars,
+                b_vars,
+                c_vars,
+            } => write!(
+                f,
+                "matrices of {}, {} and {} variables cannot satisfy A·B = C",
+                a_vars, b_vars, c_vars
+            ),
+        }
+    }
+}
+
+impl<F: Field> std::error::Error for SumCheckError<F> {}
+
+/// A proof whose shape is wrong before any of its values are checked.
+#[derive(Debug, Clone, PartialEq)]
+pub enum ProofFormatError {
+    InvalidRoundPolynomialLength {
+        round: usize,
+        expected: usize,
+        got: usize,
+    },
+}
+
+impl fmt::Display for ProofFormatError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ProofFormatError::InvalidRoundPolynomialLength {
+                round,
+                expected,
+                got,
+            } => write!(
+                f,
+                "round {} polynomial has {} evaluations, expected {}",
+                round, got, expected
+            ),
+        }
+    }
+}
+
+impl std::error::Error for ProofFormatError {}
+
+/// Why a GKR layer's round polynomials were rejected.
+#[derive(Debug, Clone, PartialEq)]
+pub enum GkrError<F: PrimeField> {
+    RoundSumMismatch {
+        round: usize,
+        expected: F,
+        f_b_0: F,
+        f_b_1: F,
+    },
+    InvalidRoundPolynomialLength {
+        round: usize,
+        expected: usize,
+        got: usize,
+    },
+    WrongNumberOfClaims {
+        expected: usize,
+        got: usize,
+    },
+    LayerFailed {
+        layer: usize,
+        source: Box<GkrError<F>>,
+    },
+    WrongPointLength {
+        expected: usize,
+        got: usize,
+    },
+    WrongNumberOfRounds {
+        expected: usize,
+        got: usize,
+    },
+}
+
+impl<F: PrimeField> fmt::Display for GkrError<F> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            GkrError::RoundSumMismatch {
+                round,
+                expected,
+                f_b_0,
+                f_b_1,
+            } => write!(
+                f,
+                "round {} sum mismatch: expected {}, got {} + {}",
+                round, expected, f_b_0, f_b_1
+            ),
+            GkrError::InvalidRoundPolynomialLength {
+                round,
+                expected,
+                got,
+            } => write!(
+                f,
+                "round {} polynomial has {} evaluations, expected {}",
+                round, got, expected
+            ),
+            GkrError::WrongNumberOfClaims { expected, got } => write!(
+                f,
+                "wrong number of claims: expected {}, got {}",
+                expected, got
+            ),
+            GkrError::LayerFailed { layer, source } => write!(f, "layer {}: {}", layer, source),
+            GkrError::WrongPointLength { expected, got } => write!(
+                f,
+                "final point has {} coordinates, expected {} for the two halves",
+                got, expected
+            ),
+            GkrError::WrongNumberOfRounds { expected, got } => write!(
+                f,
+                "wrong number of round polynomials: expected {}, got {}",
+                expected, got
+            ),
+        }
+    }
+}
+
+impl<F: PrimeField> std::error::Error for GkrError<F> {}
+
+impl<F: PrimeField> From<GkrError<F>> for SumCheckError<F> {
+    fn from(error: GkrError<F>) -> Self {
+        match error {
+            GkrError::RoundSumMismatch {
+                round,
+                expected,
+                f_b_0,
+                f_b_1,
+            } => SumCheckError::RoundSumMismatch {
+                round,
+                expected,
+                got: f_b_0 + f_b_1,
+            },
+            GkrError::InvalidRoundPolynomialLength {
+                round,
+                expected,
+                got,
+            } => SumCheckError::InvalidRoundPolynomialLength {
+                round,
+                expected,
+                got,
+            },
+            GkrError::WrongNumberOfClaims { expected, got } => {
+                SumCheckError::WrongNumberOfClaims { expected, got }
+            }
+            GkrError::LayerFailed { source, .. } => Self::from(*source),
+            GkrError::WrongPointLength { expected, got }
+            | GkrError::WrongNumberOfRounds { expected, got } => {
+                SumCheckError::WrongNumberOfRounds { expected, got }
+            }
+        }
+    }
+}
+
+/// Why `gkr_prove` could not run a layer's sum-check.
+#[derive(Debug, Clone, PartialEq)]
+pub enum GkrProveError<F: PrimeField> {
+    /// The composed polynomial's shape, printed with the error, shows which
+    /// term and factor are off.
+    Shape {
+        error: ShapeError,
+        shape: SumPolyShape,
+    },
+    NextLayerMismatch {
+        num_rounds: usize,
+        got: usize,
+    },
+    ClaimMismatch {
+        expected: F,
+        got: F,
+    },
+}
+
+impl<F: PrimeField> fmt::Display for GkrProveError<F> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            GkrProveError::Shape { error, shape } => {
+                write!(f, "malformed composed polynomial {}: {}", shape, error)
+            }
+            GkrProveError::NextLayerMismatch { num_rounds, got } => write!(
+                f,
+                "next layer has {} variables, expected half of the {} rounds",
+                got, num_rounds
+            ),
+            GkrProveError::ClaimMismatch { expected, got } => write!(
+                f,
+                "claimed sum {} does not match the composed polynomial's sum {}",
+                got, expected
+            ),
+        }
+    }
+}
+
+impl<F: PrimeField> std::error::Error for GkrProveError<F> {}
+
+impl<F: PrimeField> From<GkrProveError<F>> for SumCheckError<F> {
+    fn from(error: GkrProveError<F>) -> Self {
+        match error {
+            GkrProveError::Shape { error, shape } => {
+                SumCheckError::MalformedPolynomial { error, shape }
+            }
+            GkrProveError::NextLayerMismatch { num_rounds, got } => {
+                SumCheckError::NextLayerMismatch { num_rounds, got }
+            }
+            GkrProveError::ClaimMismatch { expected, got } => {
+                SumCheckError::ClaimMismatch { expected, got }
+            }
+        }
+    }
+}
@@ -1,218 +1,1016 @@
 use ark_bn254::Fq;
+use ark_ff::{BigInteger, Field, PrimeField};
 use fiat_shamir::fiat_shamir_transcript::{fq_vec_to_bytes, Transcript};
 use multilinear_polynomial::{
-    composed_polynomial::SumPoly, multilinear_polynomial_evaluation::MultilinearPoly,
+    composed_polynomial::ComposedPolynomial, evaluation_claim::EvaluationClaim,
+    multilinear_polynomial_evaluation::MultilinearPoly,
 };
-use univariate_polynomial::univariate_polynomial_dense::UnivariatePoly;
+#[cfg(feature = "parallel")]
+use rayon::prelude::*;
+use univariate_polynomial::{
+    barycentric::BarycentricDomain,
+    univariate_polynomial_dense::{InterpolationError, UnivariatePoly},
+};
+
+use crate::{
+    interactive_sum_check::{ChallengeSource, SumCheckProver, SumCheckVerifier},
+    sum_check_error::{GkrError, GkrProveError, ProofFormatError, SumCheckError},
+};
+
+/// A non-interactive sum-check proof.
+///
+/// The transcript is bound to the polynomial through its `digest()` rather
+/// than its full evaluation table, so proofs produced before that change do
+/// not verify. `final_evaluation` is the prover's value of the polynomial at
+/// the sampled point, absorbed after the last round.
+///
+/// A polynomial with no variables has no rounds: `proof_polynomials` is empty
+/// and `claimed_sum` and `final_evaluation` both equal its single evaluation.
+/// A one-variable polynomial has a single round whose message is its table.
+///
+/// Outside this crate a proof can only be built through `Proof::new`, so
+/// every proof handed to `verify` has a well-formed shape.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Proof<F: PrimeField> {
+    pub(crate) proof_polynomials: Vec<Vec<F>>,
+    pub(crate) claimed_sum: F,
+    pub(crate) final_evaluation: F,
+}
+
+impl<F: PrimeField> Proof<F> {
+    /// Builds a proof after checking that every round polynomial is sent as
+    /// its evaluations at `0` and `1`.
+    pub fn new(
+        proof_polynomials: Vec<Vec<F>>,
+        claimed_sum: F,
+        final_evaluation: F,
+    ) -> Result<Self, ProofFormatError> {
+        for (round, poly) in proof_polynomials.iter().enumerate() {
+            if poly.len() != 2 {
+                return Err(ProofFormatError::InvalidRoundPolynomialLength {
+                    round,
+                    expected: 2,
+                    got: poly.len(),
+                });
+            }
+        }
+
+        Ok(Self {
+            proof_polynomials,
+            claimed_sum,
+            final_evaluation,
+        })
+    }
+
+    pub fn num_rounds(&self) -> usize {
+        self.proof_polynomials.len()
+    }
+
+    pub fn round_polynomial(&self, round: usize) -> Option<&[F]> {
+        self.proof_polynomials.get(round).map(Vec::as_slice)
+    }
+
+    pub fn claimed_sum(&self) -> F {
+        self.claimed_sum
+    }
+
+    pub fn final_evaluation(&self) -> F {
+        self.final_evaluation
+    }
+
+    /// The size of the proof's field elements in their canonical byte form.
+    pub fn size_in_bytes(&self) -> usize {
+        let element_size = F::zero().into_bigint().to_bytes_le().len();
+        let num_elements: usize = self.proof_polynomials.iter().map(Vec::len).sum();
+
+        (num_elements + 2) * element_size
+    }
+}
+
+/// A well-formed proof of 0 to `MAX_VARS` rounds with arbitrary values,
+/// which verifies for no polynomial except by chance.
+#[cfg(any(test, feature = "arbitrary"))]
+impl<'a, F: PrimeField> arbitrary::Arbitrary<'a> for Proof<F> {
+    fn arbitrary(u: &mut arbitrary::Unstructured<'a>) -> arbitrary::Result<Self> {
+        use multilinear_polynomial::fuzzing::{arbitrary_field, MAX_VARS};
+
+        let num_rounds = u.int_in_range(0..=MAX_VARS)?;
+
+        Ok(Self {
+            proof_polynomials: (0..num_rounds)
+                .map(|_| Ok(vec![arbitrary_field(u)?, arbitrary_field(u)?]))
+                .collect::<arbitrary::Result<_>>()?,
+            claimed_sum: arbitrary_field(u)?,
+            final_evaluation: arbitrary_field(u)?,
+        })
+    }
+}
 
-#[derive(Debug, Clone)]
-pub struct Proof {
-    pub proof_polynomials: Vec<Vec<Fq>>,
-    pub claimed_sum: Fq,
+/// Which variable the prover binds in each round. Either way the round
+/// messages have the same form; only the order of the challenges within the
+/// final evaluation point differs.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum BindingOrder {
+    /// Binds variable 0, the most significant bit of the table index, first.
+    #[default]
+    MostSignificantFirst,
+    /// Binds the last variable, the least significant bit, first.
+    LeastSignificantFirst,
 }
-pub struct GkrProof {
-    pub proof_polynomials: Vec<Vec<Fq>>,
-    pub claimed_sum: Fq,
-    pub random_challenges: Vec<Fq>,
+
+impl BindingOrder {
+    /// Arranges the round challenges into the point, in variable order, at
+    /// which the polynomial ends up evaluated.
+    pub fn evaluation_point<F: PrimeField>(&self, challenges: &[F]) -> Vec<F> {
+        match self {
+            BindingOrder::MostSignificantFirst => challenges.to_vec(),
+            BindingOrder::LeastSignificantFirst => challenges.iter().rev().copied().collect(),
+        }
+    }
 }
 
-pub struct GkrVerify {
-    pub verified: bool,
-    pub final_claimed_sum: Fq,
-    pub random_challenges: Vec<Fq>,
+/// One GKR layer's sum-check. The rounds run over `(b, c)`, and `w_b` and
+/// `w_c` are the prover's claimed values of the layer below at the `b` and
+/// `c` halves of the final point, which the verifier folds into its next
+/// claim. Each round polynomial is sent as its evaluations at
+/// `0, 1, ..., d`.
+///
+/// `random_challenges` records the prover's view of the transcript for its
+/// own use. Verifiers resample the challenges and never read this field;
+/// `validate_challenges` checks it when a caller does want to rely on it.
+#[derive(Debug, Clone, PartialEq)]
+pub struct GkrProof<F: PrimeField> {
+    pub proof_polynomials: Vec<Vec<F>>,
+    pub claimed_sum: F,
+    pub random_challenges: Vec<F>,
+    pub w_b: F,
+    pub w_c: F,
 }
 
-fn get_round_partial_polynomial_proof_gkr(composed_poly: &SumPoly<Fq>) -> Vec<Fq> {
-    let degree = composed_poly.get_degree();
-    let mut poly_proof = Vec::with_capacity(degree + 1);
+impl<F: PrimeField> GkrProof<F> {
+    /// Splits the final point into `(r_b, r_c)` the same way
+    /// `split_gkr_point` splits the point of the verifier's claim.
+    pub fn split_point(&self, half_vars: usize) -> Result<GkrLayerPoint<F>, GkrError<F>> {
+        split_gkr_point(&self.random_challenges, half_vars)
+    }
 
-    for i in 0..degree {
-        let value = Fq::from(i as u64);
-        let partial_poly = composed_poly.partial_evaluate(&value);
+    /// The round polynomials in the coefficient form earlier versions of
+    /// `gkr_prove` sent, lowest degree first. Fails on a round with no
+    /// evaluations.
+    pub fn coefficient_polynomials(&self) -> Result<Vec<Vec<F>>, InterpolationError<F>> {
+        self.proof_polynomials
+            .iter()
+            .map(|evals| {
+                let points = evals
+                    .iter()
+                    .enumerate()
+                    .map(|(i, y)| (F::from(i as u64), *y))
+                    .collect();
 
-        let eval = partial_poly.reduce().iter().sum();
-        poly_proof.push(eval);
+                UnivariatePoly::interpolate(points).map(|poly| poly.coefficient)
+            })
+            .collect()
     }
 
-    let points = poly_proof
-        .iter()
-        .enumerate()
-        .map(|(i, y)| (Fq::from(i as u64), *y))
-        .collect();
+    /// Replays the round polynomials on `transcript`, which must be in the
+    /// state the prover left it in after any label, and checks that
+    /// `random_challenges` are the challenges it yields.
+    pub fn validate_challenges(
+        &self,
+        transcript: &mut Transcript<F>,
+    ) -> Result<(), SumCheckError<F>> {
+        if self.random_challenges.len() != self.proof_polynomials.len() {
+            return Err(SumCheckError::WrongNumberOfRounds {
+                expected: self.proof_polynomials.len(),
+                got: self.random_challenges.len(),
+            });
+        }
+
+        append_gkr_layer_tag(transcript, self.proof_polynomials.len(), self.claimed_sum);
+
+        for (round, (poly, challenge)) in self
+            .proof_polynomials
+            .iter()
+            .zip(&self.random_challenges)
+            .enumerate()
+        {
+            transcript.append(&fq_vec_to_bytes(poly));
+
+            if transcript.get_uniform_challenge() != *challenge {
+                return Err(SumCheckError::ChallengeMismatch { round });
+            }
+        }
+
+        Ok(())
+    }
+}
 
-    UnivariatePoly::interpolate(points).coefficient
+/// A GKR layer's final point, split into the points `b` and `c` of the
+/// layer below are evaluated at.
+#[derive(Debug, Clone, PartialEq)]
+pub struct GkrLayerPoint<F: PrimeField> {
+    pub b_point: Vec<F>,
+    pub c_point: Vec<F>,
 }
 
-fn get_round_partial_polynomial_proof(polynomial: &[Fq]) -> Vec<Fq> {
+/// The split both `gkr_prove` and the verifier use, so the
+/// two sides agree on `(r_b, r_c)` by construction. Fails unless `point` has
+/// exactly `2 * half_vars` coordinates.
+pub fn split_gkr_point<F: PrimeField>(
+    point: &[F],
+    half_vars: usize,
+) -> Result<GkrLayerPoint<F>, GkrError<F>> {
+    if half_vars.checked_mul(2) != Some(point.len()) {
+        return Err(GkrError::WrongPointLength {
+            expected: half_vars.saturating_mul(2),
+            got: point.len(),
+        });
+    }
+
+    let (b_point, c_point) = point.split_at(half_vars);
+
+    Ok(GkrLayerPoint {
+        b_point: b_point.to_vec(),
+        c_point: c_point.to_vec(),
+    })
+}
+
+pub type GkrProofBn254 = GkrProof<Fq>;
+
+pub(crate) fn get_round_partial_polynomial_proof<F: Field>(polynomial: &[F]) -> Vec<F> {
     let mid_point = polynomial.len() / 2;
     let (zeros, ones) = polynomial.split_at(mid_point);
 
+    #[cfg(feature = "parallel")]
+    let poly_proof = vec![zeros.par_iter().sum(), ones.par_iter().sum()];
+
+    #[cfg(not(feature = "parallel"))]
     let poly_proof = vec![zeros.iter().sum(), ones.iter().sum()];
 
     poly_proof
 }
 
-pub fn prove(polynomial: &MultilinearPoly<Fq>) -> Proof {
-    let mut transcript = Transcript::<Fq>::new();
-    transcript.append(&fq_vec_to_bytes(&polynomial.evaluation));
+pub fn compute_sum<F: PrimeField>(polynomial: &MultilinearPoly<F>) -> F {
+    polynomial.hypercube_sum()
+}
+
+pub fn prove<F: PrimeField>(polynomial: &MultilinearPoly<F>) -> Proof<F> {
+    let mut transcript = Transcript::<F>::new();
+
+    prove_with_transcript(polynomial, &mut transcript)
+}
+
+/// Like `prove`, but starts from a transcript that has absorbed `label`, so
+/// proofs made for one application do not verify under another's label.
+pub fn prove_labeled<F: PrimeField>(label: &[u8], polynomial: &MultilinearPoly<F>) -> Proof<F> {
+    let mut transcript = Transcript::<F>::new();
+    append_label(&mut transcript, label);
+
+    prove_with_transcript(polynomial, &mut transcript)
+}
+
+/// Like `prove`, but continues an existing transcript so the sum-check can be
+/// embedded in a larger protocol.
+pub fn prove_with_transcript<F: PrimeField>(
+    polynomial: &MultilinearPoly<F>,
+    transcript: &mut Transcript<F>,
+) -> Proof<F> {
+    let claimed_sum = compute_sum(polynomial);
+
+    prove_claim(polynomial, claimed_sum, transcript)
+}
+
+/// Proves that `polynomial` sums to `claimed_sum` over the hypercube, failing
+/// before any rounds are run if the claim is inconsistent with the table.
+pub fn prove_with_claim<F: PrimeField>(
+    polynomial: &MultilinearPoly<F>,
+    claimed_sum: F,
+) -> Result<Proof<F>, SumCheckError<F>> {
+    let sum = compute_sum(polynomial);
+    if sum != claimed_sum {
+        return Err(SumCheckError::ClaimMismatch {
+            expected: sum,
+            got: claimed_sum,
+        });
+    }
+
+    let mut transcript = Transcript::<F>::new();
+
+    Ok(prove_claim(polynomial, claimed_sum, &mut transcript))
+}
+
+fn prove_claim<F: PrimeField>(
+    polynomial: &MultilinearPoly<F>,
+    claimed_sum: F,
+    transcript: &mut Transcript<F>,
+) -> Proof<F> {
+    transcript.append(&polynomial.digest());
+
+    prove_unbound(
+        polynomial,
+        claimed_sum,
+        BindingOrder::MostSignificantFirst,
+        transcript,
+    )
+}
+
+/// Like `prove`, but binds the variables in the given `order`. Verify with
+/// `verify_with_options` and the same order.
+pub fn prove_with_options<F: PrimeField>(
+    polynomial: &MultilinearPoly<F>,
+    order: BindingOrder,
+) -> Proof<F> {
+    let mut transcript = Transcript::<F>::new();
+    transcript.append(&polynomial.digest());
+
+    prove_unbound(polynomial, compute_sum(polynomial), order, &mut transcript)
+}
+
+/// The prover side of `verify_partial`: everything `prove` does except
+/// binding the transcript to the polynomial, which is left to the caller.
+pub(crate) fn prove_unbound<F: PrimeField>(
+    polynomial: &MultilinearPoly<F>,
+    claimed_sum: F,
+    order: BindingOrder,
+    transcript: &mut Transcript<F>,
+) -> Proof<F> {
+    append_statement(transcript, polynomial.num_vars(), claimed_sum);
+
+    let (proof_polynomials, _, final_evaluation) =
+        prove_rounds_in_order(polynomial, order, transcript);
+    transcript.append(&fq_vec_to_bytes(&[final_evaluation]));
+
+    Proof {
+        proof_polynomials,
+        claimed_sum,
+        final_evaluation,
+    }
+}
 
-    let claimed_sum: Fq = polynomial.evaluation.iter().sum();
-    transcript.append(&fq_vec_to_bytes(&[claimed_sum]));
+/// Runs the sum-check rounds for `polynomial` against `transcript`, returning
+/// the round polynomials, the challenges that were drawn and the polynomial's
+/// evaluation at those challenges.
+pub(crate) fn prove_rounds<F: PrimeField>(
+    polynomial: &MultilinearPoly<F>,
+    transcript: &mut Transcript<F>,
+) -> (Vec<Vec<F>>, Vec<F>, F) {
+    prove_rounds_in_order(polynomial, BindingOrder::MostSignificantFirst, transcript)
+}
 
-    let num_rounds = polynomial.num_of_vars;
-    let mut proof_polynomials = Vec::with_capacity(num_rounds as usize);
-    let mut current_poly = polynomial.clone();
+/// `prove_rounds` with the variables bound in `order`. The challenges are
+/// returned in round order.
+pub(crate) fn prove_rounds_in_order<F: PrimeField>(
+    polynomial: &MultilinearPoly<F>,
+    order: BindingOrder,
+    transcript: &mut Transcript<F>,
+) -> (Vec<Vec<F>>, Vec<F>, F) {
+    let num_rounds = polynomial.num_vars();
+    let mut proof_polynomials = Vec::with_capacity(num_rounds);
+    let mut random_challenges = Vec::with_capacity(num_rounds);
+    let mut prover = SumCheckProver::with_binding_order(polynomial, order);
 
     for _ in 0..num_rounds {
-        let proof_poly = get_round_partial_polynomial_proof(&current_poly.evaluation);
+        let proof_poly = prover.round_message();
 
-        transcript.append(&fq_vec_to_bytes(&proof_poly));
+        let random_challenge = transcript.next_challenge(&proof_poly);
 
         proof_polynomials.push(proof_poly);
 
-        let random_challenge = transcript.get_random_challenge();
+        prover.receive_challenge(random_challenge);
 
-        current_poly = current_poly.partial_evaluate(0, &random_challenge);
+        random_challenges.push(random_challenge);
     }
 
-    Proof {
+    (
         proof_polynomials,
+        random_challenges,
+        prover.final_evaluation(),
+    )
+}
+
+pub fn verify<F: PrimeField>(
+    polynomial: &MultilinearPoly<F>,
+    proof: Proof<F>,
+) -> Result<(), SumCheckError<F>> {
+    let mut transcript = Transcript::<F>::new();
+
+    verify_with_transcript(polynomial, proof, &mut transcript)
+}
+
+/// Verifies a proof made by `prove_labeled` under the same `label`.
+pub fn verify_labeled<F: PrimeField>(
+    label: &[u8],
+    polynomial: &MultilinearPoly<F>,
+    proof: Proof<F>,
+) -> Result<(), SumCheckError<F>> {
+    let mut transcript = Transcript::<F>::new();
+    append_label(&mut transcript, label);
+
+    verify_with_transcript(polynomial, proof, &mut transcript)
+}
+
+/// Verifies a proof made by `prove_with_options` with the same `order`.
+pub fn verify_with_options<F: PrimeField>(
+    polynomial: &MultilinearPoly<F>,
+    proof: Proof<F>,
+    order: BindingOrder,
+) -> Result<(), SumCheckError<F>> {
+    let mut transcript = Transcript::<F>::new();
+
+    verify_claim(
+        &polynomial.digest(),
+        proof.claimed_sum,
+        polynomial.num_vars(),
+        proof,
+        |point| evaluate_at(polynomial, &order.evaluation_point(point)),
+        &mut transcript,
+    )?;
+
+    Ok(())
+}
+
+/// Like `verify`, but replays the proof on an existing transcript, which must
+/// be in the same state the prover's was in when `prove_with_transcript` ran.
+pub fn verify_with_transcript<F: PrimeField>(
+    polynomial: &MultilinearPoly<F>,
+    proof: Proof<F>,
+    transcript: &mut Transcript<F>,
+) -> Result<(), SumCheckError<F>> {
+    verify_claim(
+        &polynomial.digest(),
+        proof.claimed_sum,
+        polynomial.num_vars(),
+        proof,
+        |point| evaluate_at(polynomial, point),
+        transcript,
+    )?;
+
+    Ok(())
+}
+
+/// The verifier's oracle query, a single streaming pass over the table. A
+/// point of the wrong length is reported as a wrong number of rounds instead
+/// of panicking.
+fn evaluate_at<F: PrimeField>(
+    polynomial: &MultilinearPoly<F>,
+    point: &[F],
+) -> Result<F, SumCheckError<F>> {
+    if point.len() != polynomial.num_vars() {
+        return Err(SumCheckError::WrongNumberOfRounds {
+            expected: polynomial.num_vars(),
+            got: point.len(),
+        });
+    }
+
+    Ok(polynomial.evaluate_streaming(point))
+}
+
+/// Verifies `proof` without access to the evaluation table, only its
+/// `digest`. The `oracle` is queried once, at the final random point, and its
+/// answer must match the claim left over from the last round.
+pub fn verify_with_oracle<F: PrimeField>(
+    digest: &[u8; 32],
+    claimed_sum: F,
+    num_vars: usize,
+    proof: Proof<F>,
+    oracle: impl FnOnce(&[F]) -> F,
+) -> Result<EvaluationClaim<F>, SumCheckError<F>> {
+    let mut transcript = Transcript::<F>::new();
+
+    verify_claim(
+        digest,
         claimed_sum,
+        num_vars,
+        proof,
+        |point| Ok(oracle(point)),
+        &mut transcript,
+    )
+}
+
+fn verify_claim<F: PrimeField>(
+    digest: &[u8; 32],
+    claimed_sum: F,
+    num_vars: usize,
+    proof: Proof<F>,
+    oracle: impl FnOnce(&[F]) -> Result<F, SumCheckError<F>>,
+    transcript: &mut Transcript<F>,
+) -> Result<EvaluationClaim<F>, SumCheckError<F>> {
+    if proof.claimed_sum != claimed_sum {
+        return Err(SumCheckError::ClaimMismatch {
+            expected: claimed_sum,
+            got: proof.claimed_sum,
+        });
     }
+
+    transcript.append(digest);
+
+    let sub_claim = verify_partial(proof, num_vars, transcript)?;
+
+    let oracle_eval = oracle(&sub_claim.point)?;
+    if sub_claim.value != oracle_eval {
+        return Err(SumCheckError::FinalEvaluationMismatch {
+            expected: sub_claim.value,
+            got: oracle_eval,
+        });
+    }
+
+    Ok(sub_claim)
 }
 
-pub fn verify(polynomial: &MultilinearPoly<Fq>, proof: Proof) -> bool {
-    let mut transcript = Transcript::<Fq>::new();
-    transcript.append(&fq_vec_to_bytes(&polynomial.evaluation));
-    transcript.append(&fq_vec_to_bytes(&[proof.claimed_sum]));
+/// Runs every check except the final oracle query and returns the sampled
+/// point with the evaluation the polynomial must have there. The caller is
+/// responsible for binding `transcript` to the polynomial beforehand, e.g. by
+/// absorbing its `digest()` or a commitment, and for discharging the claim.
+pub fn verify_partial<F: PrimeField>(
+    proof: Proof<F>,
+    num_vars: usize,
+    transcript: &mut Transcript<F>,
+) -> Result<EvaluationClaim<F>, SumCheckError<F>> {
+    append_statement(transcript, num_vars, proof.claimed_sum);
 
-    let mut current_poly = polynomial.clone();
-    let mut random_challenges = Vec::with_capacity(proof.proof_polynomials.len());
-    let mut expected_sum = proof.claimed_sum;
+    let sub_claim = verify_rounds(
+        proof.claimed_sum,
+        num_vars,
+        &proof.proof_polynomials,
+        transcript,
+    )?;
 
-    for poly in proof.proof_polynomials {
-        let poly = MultilinearPoly::new(poly.to_vec());
+    verify_final_evaluation(sub_claim, proof.final_evaluation, transcript)
+}
 
-        if poly.evaluation.iter().sum::<Fq>() != expected_sum {
-            return false;
-        }
+/// Checks the round polynomials against `claimed_sum`, replaying the
+/// challenges from `transcript`. The final oracle query is left to the caller.
+pub(crate) fn verify_rounds<F: PrimeField>(
+    claimed_sum: F,
+    num_vars: usize,
+    proof_polynomials: &[Vec<F>],
+    transcript: &mut Transcript<F>,
+) -> Result<EvaluationClaim<F>, SumCheckError<F>> {
+    if proof_polynomials.len() != num_vars {
+        return Err(SumCheckError::WrongNumberOfRounds {
+            expected: num_vars,
+            got: proof_polynomials.len(),
+        });
+    }
 
-        transcript.append(&fq_vec_to_bytes(&poly.evaluation));
-        let random_challenge = transcript.get_random_challenge();
+    let mut verifier = SumCheckVerifier::new(claimed_sum, num_vars, transcript);
 
-        expected_sum =
-            poly.evaluation[0] + random_challenge * (poly.evaluation[1] - poly.evaluation[0]);
+    for poly in proof_polynomials {
+        verifier.receive_message(poly)?;
+    }
 
-        current_poly = current_poly.partial_evaluate(0, &random_challenge);
+    verifier.into_sub_claim()
+}
 
-        random_challenges.push(random_challenge);
+/// Checks the prover's `final_evaluation` against the claim left by the last
+/// round and absorbs it, leaving a single opening claim for that value.
+pub(crate) fn verify_final_evaluation<F: PrimeField>(
+    sub_claim: EvaluationClaim<F>,
+    final_evaluation: F,
+    transcript: &mut Transcript<F>,
+) -> Result<EvaluationClaim<F>, SumCheckError<F>> {
+    if sub_claim.value != final_evaluation {
+        return Err(SumCheckError::FinalEvaluationMismatch {
+            expected: sub_claim.value,
+            got: final_evaluation,
+        });
+    }
+
+    transcript.append(&fq_vec_to_bytes(&[final_evaluation]));
+
+    Ok(sub_claim)
+}
+
+/// Absorbs a domain-separation label, prefixed with its length so that no
+/// label is a prefix of another's framing.
+pub(crate) fn append_label<F: PrimeField>(transcript: &mut Transcript<F>, label: &[u8]) {
+    transcript.append(&[&(label.len() as u64).to_le_bytes()[..], label].concat());
+}
+
+pub(crate) fn append_statement<F: PrimeField>(
+    transcript: &mut Transcript<F>,
+    num_vars: usize,
+    claimed_sum: F,
+) {
+    transcript.append(&fq_vec_to_bytes(&[F::from(num_vars as u64), claimed_sum]));
+}
+
+/// The label of layer `layer` in a multi-layer GKR proof, which keeps a
+/// layer's round polynomials from being replayed at another position.
+pub fn gkr_layer_label(layer: usize) -> Vec<u8> {
+    [b"gkr_layer".as_slice(), &(layer as u64).to_le_bytes()].concat()
+}
+
+/// Frames a GKR layer's sum-check with its number of variables and claimed
+/// sum, so that its round polynomials are bound to the statement.
+fn append_gkr_layer_tag<F: PrimeField>(
+    transcript: &mut Transcript<F>,
+    num_vars: usize,
+    claimed_sum: F,
+) {
+    transcript.append_with_label(
+        b"gkr_layer_statement",
+        &fq_vec_to_bytes(&[F::from(num_vars as u64), claimed_sum]),
+    );
+}
+
+/// Runs one GKR layer's sum-check over `composed_polynomial`, which ranges
+/// over `(b, c)`, and evaluates `next_layer` at the two halves of the final
+/// point. Each round polynomial is sent as its evaluations at
+/// `0, 1, ..., d`, where `d` is the degree in that round's variable, as
+/// `gkr_verify_rounds` expects. The claimed sum is
+/// absorbed before the first round, so the challenges depend on it.
+///
+/// `composed_polynomial` may be a `SumPoly` or a `VirtualPolynomial`; both
+/// send the same round polynomials for the same polynomial.
+///
+/// Fails if the factors do not all range over the same variables, twice as
+/// many as `next_layer` has, or if `claimed_sum` is not the sum of
+/// `composed_polynomial` over the hypercube.
+pub fn gkr_prove<F: PrimeField, P: ComposedPolynomial<F>>(
+    claimed_sum: F,
+    composed_polynomial: &P,
+    next_layer: &MultilinearPoly<F>,
+    transcript: &mut Transcript<F>,
+    label: Option<&[u8]>,
+) -> Result<GkrProof<F>, GkrProveError<F>> {
+    let num_rounds = checked_num_vars(composed_polynomial)?;
+    if next_layer.num_vars().checked_mul(2) != Some(num_rounds) {
+        return Err(GkrProveError::NextLayerMismatch {
+            num_rounds,
+            got: next_layer.num_vars(),
+        });
     }
 
-    let poly_eval_sum = polynomial.evaluate(random_challenges);
-    expected_sum == poly_eval_sum
+    let GkrLayerRounds {
+        proof_polynomials,
+        random_challenges,
+        ..
+    } = gkr_prove_rounds(claimed_sum, composed_polynomial, transcript, label)?;
+
+    let GkrLayerPoint { b_point, c_point } =
+        split_gkr_point(&random_challenges, next_layer.num_vars())
+            .expect("the rounds bind two copies of the next layer's variables");
+    let w_b = next_layer.evaluate(&b_point);
+    let w_c = next_layer.evaluate(&c_point);
+
+    Ok(GkrProof {
+        proof_polynomials,
+        claimed_sum,
+        random_challenges,
+        w_b,
+        w_c,
+    })
+}
+
+/// The composed polynomial's number of variables, or why it has none, with
+/// its shape.
+fn checked_num_vars<F: PrimeField, P: ComposedPolynomial<F>>(
+    composed_polynomial: &P,
+) -> Result<usize, GkrProveError<F>> {
+    composed_polynomial
+        .num_vars()
+        .map_err(|error| GkrProveError::Shape {
+            error,
+            shape: composed_polynomial.shape(),
+        })
+}
+
+/// The prover's side of a GKR layer's rounds: the sum they started from,
+/// the round polynomials it sent and the challenges it received.
+#[derive(Debug, Clone, PartialEq)]
+pub struct GkrLayerRounds<F: PrimeField> {
+    pub claimed_sum: F,
+    pub proof_polynomials: Vec<Vec<F>>,
+    pub random_challenges: Vec<F>,
+}
+
+/// The rounds of `gkr_prove` on their own, for layers whose final point does
+/// not split into two halves of one next-layer point. The claims on the
+/// layer below are left to the caller.
+///
+/// Fails if the factors do not all range over the same variables or if
+/// `claimed_sum` is not the sum of `composed_polynomial` over the hypercube.
+pub fn gkr_prove_rounds<F: PrimeField, P: ComposedPolynomial<F>>(
+    claimed_sum: F,
+    composed_polynomial: &P,
+    transcript: &mut Transcript<F>,
+    label: Option<&[u8]>,
+) -> Result<GkrLayerRounds<F>, GkrProveError<F>> {
+    let num_rounds = checked_num_vars(composed_polynomial)?;
+
+    let sum: F = composed_polynomial.hypercube_sum();
+    if sum != claimed_sum {
+        return Err(GkrProveError::ClaimMismatch {
+            expected: sum,
+            got: claimed_sum,
+        });
+    }
+
+    Ok(run_gkr_rounds(
+        num_rounds,
+        claimed_sum,
+        composed_polynomial,
+        transcript,
+        label,
+    ))
+}
+
+/// `gkr_prove_rounds` claiming the polynomial's own sum, which it takes one
+/// pass over the tables to find. The sum comes back in the rounds.
+pub(crate) fn gkr_prove_layer_rounds<F: PrimeField, P: ComposedPolynomial<F>>(
+    composed_polynomial: &P,
+    transcript: &mut Transcript<F>,
+    label: Option<&[u8]>,
+) -> Result<GkrLayerRounds<F>, GkrProveError<F>> {
+    let num_rounds = checked_num_vars(composed_polynomial)?;
+    let claimed_sum = composed_polynomial.hypercube_sum();
+
+    Ok(run_gkr_rounds(
+        num_rounds,
+        claimed_sum,
+        composed_polynomial,
+        transcript,
+        label,
+    ))
 }
 
-////! verified correct
-pub fn gkr_prove(
-    claimed_sum: Fq,
-    composed_polynomial: &SumPoly<Fq>,
-    transcript: &mut Transcript<Fq>,
-) -> GkrProof {
-    let num_rounds = composed_polynomial.polys[0].evaluation[0].num_of_vars;
-    let mut proof_polynomials = Vec::with_capacity(num_rounds as usize);
-    let mut current_poly = composed_polynomial.clone();
+fn run_gkr_rounds<F: PrimeField, P: ComposedPolynomial<F>>(
+    num_rounds: usize,
+    claimed_sum: F,
+    composed_polynomial: &P,
+    transcript: &mut Transcript<F>,
+    label: Option<&[u8]>,
+) -> GkrLayerRounds<F> {
+    if let Some(label) = label {
+        append_label(transcript, label);
+    }
+    append_gkr_layer_tag(transcript, num_rounds, claimed_sum);
+
+    let mut proof_polynomials = Vec::with_capacity(num_rounds);
+    // Stays borrowed until the first challenge, whose bind halves the tables;
+    // later rounds fold that copy in place.
+    let mut current_poly: Option<P> = None;
     let mut random_challenges = Vec::new();
 
     for _ in 0..num_rounds {
-        let proof_poly = get_round_partial_polynomial_proof_gkr(&current_poly); //this is f(b)
+        let round_poly = current_poly.as_ref().unwrap_or(composed_polynomial);
+        let proof_poly = round_poly.round_evaluations(); //this is f(b)
 
         transcript.append(&fq_vec_to_bytes(&proof_poly));
 
         proof_polynomials.push(proof_poly);
 
-        let random_challenge = transcript.get_random_challenge(); //this is r1 and r2
+        let random_challenge = transcript.get_uniform_challenge(); //this is r1 and r2
 
         random_challenges.push(random_challenge);
 
-        current_poly = current_poly.partial_evaluate(&random_challenge);
+        match current_poly.as_mut() {
+            Some(poly) => poly.fold_in_place(&random_challenge),
+            None => current_poly = Some(composed_polynomial.partial_evaluate(&random_challenge)),
+        }
     }
 
-    GkrProof {
-        proof_polynomials,
+    GkrLayerRounds {
         claimed_sum,
+        proof_polynomials,
         random_challenges,
     }
 }
 
-////! verified correct
-pub fn gkr_verify(
-    round_polys: Vec<Vec<Fq>>,
-    mut claimed_sum: Fq,
-    mut transcript: Transcript<Fq>,
-) -> GkrVerify {
-    let mut random_challenges = Vec::new();
+/// Checks the round polynomials only, failing at the first round whose sum
+/// does not match or whose polynomial has degree above `degree`. The
+/// returned claim is about the composed polynomial, which the caller must
+/// still check with `gkr_verify_final`.
+pub fn gkr_verify_rounds<F: PrimeField>(
+    round_polys: Vec<Vec<F>>,
+    claimed_sum: F,
+    degree: usize,
+    transcript: &mut Transcript<F>,
+    label: Option<&[u8]>,
+) -> Result<EvaluationClaim<F>, GkrError<F>> {
+    if let Some(label) = label {
+        append_label(transcript, label);
+    }
+
+    verify_gkr_rounds(&round_polys, claimed_sum, degree, transcript)
+}
+
+/// `gkr_verify_rounds` with a degree bound per round, `degrees[i]` for the
+/// polynomial of round `i`, such as `SumPoly::degree_in_variable(i)`. Fails
+/// unless there is one bound per round.
+pub fn gkr_verify_rounds_with_degrees<F: PrimeField>(
+    round_polys: Vec<Vec<F>>,
+    claimed_sum: F,
+    degrees: &[usize],
+    transcript: &mut Transcript<F>,
+    label: Option<&[u8]>,
+) -> Result<EvaluationClaim<F>, GkrError<F>> {
+    if degrees.len() != round_polys.len() {
+        return Err(GkrError::WrongNumberOfRounds {
+            expected: degrees.len(),
+            got: round_polys.len(),
+        });
+    }
 
-    for poly in round_polys {
-        let round_poly = MultilinearPoly::new(poly.to_vec());
+    if let Some(label) = label {
+        append_label(transcript, label);
+    }
+
+    verify_gkr_rounds_with_buffer(
+        &round_polys,
+        claimed_sum,
+        |round| degrees[round],
+        transcript,
+        &mut Vec::new(),
+    )
+}
+
+/// Checks the claim left by `gkr_verify_rounds` against the composed
+/// polynomial's evaluation at the sampled point.
+pub fn gkr_verify_final<F: PrimeField>(
+    final_claim: F,
+    oracle_eval: F,
+) -> Result<(), SumCheckError<F>> {
+    if final_claim != oracle_eval {
+        return Err(SumCheckError::FinalEvaluationMismatch {
+            expected: final_claim,
+            got: oracle_eval,
+        });
+    }
+
+    Ok(())
+}
+
+/// The round polynomials of one GKR layer, with the degree bound they are
+/// checked against.
+#[derive(Debug, Clone, PartialEq)]
+pub struct GkrRoundPolys<F: PrimeField> {
+    pub round_polys: Vec<Vec<F>>,
+    pub degree: usize,
+}
+
+/// Verifies a sequence of GKR layers on one transcript, in the order the
+/// prover ran them, where `claims[i]` is the claimed sum of layer `i`. Stops
+/// at the first failing layer. The prover must label layer `i` with
+/// `gkr_layer_label(i)`.
+pub fn gkr_verify_batch<F: PrimeField>(
+    proofs: &[GkrRoundPolys<F>],
+    claims: &[F],
+    transcript: &mut Transcript<F>,
+) -> Result<Vec<EvaluationClaim<F>>, GkrError<F>> {
+    if claims.len() != proofs.len() {
+        return Err(GkrError::WrongNumberOfClaims {
+            expected: proofs.len(),
+            got: claims.len(),
+        });
+    }
+
+    let mut bytes = Vec::new();
+
+    proofs
+        .iter()
+        .zip(claims)
+        .enumerate()
+        .map(|(layer, (proof, claimed_sum))| {
+            append_label(transcript, &gkr_layer_label(layer));
 
-        let f_b_0 = round_poly.evaluate(vec![Fq::from(0)]);
-        let f_b_1 = round_poly.evaluate(vec![Fq::from(1)]);
+            verify_gkr_rounds_with_buffer(
+                &proof.round_polys,
+                *claimed_sum,
+                |_| proof.degree,
+                transcript,
+                &mut bytes,
+            )
+            .map_err(|error| GkrError::LayerFailed {
+                layer,
+                source: Box::new(error),
+            })
+        })
+        .collect()
+}
+
+/// Checks GKR round polynomials against a running claim. Each is sent as its
+/// evaluations at `0, 1, ..., d` for some `d` between one and `degree`, the
+/// degree of the composed polynomial in the bound variable; a round whose
+/// variable appears in fewer factors sends fewer.
+pub(crate) fn verify_gkr_rounds<F: PrimeField>(
+    round_polys: &[Vec<F>],
+    claimed_sum: F,
+    degree: usize,
+    transcript: &mut Transcript<F>,
+) -> Result<EvaluationClaim<F>, GkrError<F>> {
+    verify_gkr_rounds_with_buffer(
+        round_polys,
+        claimed_sum,
+        |_| degree,
+        transcript,
+        &mut Vec::new(),
+    )
+}
+
+/// `verify_gkr_rounds` with the bound of each round given by `degree`,
+/// encoding each round message into `bytes` so that callers checking many
+/// layers reuse one buffer.
+fn verify_gkr_rounds_with_buffer<F: PrimeField>(
+    round_polys: &[Vec<F>],
+    mut claimed_sum: F,
+    degree: impl Fn(usize) -> usize,
+    transcript: &mut Transcript<F>,
+    bytes: &mut Vec<u8>,
+) -> Result<EvaluationClaim<F>, GkrError<F>> {
+    append_gkr_layer_tag(transcript, round_polys.len(), claimed_sum);
+
+    let mut domain = BarycentricDomain::new(1);
+    let mut random_challenges = Vec::with_capacity(round_polys.len());
+
+    for (round, poly) in round_polys.iter().enumerate() {
+        let bound = degree(round).max(1);
+        if poly.len() < 2 || poly.len() > bound + 1 {
+            return Err(GkrError::InvalidRoundPolynomialLength {
+                round,
+                expected: bound + 1,
+                got: poly.len(),
+            });
+        }
+
+        if domain.size() != poly.len() {
+            domain = BarycentricDomain::new(poly.len() - 1);
+        }
+
+        let (f_b_0, f_b_1) = (poly[0], poly[1]);
 
         if f_b_0 + f_b_1 != claimed_sum {
-            return GkrVerify {
-                verified: false,
-                final_claimed_sum: Fq::from(0),
-                random_challenges: vec![Fq::from(0)],
-            };
+            return Err(GkrError::RoundSumMismatch {
+                round,
+                expected: claimed_sum,
+                f_b_0,
+                f_b_1,
+            });
         }
 
-        transcript.append(&fq_vec_to_bytes(&poly));
+        bytes.clear();
+        bytes.extend(poly.iter().flat_map(|x| x.into_bigint().to_bytes_le()));
+        transcript.append(bytes);
 
-        let r_c = transcript.get_random_challenge();
+        let r_c = transcript.get_uniform_challenge();
 
         random_challenges.push(r_c);
 
-        claimed_sum = round_poly.evaluate(vec![r_c]); //next expected sum
+        claimed_sum = domain.evaluate(poly, r_c);
     }
 
-    GkrVerify {
-        verified: true,
-        final_claimed_sum: claimed_sum,
-        random_challenges,
-    }
+    Ok(EvaluationClaim {
+        point: random_challenges,
+        value: claimed_sum,
+    })
 }
 
 #[cfg(test)]
 mod test {
-    use ark_bn254::Fq;
-    use multilinear_polynomial::multilinear_polynomial_evaluation::MultilinearPoly;
+    use ark_bn254::{Fq, Fr};
+    use ark_ff::PrimeField;
+    use fiat_shamir::fiat_shamir_transcript::{fq_vec_to_bytes, Transcript};
+    use multilinear_polynomial::{
+        composed_polynomial::{ComposedPolynomial, ProductPoly, ShapeError, SumPoly, SumPolyShape},
+        multilinear_polynomial_evaluation::MultilinearPoly,
+        virtual_polynomial::VirtualPolynomial,
+    };
+    use rand::{rngs::StdRng, Rng, SeedableRng};
+    use univariate_polynomial::{
+        barycentric::BarycentricDomain,
+        univariate_polynomial_dense::{InterpolationError, UnivariatePoly},
+    };
 
-    use crate::sum_check_protocol::{prove, verify, Proof};
+    use crate::{
+        sum_check_error::{GkrError, GkrProveError, ProofFormatError, SumCheckError},
+        sum_check_protocol::{
+            compute_sum, gkr_layer_label, gkr_prove, gkr_prove_rounds, gkr_verify_batch,
+            gkr_verify_final, gkr_verify_rounds, gkr_verify_rounds_with_degrees, prove,
+            prove_labeled, prove_with_claim, prove_with_options, prove_with_transcript,
+            split_gkr_point, verify, verify_labeled, verify_partial, verify_with_options,
+            verify_with_oracle, verify_with_transcript, BindingOrder, GkrProof, GkrRoundPolys,
+            Proof,
+        },
+        test_utils::{check_property, sample_poly},
+    };
 
     #[test]
     fn test_valid_proving_and_verification() {
-        let initial_polynomial = MultilinearPoly::new(vec![
-            Fq::from(0),
-            Fq::from(0),
-            Fq::from(0),
-            Fq::from(2),
-            Fq::from(0),
-            Fq::from(10),
-            Fq::from(0),
-            Fq::from(17),
-        ]);
+        check_property(16, |rng| {
+            let initial_polynomial = MultilinearPoly::<Fq>::rand(rng.gen_range(1..=8), rng);
 
-        let proof = prove(&initial_polynomial);
+            let proof = prove(&initial_polynomial);
 
-        let is_verified = verify(&initial_polynomial, proof);
+            let result = verify(&initial_polynomial, proof);
 
-        assert_eq!(is_verified, true);
+            assert_eq!(result, Ok(()));
+        });
     }
 
     #[test]
     fn test_invalid_proof_doesnt_verify() {
-        let initial_polynomial =
-            MultilinearPoly::new(vec![Fq::from(0), Fq::from(3), Fq::from(2), Fq::from(5)]);
+        let initial_polynomial = MultilinearPoly::<Fq>::from(vec![0, 3, 2, 5]);
 
         let false_proof = Proof {
             claimed_sum: Fq::from(20),
@@ -220,10 +1018,1411 @@ mod test {
                 vec![Fq::from(3), Fq::from(9)],
                 vec![Fq::from(1), Fq::from(2)],
             ],
+            final_evaluation: Fq::from(0),
         };
 
-        let is_verified = verify(&initial_polynomial, false_proof);
+        let result = verify(&initial_polynomial, false_proof);
+
+        assert_eq!(
+            result,
+            Err(SumCheckError::RoundSumMismatch {
+                round: 0,
+                expected: Fq::from(20),
+                got: Fq::from(12),
+            })
+        );
+    }
+
+    #[test]
+    fn test_tampered_last_round_fails_final_evaluation() {
+        let initial_polynomial =
+            MultilinearPoly::new(vec![Fq::from(0), Fq::from(3), Fq::from(2), Fq::from(5)]);
+
+        let mut proof = prove(&initial_polynomial);
+        let last_round = proof.proof_polynomials.last_mut().unwrap();
+        last_round[0] += Fq::from(1);
+        last_round[1] -= Fq::from(1);
+
+        let result = verify(&initial_polynomial, proof);
+
+        assert!(matches!(
+            result,
+            Err(SumCheckError::FinalEvaluationMismatch { .. })
+        ));
+    }
+
+    #[test]
+    fn test_tampered_final_evaluation_is_rejected() {
+        let initial_polynomial =
+            MultilinearPoly::new(vec![Fq::from(0), Fq::from(3), Fq::from(2), Fq::from(5)]);
+
+        let mut proof = prove(&initial_polynomial);
+        let honest_final_evaluation = proof.final_evaluation;
+        proof.final_evaluation += Fq::from(1);
+
+        let result = verify(&initial_polynomial, proof);
+
+        assert_eq!(
+            result,
+            Err(SumCheckError::FinalEvaluationMismatch {
+                expected: honest_final_evaluation,
+                got: honest_final_evaluation + Fq::from(1),
+            })
+        );
+    }
+
+    #[test]
+    fn test_labeled_proof_only_verifies_under_its_label() {
+        let initial_polynomial = sample_poly();
+
+        let proof = prove_labeled(b"app-a", &initial_polynomial);
+
+        assert!(verify_labeled(b"app-a", &initial_polynomial, proof.clone()).is_ok());
+        assert!(verify_labeled(b"app-b", &initial_polynomial, proof.clone()).is_err());
+        assert!(verify(&initial_polynomial, proof).is_err());
+    }
+
+    #[test]
+    fn test_both_binding_orders_verify() {
+        let initial_polynomial = sample_poly();
+
+        let msb_proof = prove_with_options(&initial_polynomial, BindingOrder::MostSignificantFirst);
+        let lsb_proof =
+            prove_with_options(&initial_polynomial, BindingOrder::LeastSignificantFirst);
+
+        assert_eq!(
+            msb_proof.proof_polynomials,
+            prove(&initial_polynomial).proof_polynomials
+        );
+        assert_ne!(msb_proof.proof_polynomials, lsb_proof.proof_polynomials);
+
+        assert!(verify_with_options(
+            &initial_polynomial,
+            msb_proof,
+            BindingOrder::MostSignificantFirst
+        )
+        .is_ok());
+        assert!(verify_with_options(
+            &initial_polynomial,
+            lsb_proof.clone(),
+            BindingOrder::LeastSignificantFirst
+        )
+        .is_ok());
+        assert!(verify_with_options(
+            &initial_polynomial,
+            lsb_proof,
+            BindingOrder::MostSignificantFirst
+        )
+        .is_err());
+    }
+
+    fn next_layer() -> MultilinearPoly<Fq> {
+        MultilinearPoly::new(vec![Fq::from(3), Fq::from(8)])
+    }
+
+    fn quadratic_sum_poly() -> SumPoly<Fq> {
+        // Both factors of each product depend on the first variable, so every
+        // round polynomial is genuinely quadratic.
+        SumPoly::new(vec![
+            ProductPoly::new(vec![
+                vec![Fq::from(1), Fq::from(2), Fq::from(3), Fq::from(5)],
+                vec![Fq::from(2), Fq::from(1), Fq::from(7), Fq::from(4)],
+            ]),
+            ProductPoly::new(vec![
+                vec![Fq::from(0), Fq::from(3), Fq::from(1), Fq::from(6)],
+                vec![Fq::from(1), Fq::from(1), Fq::from(5), Fq::from(2)],
+            ]),
+        ])
+    }
+
+    #[test]
+    fn test_gkr_round_polynomials_are_exact_for_quadratic_rounds() {
+        let composed_polynomial = quadratic_sum_poly();
+        let claimed_sum: Fq = composed_polynomial.reduce().hypercube_sum();
+
+        let proof = gkr_prove(
+            claimed_sum,
+            &composed_polynomial,
+            &next_layer(),
+            &mut Transcript::new(),
+            None,
+        )
+        .unwrap();
+
+        assert!(proof.proof_polynomials.iter().all(|poly| poly.len() == 3));
+        assert_eq!(
+            (proof.w_b, proof.w_c),
+            (
+                next_layer().evaluate(&[proof.random_challenges[0]]),
+                next_layer().evaluate(&[proof.random_challenges[1]])
+            )
+        );
+
+        let result = gkr_verify_rounds(
+            proof.proof_polynomials,
+            claimed_sum,
+            2,
+            &mut Transcript::new(),
+            None,
+        )
+        .unwrap();
+        assert_eq!(result.point, proof.random_challenges);
+        assert_eq!(result.value, composed_polynomial.evaluate(&result.point));
+    }
+
+    fn prove_and_verify_gkr_layer<F: PrimeField>() {
+        let table = |seed: u64| -> Vec<F> { (0..16u64).map(|i| F::from(i * seed + 1)).collect() };
+        let composed_polynomial = SumPoly::new(vec![
+            ProductPoly::new(vec![table(1), table(2)]),
+            ProductPoly::new(vec![table(3), table(4)]),
+        ]);
+        let layer = MultilinearPoly::new(vec![F::from(2), F::from(5), F::from(1), F::from(9)]);
+        let claimed_sum: F = composed_polynomial.reduce().hypercube_sum();
+
+        let proof = gkr_prove(
+            claimed_sum,
+            &composed_polynomial,
+            &layer,
+            &mut Transcript::new(),
+            None,
+        )
+        .unwrap();
+
+        let result = gkr_verify_rounds(
+            proof.proof_polynomials,
+            claimed_sum,
+            2,
+            &mut Transcript::new(),
+            None,
+        )
+        .unwrap();
+        let oracle_eval = composed_polynomial.evaluate(&result.point);
+
+        assert_eq!(gkr_verify_final(result.value, oracle_eval), Ok(()));
+    }
+
+    #[test]
+    fn test_gkr_layer_over_several_fields() {
+        // BN254's scalar field is also Grumpkin's base field.
+        prove_and_verify_gkr_layer::<Fq>();
+        prove_and_verify_gkr_layer::<Fr>();
+    }
+
+    #[test]
+    fn test_gkr_proof_matches_sequential_reference() {
+        let tables: Vec<Vec<Fq>> = (0..4u64)
+            .map(|seed| (0..64u64).map(|i| Fq::from(i * i + seed * i + 1)).collect())
+            .collect();
+        let layer = MultilinearPoly::new((0..8u64).map(|i| Fq::from(2 * i + 5)).collect());
+        let composed_polynomial = SumPoly::new(vec![
+            ProductPoly::new(vec![tables[0].clone(), tables[1].clone()]),
+            ProductPoly::new(vec![tables[2].clone(), tables[3].clone()]),
+        ]);
+        let claimed_sum: Fq = composed_polynomial.reduce().hypercube_sum();
+
+        // A plain sequential prover over the raw tables, independent of the
+        // `parallel` feature.
+        let mut transcript = Transcript::<Fq>::new();
+        transcript.append_with_label(
+            b"gkr_layer_statement",
+            &fq_vec_to_bytes(&[Fq::from(6), claimed_sum]),
+        );
+        let mut tables = tables;
+        let mut expected_polynomials = vec![];
+        while tables[0].len() > 1 {
+            let half = tables[0].len() / 2;
+            let bind = |table: &[Fq], t: Fq| -> Vec<Fq> {
+                (0..half)
+                    .map(|j| table[j] + t * (table[j + half] - table[j]))
+                    .collect()
+            };
+            let round: Vec<Fq> = (0..3u64)
+                .map(|i| {
+                    let t = Fq::from(i);
+                    let bound: Vec<_> = tables.iter().map(|table| bind(table, t)).collect();
+
+                    (0..half)
+                        .map(|j| bound[0][j] * bound[1][j] + bound[2][j] * bound[3][j])
+                        .sum()
+                })
+                .collect();
+
+            transcript.append(&fq_vec_to_bytes(&round));
+            let challenge = transcript.get_uniform_challenge();
+
+            tables = tables.iter().map(|table| bind(table, challenge)).collect();
+            expected_polynomials.push(round);
+        }
+
+        let proof = gkr_prove(
+            claimed_sum,
+            &composed_polynomial,
+            &layer,
+            &mut Transcript::new(),
+            None,
+        )
+        .unwrap();
+
+        assert_eq!(proof.proof_polynomials, expected_polynomials);
+    }
+
+    #[test]
+    fn test_gkr_round_evaluations_agree_with_legacy_coefficients() {
+        let composed_polynomial = quadratic_sum_poly();
+        let claimed_sum: Fq = composed_polynomial.reduce().hypercube_sum();
+
+        let proof = gkr_prove(
+            claimed_sum,
+            &composed_polynomial,
+            &next_layer(),
+            &mut Transcript::new(),
+            None,
+        )
+        .unwrap();
+
+        gkr_verify_rounds(
+            proof.proof_polynomials.clone(),
+            claimed_sum,
+            2,
+            &mut Transcript::new(),
+            None,
+        )
+        .unwrap();
+
+        let domain = BarycentricDomain::new(2);
+        let challenge = Fq::from(987654321u64);
+        for (evals, coefficients) in proof
+            .proof_polynomials
+            .iter()
+            .zip(proof.coefficient_polynomials().unwrap())
+        {
+            assert_eq!(
+                domain.evaluate(evals, challenge),
+                UnivariatePoly::new(coefficients).evaluate(challenge)
+            );
+        }
+
+        let mut tampered = proof;
+        tampered.proof_polynomials[0].clear();
+        assert_eq!(
+            tampered.coefficient_polynomials(),
+            Err(InterpolationError::EmptyInput)
+        );
+    }
+
+    #[test]
+    fn test_gkr_round_evaluations_match_binding_each_point() {
+        let table = |seed: u64| -> Vec<Fq> { (0..16u64).map(|i| Fq::from(i * seed + 3)).collect() };
+        let mut composed_polynomial = SumPoly::new(vec![
+            ProductPoly::new(vec![table(1)]),
+            ProductPoly::new(vec![table(2), table(5), table(7)]),
+        ]);
+
+        for round in 0..4u64 {
+            let expected: Vec<Fq> = (0..=3u64)
+                .map(|i| {
+                    composed_polynomial
+                        .partial_evaluate(&Fq::from(i))
+                        .reduce()
+                        .hypercube_sum()
+                })
+                .collect();
+
+            assert_eq!(
+                ComposedPolynomial::round_evaluations(&composed_polynomial),
+                expected
+            );
+
+            // The coefficient form agrees with the evaluations sent.
+            let univariate = composed_polynomial.round_univariate();
+            assert!((0..=3u64).all(|i| univariate.evaluate(Fq::from(i)) == expected[i as usize]));
+
+            composed_polynomial.fold_in_place(&Fq::from(10 + round));
+        }
+    }
+
+    #[test]
+    fn test_gkr_final_point_splits_the_same_way_on_both_sides() {
+        let composed_polynomial = quadratic_sum_poly();
+        let claimed_sum: Fq = composed_polynomial.reduce().hypercube_sum();
+
+        let proof = gkr_prove(
+            claimed_sum,
+            &composed_polynomial,
+            &next_layer(),
+            &mut Transcript::new(),
+            None,
+        )
+        .unwrap();
+        let result = gkr_verify_rounds(
+            proof.proof_polynomials.clone(),
+            claimed_sum,
+            2,
+            &mut Transcript::new(),
+            None,
+        )
+        .unwrap();
+
+        let point = split_gkr_point(&result.point, 1).unwrap();
+        assert_eq!(point, proof.split_point(1).unwrap());
+        assert_eq!(point.b_point, result.point[..1]);
+        assert_eq!(point.c_point, result.point[1..]);
+        assert_eq!(
+            (proof.w_b, proof.w_c),
+            (
+                next_layer().evaluate(&point.b_point),
+                next_layer().evaluate(&point.c_point)
+            )
+        );
+
+        let odd = [Fq::from(1), Fq::from(2), Fq::from(3)];
+        assert_eq!(
+            split_gkr_point(&odd, 1),
+            Err(GkrError::WrongPointLength {
+                expected: 2,
+                got: 3
+            })
+        );
+        assert!(split_gkr_point(&odd, 2).is_err());
+    }
+
+    #[test]
+    fn test_gkr_prove_checks_and_absorbs_the_claimed_sum() {
+        let composed_polynomial = quadratic_sum_poly();
+        let claimed_sum: Fq = composed_polynomial.reduce().hypercube_sum();
+
+        assert_eq!(
+            gkr_prove(
+                claimed_sum + Fq::from(1),
+                &composed_polynomial,
+                &next_layer(),
+                &mut Transcript::new(),
+                None,
+            ),
+            Err(GkrProveError::ClaimMismatch {
+                expected: claimed_sum,
+                got: claimed_sum + Fq::from(1),
+            })
+        );
+
+        let mut proof = gkr_prove(
+            claimed_sum,
+            &composed_polynomial,
+            &next_layer(),
+            &mut Transcript::new(),
+            None,
+        )
+        .unwrap();
+        proof.validate_challenges(&mut Transcript::new()).unwrap();
+
+        // The first challenge already depends on the claim.
+        proof.claimed_sum += Fq::from(1);
+        assert_eq!(
+            proof.validate_challenges(&mut Transcript::new()),
+            Err(SumCheckError::ChallengeMismatch { round: 0 })
+        );
+    }
+
+    #[test]
+    fn test_gkr_prove_rejects_malformed_composed_polynomials() {
+        let prove_layer = |composed_polynomial: &SumPoly<Fq>| {
+            gkr_prove(
+                Fq::from(0),
+                composed_polynomial,
+                &next_layer(),
+                &mut Transcript::new(),
+                None,
+            )
+        };
+
+        assert_eq!(
+            prove_layer(&SumPoly { polys: vec![] }),
+            Err(GkrProveError::Shape {
+                error: ShapeError::EmptySumPoly,
+                shape: SumPolyShape { terms: vec![] }
+            })
+        );
+
+        let mismatched = SumPoly {
+            polys: vec![ProductPoly {
+                evaluation: vec![
+                    MultilinearPoly::new(vec![Fq::from(1); 4]).into(),
+                    MultilinearPoly::new(vec![Fq::from(1); 2]).into(),
+                ],
+            }],
+        };
+        assert_eq!(
+            prove_layer(&mismatched),
+            Err(GkrProveError::Shape {
+                error: ShapeError::MismatchedNumVars {
+                    term: 0,
+                    factor: 1,
+                    expected: 2,
+                    got: 1
+                },
+                shape: mismatched.shape()
+            })
+        );
+        assert_eq!(
+            prove_layer(&mismatched).unwrap_err().to_string(),
+            "malformed composed polynomial SumPoly { terms: 1, term0: [mle(2 vars), mle(1 var)] }: \
+             factor 1 of term 0 has 1 variables, expected 2"
+        );
+
+        let too_wide = SumPoly::new(vec![ProductPoly::new(vec![vec![Fq::from(1); 8]])]);
+        assert_eq!(
+            prove_layer(&too_wide),
+            Err(GkrProveError::NextLayerMismatch {
+                num_rounds: 3,
+                got: 1
+            })
+        );
+    }
+
+    #[test]
+    fn test_gkr_verify_reports_the_failing_round() {
+        let composed_polynomial = quadratic_sum_poly();
+        let claimed_sum: Fq = composed_polynomial.reduce().hypercube_sum();
+
+        let mut proof = gkr_prove(
+            claimed_sum,
+            &composed_polynomial,
+            &next_layer(),
+            &mut Transcript::new(),
+            None,
+        )
+        .unwrap();
+        proof.proof_polynomials[1][0] += Fq::from(1);
+
+        match gkr_verify_rounds(
+            proof.proof_polynomials,
+            claimed_sum,
+            2,
+            &mut Transcript::new(),
+            None,
+        ) {
+            Err(GkrError::RoundSumMismatch {
+                round,
+                expected,
+                f_b_0,
+                f_b_1,
+            }) => {
+                assert_eq!(round, 1);
+                assert_ne!(f_b_0 + f_b_1, expected);
+            }
+            other => panic!("expected round 1 to fail, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_gkr_verify_rejects_round_polynomials_above_the_degree_bound() {
+        let composed_polynomial = quadratic_sum_poly();
+        let claimed_sum: Fq = composed_polynomial.reduce().hypercube_sum();
+
+        let proof = gkr_prove(
+            claimed_sum,
+            &composed_polynomial,
+            &next_layer(),
+            &mut Transcript::new(),
+            None,
+        )
+        .unwrap();
+
+        let result = gkr_verify_rounds(
+            proof.proof_polynomials,
+            claimed_sum,
+            1,
+            &mut Transcript::new(),
+            None,
+        );
+
+        assert_eq!(
+            result,
+            Err(GkrError::InvalidRoundPolynomialLength {
+                round: 0,
+                expected: 2,
+                got: 3
+            })
+        );
+    }
+
+    #[test]
+    fn test_gkr_round_polynomials_follow_the_largest_term() {
+        let mut rng = StdRng::seed_from_u64(88);
+        let composed_polynomial = SumPoly::new(
+            (1..=3)
+                .map(|degree| ProductPoly::rand(3, degree, &mut rng))
+                .collect(),
+        );
+        assert_eq!(composed_polynomial.degree_in_bound_variable(), 3);
+
+        let claimed_sum: Fq = composed_polynomial.reduce().hypercube_sum();
+        let rounds = gkr_prove_rounds(
+            claimed_sum,
+            &composed_polynomial,
+            &mut Transcript::new(),
+            None,
+        )
+        .unwrap();
+        assert!(rounds.proof_polynomials.iter().all(|poly| poly.len() == 4));
+
+        let claim = gkr_verify_rounds(
+            rounds.proof_polynomials.clone(),
+            claimed_sum,
+            3,
+            &mut Transcript::new(),
+            None,
+        )
+        .unwrap();
+        assert_eq!(composed_polynomial.evaluate(&claim.point), claim.value);
+
+        assert_eq!(
+            gkr_verify_rounds(
+                rounds.proof_polynomials,
+                claimed_sum,
+                2,
+                &mut Transcript::new(),
+                None,
+            ),
+            Err(GkrError::InvalidRoundPolynomialLength {
+                round: 0,
+                expected: 3,
+                got: 4
+            })
+        );
+    }
+
+    #[test]
+    fn test_gkr_rounds_send_only_the_points_their_degree_needs() {
+        let mut rng = StdRng::seed_from_u64(94);
+        let [add, mul] = [(); 2].map(|_| MultilinearPoly::<Fq>::rand(4, &mut rng));
+        let ones = MultilinearPoly::new(vec![Fq::from(1); 4]);
+        let w = MultilinearPoly::<Fq>::rand(2, &mut rng);
+        let (w_b, w_c) = (w.tensor(&ones).unwrap(), ones.tensor(&w).unwrap());
+
+        // `add·W(b) + add·W(c) + mul·W(b)·W(c)` has three factors in its
+        // last term but only two that depend on any one variable.
+        let composed_polynomial = SumPoly::zero(4)
+            .add_product(vec![add.clone(), w_b.clone()])
+            .unwrap()
+            .add_product(vec![add, w_c.clone()])
+            .unwrap()
+            .add_product(vec![mul, w_b, w_c])
+            .unwrap();
+        let global_degree = composed_polynomial.degree_in_bound_variable();
+        let degrees: Vec<usize> = (0..4)
+            .map(|index| composed_polynomial.degree_in_variable(index))
+            .collect();
+        assert_eq!(global_degree, 3);
+        assert_eq!(degrees, vec![2; 4]);
+
+        let claimed_sum: Fq = composed_polynomial.reduce().hypercube_sum();
+        let proof = gkr_prove(
+            claimed_sum,
+            &composed_polynomial,
+            &w,
+            &mut Transcript::new(),
+            None,
+        )
+        .unwrap();
+        assert!(proof
+            .proof_polynomials
+            .iter()
+            .all(|poly| poly.len() < global_degree + 1));
+
+        let claim = gkr_verify_rounds_with_degrees(
+            proof.proof_polynomials.clone(),
+            claimed_sum,
+            &degrees,
+            &mut Transcript::new(),
+            None,
+        )
+        .unwrap();
+        assert_eq!(composed_polynomial.evaluate(&claim.point), claim.value);
+
+        // A global bound still accepts the shorter messages.
+        assert_eq!(
+            gkr_verify_rounds(
+                proof.proof_polynomials.clone(),
+                claimed_sum,
+                global_degree,
+                &mut Transcript::new(),
+                None,
+            ),
+            Ok(claim)
+        );
+
+        assert_eq!(
+            gkr_verify_rounds_with_degrees(
+                proof.proof_polynomials.clone(),
+                claimed_sum,
+                &[2, 1, 2, 2],
+                &mut Transcript::new(),
+                None,
+            ),
+            Err(GkrError::InvalidRoundPolynomialLength {
+                round: 1,
+                expected: 2,
+                got: 3
+            })
+        );
+        assert_eq!(
+            gkr_verify_rounds_with_degrees(
+                proof.proof_polynomials,
+                claimed_sum,
+                &degrees[1..],
+                &mut Transcript::new(),
+                None,
+            ),
+            Err(GkrError::WrongNumberOfRounds {
+                expected: 3,
+                got: 4
+            })
+        );
+    }
+
+    #[test]
+    fn test_gkr_virtual_polynomial_sends_the_same_proof() {
+        let mut rng = StdRng::seed_from_u64(90);
+        let w = MultilinearPoly::<Fq>::rand(4, &mut rng).into_evaluations();
+        let [a, b, c] =
+            [(); 3].map(|_| MultilinearPoly::<Fq>::rand(4, &mut rng).into_evaluations());
+
+        // `W` fills four factor slots.
+        let composed_polynomial = SumPoly::new(vec![
+            ProductPoly::new(vec![a, w.clone()]),
+            ProductPoly::new(vec![b, w.clone()]),
+            ProductPoly::new(vec![c, w.clone(), w]),
+        ]);
+        let virtual_polynomial = VirtualPolynomial::try_from(&composed_polynomial).unwrap();
+        assert_eq!(virtual_polynomial.multilinears().len(), 4);
+
+        let claimed_sum: Fq = composed_polynomial.reduce().hypercube_sum();
+        let next_layer = MultilinearPoly::<Fq>::rand(2, &mut rng);
+        let from_sum_poly = gkr_prove(
+            claimed_sum,
+            &composed_polynomial,
+            &next_layer,
+            &mut Transcript::new(),
+            None,
+        );
+        let from_virtual = gkr_prove(
+            claimed_sum,
+            &virtual_polynomial,
+            &next_layer,
+            &mut Transcript::new(),
+            None,
+        );
+        assert!(from_sum_poly.is_ok());
+        assert_eq!(from_virtual, from_sum_poly);
+    }
+
+    #[test]
+    fn test_gkr_zero_terms_do_not_change_the_proof() {
+        let mut rng = StdRng::seed_from_u64(93);
+        let [add, w] = [(); 2].map(|_| MultilinearPoly::<Fq>::rand(4, &mut rng).into_evaluations());
+
+        // A layer with no multiplication gates has a zero `mul·W·W` term,
+        // which the `SumPoly` skips and the `VirtualPolynomial` multiplies out.
+        let composed_polynomial = SumPoly::new(vec![
+            ProductPoly::new(vec![add, w.clone()]),
+            ProductPoly::new(vec![vec![Fq::from(0); 16], w.clone(), w]),
+        ]);
+        let virtual_polynomial = VirtualPolynomial::try_from(&composed_polynomial).unwrap();
+
+        let claimed_sum: Fq = composed_polynomial.reduce().hypercube_sum();
+        let next_layer = MultilinearPoly::<Fq>::rand(2, &mut rng);
+        let skipping = gkr_prove(
+            claimed_sum,
+            &composed_polynomial,
+            &next_layer,
+            &mut Transcript::new(),
+            None,
+        );
+        let multiplying_out = gkr_prove(
+            claimed_sum,
+            &virtual_polynomial,
+            &next_layer,
+            &mut Transcript::new(),
+            None,
+        );
+        assert!(skipping.is_ok());
+        assert_eq!(skipping, multiplying_out);
+
+        // The zero term does not count towards the degree either.
+        assert!(skipping
+            .unwrap()
+            .proof_polynomials
+            .iter()
+            .all(|poly| poly.len() == 3));
+    }
+
+    #[test]
+    fn test_gkr_doctored_challenges_are_rejected_and_ignored() {
+        let composed_polynomial = quadratic_sum_poly();
+        let claimed_sum: Fq = composed_polynomial.reduce().hypercube_sum();
+
+        let mut proof = gkr_prove(
+            claimed_sum,
+            &composed_polynomial,
+            &next_layer(),
+            &mut Transcript::new(),
+            None,
+        )
+        .unwrap();
+        assert_eq!(proof.validate_challenges(&mut Transcript::new()), Ok(()));
+
+        let honest_challenges = proof.random_challenges.clone();
+        proof.random_challenges[1] += Fq::from(1);
+
+        assert_eq!(
+            proof.validate_challenges(&mut Transcript::new()),
+            Err(SumCheckError::ChallengeMismatch { round: 1 })
+        );
+
+        let result = gkr_verify_rounds(
+            proof.proof_polynomials,
+            claimed_sum,
+            2,
+            &mut Transcript::new(),
+            None,
+        )
+        .unwrap();
+        assert_eq!(result.point, honest_challenges);
+    }
+
+    #[test]
+    fn test_gkr_verify_final_rejects_rounds_for_the_wrong_polynomial() {
+        let composed_polynomial = quadratic_sum_poly();
+        let wrong_polynomial = SumPoly::new(vec![
+            ProductPoly::new(vec![
+                vec![Fq::from(3), Fq::from(9), Fq::from(1), Fq::from(4)],
+                vec![Fq::from(2), Fq::from(5), Fq::from(6), Fq::from(2)],
+            ]),
+            ProductPoly::new(vec![
+                vec![Fq::from(0), Fq::from(0), Fq::from(0), Fq::from(0)],
+                vec![Fq::from(0), Fq::from(0), Fq::from(0), Fq::from(0)],
+            ]),
+        ]);
+        let claimed_sum: Fq = composed_polynomial.reduce().hypercube_sum();
+        assert_eq!(wrong_polynomial.reduce().hypercube_sum(), claimed_sum);
+
+        let proof = gkr_prove(
+            claimed_sum,
+            &wrong_polynomial,
+            &next_layer(),
+            &mut Transcript::new(),
+            None,
+        )
+        .unwrap();
+
+        let result = gkr_verify_rounds(
+            proof.proof_polynomials,
+            claimed_sum,
+            2,
+            &mut Transcript::new(),
+            None,
+        )
+        .unwrap();
+
+        let oracle_eval = composed_polynomial.evaluate(&result.point);
+        assert!(matches!(
+            gkr_verify_final(result.value, oracle_eval),
+            Err(SumCheckError::FinalEvaluationMismatch { .. })
+        ));
+    }
+
+    #[test]
+    fn test_gkr_verify_batch_reports_the_failing_layer_and_round() {
+        let table = |seed: u64| -> Vec<Fq> {
+            (0..16u64)
+                .map(|i| Fq::from((i * seed + 3 * i + seed) % 11))
+                .collect()
+        };
+        let next_layer =
+            MultilinearPoly::new(vec![Fq::from(2), Fq::from(7), Fq::from(1), Fq::from(4)]);
+        let layers: Vec<_> = (0..5u64)
+            .map(|layer| {
+                SumPoly::new(vec![
+                    ProductPoly::new(vec![table(4 * layer), table(4 * layer + 1)]),
+                    ProductPoly::new(vec![table(4 * layer + 2), table(4 * layer + 3)]),
+                ])
+            })
+            .collect();
+
+        let mut prover_transcript = Transcript::new();
+        let proofs: Vec<_> = layers
+            .iter()
+            .enumerate()
+            .map(|(index, layer)| {
+                gkr_prove(
+                    layer.reduce().hypercube_sum(),
+                    layer,
+                    &next_layer,
+                    &mut prover_transcript,
+                    Some(&gkr_layer_label(index)),
+                )
+                .unwrap()
+            })
+            .collect();
+        let claims: Vec<Fq> = proofs.iter().map(|proof| proof.claimed_sum).collect();
+        let mut round_polys: Vec<_> = proofs
+            .iter()
+            .map(|proof| GkrRoundPolys {
+                round_polys: proof.proof_polynomials.clone(),
+                degree: 2,
+            })
+            .collect();
+
+        let results = gkr_verify_batch(&round_polys, &claims, &mut Transcript::new()).unwrap();
+        for ((result, proof), layer) in results.iter().zip(&proofs).zip(&layers) {
+            assert_eq!(result.point, proof.random_challenges);
+            assert_eq!(result.value, layer.evaluate(&result.point));
+        }
+
+        round_polys[3].round_polys[2][0] += Fq::from(1);
+
+        match gkr_verify_batch(&round_polys, &claims, &mut Transcript::new()) {
+            Err(GkrError::LayerFailed { layer, source }) => {
+                assert_eq!(layer, 3);
+                assert!(matches!(
+                    *source,
+                    GkrError::RoundSumMismatch { round: 2, .. }
+                ));
+            }
+            other => panic!("expected layer 3 to fail, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_gkr_layer_labels_block_swapping_layers() {
+        let composed_polynomial = quadratic_sum_poly();
+        let claimed_sum: Fq = composed_polynomial.reduce().hypercube_sum();
+
+        let prove_at = |layer: usize| {
+            gkr_prove(
+                claimed_sum,
+                &composed_polynomial,
+                &next_layer(),
+                &mut Transcript::new(),
+                Some(&gkr_layer_label(layer)),
+            )
+            .unwrap()
+        };
+        let verify_at = |proof: &GkrProof<Fq>, layer: usize| {
+            gkr_verify_rounds(
+                proof.proof_polynomials.clone(),
+                claimed_sum,
+                2,
+                &mut Transcript::new(),
+                Some(&gkr_layer_label(layer)),
+            )
+        };
+
+        // The same layer proved at two positions starts from the same round
+        // polynomial, but the labels send the remaining rounds apart.
+        let proofs = [prove_at(0), prove_at(1)];
+        assert_eq!(
+            proofs[0].proof_polynomials[0],
+            proofs[1].proof_polynomials[0]
+        );
+
+        assert!(verify_at(&proofs[0], 0).is_ok());
+        assert!(verify_at(&proofs[1], 1).is_ok());
+        assert!(verify_at(&proofs[1], 0).is_err());
+        assert!(verify_at(&proofs[0], 1).is_err());
+    }
+
+    #[test]
+    fn test_consecutive_gkr_layers_share_a_transcript() {
+        let first_layer = quadratic_sum_poly();
+        let second_layer = SumPoly::new(vec![
+            ProductPoly::new(vec![
+                vec![Fq::from(4), Fq::from(0), Fq::from(2), Fq::from(9)],
+                vec![Fq::from(1), Fq::from(3), Fq::from(3), Fq::from(1)],
+            ]),
+            ProductPoly::new(vec![
+                vec![Fq::from(7), Fq::from(2), Fq::from(0), Fq::from(1)],
+                vec![Fq::from(6), Fq::from(0), Fq::from(8), Fq::from(2)],
+            ]),
+        ]);
+        let layers = [first_layer, second_layer];
+
+        let mut prover_transcript = Transcript::new();
+        let proofs: Vec<_> = layers
+            .iter()
+            .map(|layer| {
+                let claimed_sum: Fq = layer.reduce().hypercube_sum();
+
+                gkr_prove(
+                    claimed_sum,
+                    layer,
+                    &next_layer(),
+                    &mut prover_transcript,
+                    None,
+                )
+                .unwrap()
+            })
+            .collect();
+
+        let mut verifier_transcript = Transcript::new();
+        for (layer, proof) in layers.iter().zip(proofs) {
+            let result = gkr_verify_rounds(
+                proof.proof_polynomials,
+                proof.claimed_sum,
+                2,
+                &mut verifier_transcript,
+                None,
+            )
+            .unwrap();
+            assert_eq!(result.point, proof.random_challenges);
+            assert_eq!(result.value, layer.evaluate(&result.point));
+        }
+
+        assert_eq!(
+            prover_transcript.get_uniform_challenge(),
+            verifier_transcript.get_uniform_challenge()
+        );
+    }
+
+    #[test]
+    fn test_prove_with_correct_claim() {
+        let initial_polynomial =
+            MultilinearPoly::new(vec![Fq::from(0), Fq::from(3), Fq::from(2), Fq::from(5)]);
+
+        assert_eq!(compute_sum(&initial_polynomial), Fq::from(10));
+
+        let proof = prove_with_claim(&initial_polynomial, Fq::from(10)).unwrap();
+
+        assert_eq!(proof.claimed_sum, Fq::from(10));
+        assert_eq!(verify(&initial_polynomial, proof), Ok(()));
+    }
+
+    #[test]
+    fn test_prove_with_wrong_claim_fails() {
+        let initial_polynomial =
+            MultilinearPoly::new(vec![Fq::from(0), Fq::from(3), Fq::from(2), Fq::from(5)]);
+
+        let result = prove_with_claim(&initial_polynomial, Fq::from(11));
+
+        assert!(matches!(
+            result,
+            Err(SumCheckError::ClaimMismatch { expected, got })
+                if expected == Fq::from(10) && got == Fq::from(11)
+        ));
+    }
+
+    #[test]
+    fn test_oracle_verification_returns_sub_claim() {
+        let initial_polynomial =
+            MultilinearPoly::new(vec![Fq::from(0), Fq::from(3), Fq::from(2), Fq::from(5)]);
+
+        let proof = prove(&initial_polynomial);
+
+        // Only the digest is shared with the verifier; the oracle stands in
+        // for an opening of the committed polynomial.
+        let digest = initial_polynomial.digest();
+
+        let sub_claim = verify_with_oracle(&digest, Fq::from(10), 2, proof, |point| {
+            initial_polynomial.evaluate(point)
+        })
+        .unwrap();
+
+        assert_eq!(sub_claim.point.len(), 2);
+        assert_eq!(
+            initial_polynomial.evaluate(&sub_claim.point),
+            sub_claim.value
+        );
+    }
+
+    #[test]
+    fn test_partial_verification_matches_the_prover_folding() {
+        let initial_polynomial = sample_poly();
+
+        let proof = prove(&initial_polynomial);
+        let final_evaluation = proof.final_evaluation;
+
+        let mut transcript = Transcript::new();
+        transcript.append(&initial_polynomial.digest());
+
+        let sub_claim = verify_partial(proof, 3, &mut transcript).unwrap();
+
+        // Folding the table at the sampled point reproduces the prover's last
+        // value, which is what the sub-claim asks the caller to open.
+        let folded = initial_polynomial.fix_prefix(&sub_claim.point);
+        assert_eq!(folded.evaluations(), [sub_claim.value]);
+        assert_eq!(sub_claim.value, final_evaluation);
+    }
+
+    #[test]
+    fn test_partial_verification_claims_are_discharged_by_the_polynomial() {
+        check_property(16, |rng| {
+            let num_vars = rng.gen_range(0..=6);
+            let polynomial = MultilinearPoly::<Fq>::rand(num_vars, rng);
+            let proof = prove(&polynomial);
+
+            let mut transcript = Transcript::new();
+            transcript.append(&polynomial.digest());
+            let claim = verify_partial(proof, num_vars, &mut transcript).unwrap();
+
+            assert!(claim.check_against(&polynomial));
+
+            let other = polynomial.clone() + MultilinearPoly::rand(num_vars, rng);
+            assert!(!claim.check_against(&other));
+        });
+    }
+
+    #[test]
+    fn test_oracle_verification_rejects_wrong_oracle_answer() {
+        let initial_polynomial =
+            MultilinearPoly::new(vec![Fq::from(0), Fq::from(3), Fq::from(2), Fq::from(5)]);
+        let other_polynomial =
+            MultilinearPoly::new(vec![Fq::from(1), Fq::from(2), Fq::from(3), Fq::from(4)]);
+
+        let proof = prove(&initial_polynomial);
+
+        let result = verify_with_oracle(
+            &initial_polynomial.digest(),
+            Fq::from(10),
+            2,
+            proof,
+            |point| other_polynomial.evaluate(point),
+        );
+
+        assert!(matches!(
+            result,
+            Err(SumCheckError::FinalEvaluationMismatch { .. })
+        ));
+    }
+
+    #[test]
+    fn test_oracle_verification_rejects_wrong_digest() {
+        let initial_polynomial =
+            MultilinearPoly::new(vec![Fq::from(0), Fq::from(3), Fq::from(2), Fq::from(5)]);
+        let other_polynomial =
+            MultilinearPoly::new(vec![Fq::from(5), Fq::from(2), Fq::from(3), Fq::from(0)]);
+
+        let proof = prove(&initial_polynomial);
+
+        let result = verify_with_oracle(
+            &other_polynomial.digest(),
+            Fq::from(10),
+            2,
+            proof,
+            |point| initial_polynomial.evaluate(point),
+        );
+
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_proof_constructor_accepts_a_well_formed_proof() {
+        let proof = Proof::new(
+            vec![
+                vec![Fq::from(2), Fq::from(8)],
+                vec![Fq::from(1), Fq::from(4)],
+            ],
+            Fq::from(10),
+            Fq::from(3),
+        )
+        .unwrap();
+
+        assert_eq!(proof.num_rounds(), 2);
+        assert_eq!(
+            proof.round_polynomial(1),
+            Some([Fq::from(1), Fq::from(4)].as_slice())
+        );
+        assert_eq!(proof.round_polynomial(2), None);
+        assert_eq!(proof.claimed_sum(), Fq::from(10));
+        assert_eq!(proof.final_evaluation(), Fq::from(3));
+        assert_eq!(proof.size_in_bytes(), 6 * 32);
+    }
+
+    #[test]
+    fn test_proof_constructor_accepts_zero_rounds() {
+        let proof = Proof::new(vec![], Fq::from(10), Fq::from(10)).unwrap();
+
+        assert_eq!(proof.num_rounds(), 0);
+        assert_eq!(proof.size_in_bytes(), 2 * 32);
+    }
+
+    #[test]
+    fn test_zero_variable_polynomial() {
+        let initial_polynomial = MultilinearPoly::new(vec![Fq::from(7)]);
+
+        let proof = prove(&initial_polynomial);
+
+        assert_eq!(proof.num_rounds(), 0);
+        assert_eq!(proof.claimed_sum(), Fq::from(7));
+        assert_eq!(proof.final_evaluation(), Fq::from(7));
+        assert!(verify(&initial_polynomial, proof.clone()).is_ok());
+
+        let mut wrong_sum = proof.clone();
+        wrong_sum.claimed_sum = Fq::from(8);
+        assert!(verify(&initial_polynomial, wrong_sum).is_err());
+
+        let mut extra_round = proof;
+        extra_round.proof_polynomials = vec![vec![Fq::from(7), Fq::from(0)]];
+        assert_eq!(
+            verify(&initial_polynomial, extra_round),
+            Err(SumCheckError::WrongNumberOfRounds {
+                expected: 0,
+                got: 1
+            })
+        );
+    }
+
+    #[test]
+    fn test_one_variable_polynomial() {
+        let initial_polynomial = MultilinearPoly::<Fq>::from(vec![3, 5]);
+
+        let proof = prove(&initial_polynomial);
+
+        assert_eq!(proof.num_rounds(), 1);
+        assert_eq!(
+            proof.round_polynomial(0),
+            Some([Fq::from(3), Fq::from(5)].as_slice())
+        );
+        assert_eq!(proof.claimed_sum(), Fq::from(8));
+        assert!(verify(&initial_polynomial, proof.clone()).is_ok());
+
+        let mut tampered = proof;
+        tampered.proof_polynomials[0][0] += Fq::from(1);
+        assert!(verify(&initial_polynomial, tampered).is_err());
+    }
+
+    #[test]
+    fn test_constant_polynomial() {
+        for num_vars in 0..5 {
+            let initial_polynomial = MultilinearPoly::new(vec![Fq::from(6); 1 << num_vars]);
+            let constant = initial_polynomial.is_constant().unwrap();
+
+            let proof = prove(&initial_polynomial);
+
+            // Every round polynomial is flat: both halves sum to the same.
+            assert_eq!(proof.num_rounds(), initial_polynomial.num_vars());
+            assert_eq!(proof.claimed_sum(), constant * Fq::from(1u64 << num_vars));
+            for (round, poly) in proof.proof_polynomials.iter().enumerate() {
+                assert_eq!(poly[0], poly[1], "round {}", round);
+            }
+            assert_eq!(proof.final_evaluation(), constant);
+            assert!(verify(&initial_polynomial, proof).is_ok());
+        }
+    }
+
+    #[test]
+    fn test_proof_constructor_rejects_a_short_round() {
+        let result = Proof::new(
+            vec![vec![Fq::from(2), Fq::from(8)], vec![Fq::from(1)]],
+            Fq::from(10),
+            Fq::from(3),
+        );
+
+        assert_eq!(
+            result.unwrap_err(),
+            ProofFormatError::InvalidRoundPolynomialLength {
+                round: 1,
+                expected: 2,
+                got: 1
+            }
+        );
+    }
+
+    #[test]
+    fn test_proof_constructor_rejects_a_long_round() {
+        let result = Proof::new(
+            vec![vec![Fq::from(2), Fq::from(8), Fq::from(0)]],
+            Fq::from(10),
+            Fq::from(3),
+        );
+
+        assert_eq!(
+            result.unwrap_err(),
+            ProofFormatError::InvalidRoundPolynomialLength {
+                round: 0,
+                expected: 2,
+                got: 3
+            }
+        );
+    }
+
+    #[test]
+    fn test_truncated_proof_is_rejected() {
+        let initial_polynomial =
+            MultilinearPoly::new(vec![Fq::from(0), Fq::from(3), Fq::from(2), Fq::from(5)]);
+
+        let mut proof = prove(&initial_polynomial);
+        proof.proof_polynomials.pop();
+
+        let result = verify(&initial_polynomial, proof);
+
+        assert_eq!(
+            result,
+            Err(SumCheckError::WrongNumberOfRounds {
+                expected: 2,
+                got: 1
+            })
+        );
+    }
+
+    #[test]
+    fn test_padded_proof_is_rejected() {
+        let initial_polynomial =
+            MultilinearPoly::new(vec![Fq::from(0), Fq::from(3), Fq::from(2), Fq::from(5)]);
+
+        let mut proof = prove(&initial_polynomial);
+        proof.proof_polynomials.push(vec![Fq::from(0), Fq::from(0)]);
+
+        let result = verify(&initial_polynomial, proof);
+
+        assert_eq!(
+            result,
+            Err(SumCheckError::WrongNumberOfRounds {
+                expected: 2,
+                got: 3
+            })
+        );
+    }
+
+    #[test]
+    fn test_round_polynomial_with_three_evaluations_is_rejected() {
+        let initial_polynomial =
+            MultilinearPoly::new(vec![Fq::from(0), Fq::from(3), Fq::from(2), Fq::from(5)]);
+
+        let mut proof = prove(&initial_polynomial);
+        proof.proof_polynomials[0].push(Fq::from(0));
+
+        let result = verify(&initial_polynomial, proof);
+
+        assert_eq!(
+            result,
+            Err(SumCheckError::InvalidRoundPolynomialLength {
+                round: 0,
+                expected: 2,
+                got: 3
+            })
+        );
+    }
+
+    #[test]
+    fn test_round_polynomial_with_four_evaluations_is_rejected() {
+        let initial_polynomial =
+            MultilinearPoly::new(vec![Fq::from(0), Fq::from(3), Fq::from(2), Fq::from(5)]);
+
+        let mut proof = prove(&initial_polynomial);
+        proof.proof_polynomials[1].extend([Fq::from(0), Fq::from(0)]);
+
+        let result = verify(&initial_polynomial, proof);
+
+        assert_eq!(
+            result,
+            Err(SumCheckError::InvalidRoundPolynomialLength {
+                round: 1,
+                expected: 2,
+                got: 4
+            })
+        );
+    }
+
+    #[test]
+    fn test_back_to_back_sum_checks_share_a_transcript() {
+        let polynomial_a =
+            MultilinearPoly::new(vec![Fq::from(0), Fq::from(3), Fq::from(2), Fq::from(5)]);
+        let polynomial_b =
+            MultilinearPoly::new(vec![Fq::from(7), Fq::from(1), Fq::from(4), Fq::from(4)]);
+
+        let mut prover_transcript = Transcript::new();
+        let proof_a = prove_with_transcript(&polynomial_a, &mut prover_transcript);
+        let proof_b = prove_with_transcript(&polynomial_b, &mut prover_transcript);
+
+        let mut verifier_transcript = Transcript::new();
+        assert_eq!(
+            verify_with_transcript(&polynomial_a, proof_a.clone(), &mut verifier_transcript),
+            Ok(())
+        );
+        assert_eq!(
+            verify_with_transcript(&polynomial_b, proof_b.clone(), &mut verifier_transcript),
+            Ok(())
+        );
+
+        // The second proof was produced on a transcript that had already
+        // absorbed the first sum-check, so it does not verify on its own.
+        assert!(verify(&polynomial_b, proof_b.clone()).is_err());
+
+        let mut reordered_transcript = Transcript::new();
+        let reordered_result =
+            verify_with_transcript(&polynomial_b, proof_b, &mut reordered_transcript).and_then(
+                |_| verify_with_transcript(&polynomial_a, proof_a, &mut reordered_transcript),
+            );
+        assert!(reordered_result.is_err());
+    }
+
+    #[test]
+    fn test_proof_matches_sequential_reference() {
+        let evaluations: Vec<Fq> = (0..1u64 << 10).map(|i| Fq::from(i * i + 3)).collect();
+        let initial_polynomial = MultilinearPoly::new(evaluations.clone());
+
+        // A plain sequential prover: halves summed in order and the table
+        // folded pairwise, independent of the `parallel` feature.
+        let mut transcript = Transcript::<Fq>::new();
+        let claimed_sum: Fq = evaluations.iter().sum();
+        transcript.append(&initial_polynomial.digest());
+        transcript.append(&fq_vec_to_bytes(&[Fq::from(10), claimed_sum]));
+
+        let mut table = evaluations;
+        let mut expected_polynomials = vec![];
+        while table.len() > 1 {
+            let (zeros, ones) = table.split_at(table.len() / 2);
+            let round = vec![zeros.iter().sum::<Fq>(), ones.iter().sum::<Fq>()];
+
+            transcript.append(&fq_vec_to_bytes(&round));
+            let challenge = transcript.get_uniform_challenge();
+
+            table = zeros
+                .iter()
+                .zip(ones.iter())
+                .map(|(a, b)| *a + challenge * (*b - a))
+                .collect();
+            expected_polynomials.push(round);
+        }
+
+        let proof = prove(&initial_polynomial);
+
+        assert_eq!(proof.proof_polynomials, expected_polynomials);
+        assert_eq!(proof.final_evaluation, table[0]);
+    }
+
+    #[test]
+    fn test_large_instance_verifies() {
+        let num_vars = 18;
+        let evaluations = (0..1u64 << num_vars).map(Fq::from).collect();
+        let initial_polynomial = MultilinearPoly::new(evaluations);
+
+        let proof = prove(&initial_polynomial);
+
+        assert_eq!(proof.proof_polynomials.len(), num_vars);
+        assert_eq!(verify(&initial_polynomial, proof), Ok(()));
+    }
+
+    #[test]
+    fn test_proving_and_verification_over_another_field() {
+        let initial_polynomial = MultilinearPoly::new(vec![
+            Fr::from(1),
+            Fr::from(4),
+            Fr::from(0),
+            Fr::from(7),
+            Fr::from(3),
+            Fr::from(0),
+            Fr::from(9),
+            Fr::from(2),
+        ]);
+
+        let proof = prove(&initial_polynomial);
+
+        assert_eq!(proof.claimed_sum, Fr::from(26));
+
+        let result = verify(&initial_polynomial, proof);
 
-        assert_eq!(is_verified, false);
+        assert_eq!(result, Ok(()));
     }
 }
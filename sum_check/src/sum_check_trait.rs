@@ -0,0 +1,416 @@
+use ark_ff::PrimeField;
+use fiat_shamir::fiat_shamir_transcript::Transcript;
+use multilinear_polynomial::{
+    composed_polynomial::SumPoly, evaluation_claim::EvaluationClaim,
+    multilinear_polynomial_evaluation::MultilinearPoly,
+};
+
+use crate::{
+    sum_check_error::SumCheckError,
+    sum_check_protocol::{
+        gkr_layer_label, gkr_prove, gkr_prove_layer_rounds, gkr_verify_rounds_with_degrees,
+        prove_unbound, verify_gkr_rounds, verify_partial, BindingOrder, GkrLayerRounds, GkrProof,
+        Proof,
+    },
+};
+
+/// What the verifier knows before a sum-check starts.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SumCheckStatement<F: PrimeField> {
+    pub num_vars: usize,
+    pub claimed_sum: F,
+}
+
+/// A sum-check variant that callers can be written against, so that one
+/// prover strategy can be swapped for another.
+///
+/// Neither side binds the transcript to the witness; callers absorb a digest
+/// or commitment first, as with `verify_partial`.
+pub trait SumCheckProtocol<F: PrimeField> {
+    type Statement;
+    type Witness;
+    type Proof;
+
+    /// Fails on a witness the protocol cannot prove.
+    fn prove(
+        &self,
+        witness: &Self::Witness,
+        transcript: &mut Transcript<F>,
+    ) -> Result<Self::Proof, SumCheckError<F>>;
+
+    /// Checks the proof without querying the witness, leaving the final
+    /// evaluation claim to the caller.
+    fn verify_partial(
+        &self,
+        statement: &Self::Statement,
+        proof: Self::Proof,
+        transcript: &mut Transcript<F>,
+    ) -> Result<EvaluationClaim<F>, SumCheckError<F>>;
+
+    fn evaluate_witness(&self, witness: &Self::Witness, point: &[F]) -> F;
+
+    fn verify(
+        &self,
+        statement: &Self::Statement,
+        witness: &Self::Witness,
+        proof: Self::Proof,
+        transcript: &mut Transcript<F>,
+    ) -> Result<EvaluationClaim<F>, SumCheckError<F>> {
+        let sub_claim = self.verify_partial(statement, proof, transcript)?;
+
+        let eval = self.evaluate_witness(witness, &sub_claim.point);
+        if eval != sub_claim.value {
+            return Err(SumCheckError::FinalEvaluationMismatch {
+                expected: sub_claim.value,
+                got: eval,
+            });
+        }
+
+        Ok(sub_claim)
+    }
+}
+
+fn check_claimed_sum<F: PrimeField>(
+    statement: &SumCheckStatement<F>,
+    claimed_sum: F,
+) -> Result<(), SumCheckError<F>> {
+    if claimed_sum != statement.claimed_sum {
+        return Err(SumCheckError::ClaimMismatch {
+            expected: statement.claimed_sum,
+            got: claimed_sum,
+        });
+    }
+
+    Ok(())
+}
+
+/// What the verifier knows before a GKR layer's sum-check starts, including
+/// the degree bound on its round polynomials.
+#[derive(Debug, Clone, PartialEq)]
+pub struct GkrStatement<F: PrimeField> {
+    pub num_vars: usize,
+    pub degree: usize,
+    pub claimed_sum: F,
+}
+
+/// The plain sum-check over a single multilinear polynomial.
+pub struct MultilinearSumCheck;
+
+impl<F: PrimeField> SumCheckProtocol<F> for MultilinearSumCheck {
+    type Statement = SumCheckStatement<F>;
+    type Witness = MultilinearPoly<F>;
+    type Proof = Proof<F>;
+
+    fn prove(
+        &self,
+        witness: &MultilinearPoly<F>,
+        transcript: &mut Transcript<F>,
+    ) -> Result<Proof<F>, SumCheckError<F>> {
+        Ok(prove_unbound(
+            witness,
+            witness.hypercube_sum(),
+            BindingOrder::MostSignificantFirst,
+            transcript,
+        ))
+    }
+
+    fn verify_partial(
+        &self,
+        statement: &SumCheckStatement<F>,
+        proof: Proof<F>,
+        transcript: &mut Transcript<F>,
+    ) -> Result<EvaluationClaim<F>, SumCheckError<F>> {
+        check_claimed_sum(statement, proof.claimed_sum)?;
+
+        verify_partial(proof, statement.num_vars, transcript)
+    }
+
+    fn evaluate_witness(&self, witness: &MultilinearPoly<F>, point: &[F]) -> F {
+        witness.evaluate(point)
+    }
+}
+
+/// A GKR layer's composed polynomial together with the values of the layer
+/// below, which the prover evaluates to produce `w_b` and `w_c`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct GkrWitness<F: PrimeField> {
+    pub composed_polynomial: SumPoly<F>,
+    pub next_layer: MultilinearPoly<F>,
+}
+
+/// The sum-check over a sum of products run by each GKR layer, together
+/// with the claims on the layer below. Proving fails on a witness
+/// `gkr_prove` rejects.
+pub struct GkrSumCheck;
+
+impl<F: PrimeField> SumCheckProtocol<F> for GkrSumCheck {
+    type Statement = GkrStatement<F>;
+    type Witness = GkrWitness<F>;
+    type Proof = GkrProof<F>;
+
+    fn prove(
+        &self,
+        witness: &GkrWitness<F>,
+        transcript: &mut Transcript<F>,
+    ) -> Result<GkrProof<F>, SumCheckError<F>> {
+        let claimed_sum = witness.composed_polynomial.reduce().hypercube_sum();
+
+        Ok(gkr_prove(
+            claimed_sum,
+            &witness.composed_polynomial,
+            &witness.next_layer,
+            transcript,
+            None,
+        )?)
+    }
+
+    fn verify_partial(
+        &self,
+        statement: &GkrStatement<F>,
+        proof: GkrProof<F>,
+        transcript: &mut Transcript<F>,
+    ) -> Result<EvaluationClaim<F>, SumCheckError<F>> {
+        if proof.claimed_sum != statement.claimed_sum {
+            return Err(SumCheckError::ClaimMismatch {
+                expected: statement.claimed_sum,
+                got: proof.claimed_sum,
+            });
+        }
+
+        if proof.proof_polynomials.len() != statement.num_vars {
+            return Err(SumCheckError::WrongNumberOfRounds {
+                expected: statement.num_vars,
+                got: proof.proof_polynomials.len(),
+            });
+        }
+
+        Ok(verify_gkr_rounds(
+            &proof.proof_polynomials,
+            statement.claimed_sum,
+            statement.degree,
+            transcript,
+        )?)
+    }
+
+    fn evaluate_witness(&self, witness: &GkrWitness<F>, point: &[F]) -> F {
+        witness.composed_polynomial.evaluate(point)
+    }
+}
+
+/// What the verifier knows before one layer's rounds of a GKR reduction: the
+/// claimed sum and the degree bound of each round, one per variable.
+#[derive(Debug, Clone, PartialEq)]
+pub struct GkrLayerStatement<F: PrimeField> {
+    pub claimed_sum: F,
+    pub degrees: Vec<usize>,
+}
+
+/// The rounds of layer `layer` of a GKR reduction, labelled with
+/// `gkr_layer_label(layer)`. The claims on the layer below are left to the
+/// caller, so layers whose final point does not split into two halves, such
+/// as fan-in-3 or data-parallel ones, run through it too.
+///
+/// The verifier resamples the challenges and never reads the proof's
+/// `random_challenges`.
+pub struct GkrLayerSumCheck {
+    pub layer: usize,
+}
+
+impl<F: PrimeField> SumCheckProtocol<F> for GkrLayerSumCheck {
+    type Statement = GkrLayerStatement<F>;
+    type Witness = SumPoly<F>;
+    type Proof = GkrLayerRounds<F>;
+
+    fn prove(
+        &self,
+        witness: &SumPoly<F>,
+        transcript: &mut Transcript<F>,
+    ) -> Result<GkrLayerRounds<F>, SumCheckError<F>> {
+        Ok(gkr_prove_layer_rounds(
+            witness,
+            transcript,
+            Some(&gkr_layer_label(self.layer)),
+        )?)
+    }
+
+    fn verify_partial(
+        &self,
+        statement: &GkrLayerStatement<F>,
+        proof: GkrLayerRounds<F>,
+        transcript: &mut Transcript<F>,
+    ) -> Result<EvaluationClaim<F>, SumCheckError<F>> {
+        Ok(gkr_verify_rounds_with_degrees(
+            proof.proof_polynomials,
+            statement.claimed_sum,
+            &statement.degrees,
+            transcript,
+            Some(&gkr_layer_label(self.layer)),
+        )?)
+    }
+
+    fn evaluate_witness(&self, witness: &SumPoly<F>, point: &[F]) -> F {
+        witness.evaluate(point)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::cell::RefCell;
+
+    use ark_bn254::Fq;
+    use fiat_shamir::fiat_shamir_transcript::Transcript;
+    use multilinear_polynomial::{
+        composed_polynomial::{ProductPoly, SumPoly},
+        evaluation_claim::EvaluationClaim,
+        multilinear_polynomial_evaluation::MultilinearPoly,
+    };
+
+    use super::{
+        GkrLayerStatement, GkrLayerSumCheck, GkrStatement, GkrSumCheck, GkrWitness,
+        MultilinearSumCheck, SumCheckProtocol, SumCheckStatement,
+    };
+    use crate::{sum_check_error::SumCheckError, test_utils::sample_poly};
+
+    /// Downstream code only sees the trait.
+    fn prove_and_verify<P: SumCheckProtocol<Fq>>(
+        protocol: &P,
+        statement: &P::Statement,
+        witness: &P::Witness,
+    ) -> Result<EvaluationClaim<Fq>, SumCheckError<Fq>> {
+        let proof = protocol.prove(witness, &mut Transcript::new())?;
+
+        protocol.verify(statement, witness, proof, &mut Transcript::new())
+    }
+
+    #[derive(Default)]
+    struct RecordingProtocol {
+        calls: RefCell<Vec<&'static str>>,
+    }
+
+    impl SumCheckProtocol<Fq> for RecordingProtocol {
+        type Statement = Fq;
+        type Witness = Vec<Fq>;
+        type Proof = Fq;
+
+        fn prove(
+            &self,
+            witness: &Vec<Fq>,
+            _transcript: &mut Transcript<Fq>,
+        ) -> Result<Fq, SumCheckError<Fq>> {
+            self.calls.borrow_mut().push("prove");
+
+            Ok(witness.iter().sum())
+        }
+
+        fn verify_partial(
+            &self,
+            statement: &Fq,
+            proof: Fq,
+            _transcript: &mut Transcript<Fq>,
+        ) -> Result<EvaluationClaim<Fq>, SumCheckError<Fq>> {
+            self.calls.borrow_mut().push("verify_partial");
+
+            if proof != *statement {
+                return Err(SumCheckError::ClaimMismatch {
+                    expected: *statement,
+                    got: proof,
+                });
+            }
+
+            Ok(EvaluationClaim {
+                point: vec![],
+                value: proof,
+            })
+        }
+
+        fn evaluate_witness(&self, witness: &Vec<Fq>, _point: &[Fq]) -> Fq {
+            self.calls.borrow_mut().push("evaluate_witness");
+
+            witness.iter().sum()
+        }
+    }
+
+    #[test]
+    fn it_runs_the_plain_protocol_through_the_trait() {
+        let polynomial = sample_poly();
+        let statement = SumCheckStatement {
+            num_vars: 3,
+            claimed_sum: Fq::from(29),
+        };
+
+        let sub_claim = prove_and_verify(&MultilinearSumCheck, &statement, &polynomial).unwrap();
+        assert_eq!(sub_claim.point.len(), 3);
+
+        let wrong_statement = SumCheckStatement {
+            num_vars: 3,
+            claimed_sum: Fq::from(30),
+        };
+        assert!(prove_and_verify(&MultilinearSumCheck, &wrong_statement, &polynomial).is_err());
+    }
+
+    #[test]
+    fn it_runs_the_gkr_protocol_through_the_trait() {
+        let witness = GkrWitness {
+            composed_polynomial: SumPoly::new(vec![
+                ProductPoly::new(vec![
+                    vec![Fq::from(1), Fq::from(2), Fq::from(3), Fq::from(5)],
+                    vec![Fq::from(2), Fq::from(1), Fq::from(7), Fq::from(4)],
+                ]),
+                ProductPoly::new(vec![
+                    vec![Fq::from(0), Fq::from(3), Fq::from(1), Fq::from(6)],
+                    vec![Fq::from(1), Fq::from(1), Fq::from(5), Fq::from(2)],
+                ]),
+            ]),
+            next_layer: MultilinearPoly::new(vec![Fq::from(3), Fq::from(8)]),
+        };
+        let statement = GkrStatement {
+            num_vars: 2,
+            degree: 2,
+            claimed_sum: witness.composed_polynomial.reduce().hypercube_sum(),
+        };
+
+        let sub_claim = prove_and_verify(&GkrSumCheck, &statement, &witness).unwrap();
+        assert_eq!(sub_claim.point.len(), 2);
+
+        let layer_statement = GkrLayerStatement {
+            claimed_sum: statement.claimed_sum,
+            degrees: vec![2, 2],
+        };
+        let layer = GkrLayerSumCheck { layer: 3 };
+        let sub_claim =
+            prove_and_verify(&layer, &layer_statement, &witness.composed_polynomial).unwrap();
+        assert_eq!(sub_claim.point.len(), 2);
+    }
+
+    #[test]
+    fn it_rejects_a_witness_that_is_not_a_gkr_layer() {
+        let witness = GkrWitness {
+            composed_polynomial: SumPoly::new(vec![ProductPoly::new(vec![vec![
+                Fq::from(1),
+                Fq::from(2),
+            ]])]),
+            next_layer: MultilinearPoly::new(vec![Fq::from(3), Fq::from(8)]),
+        };
+
+        assert_eq!(
+            GkrSumCheck.prove(&witness, &mut Transcript::new()),
+            Err(SumCheckError::NextLayerMismatch {
+                num_rounds: 1,
+                got: 1
+            })
+        );
+    }
+
+    #[test]
+    fn it_injects_a_mock_protocol() {
+        let protocol = RecordingProtocol::default();
+        let witness = vec![Fq::from(4), Fq::from(6)];
+
+        prove_and_verify(&protocol, &Fq::from(10), &witness).unwrap();
+
+        assert_eq!(
+            *protocol.calls.borrow(),
+            vec!["prove", "verify_partial", "evaluate_witness"]
+        );
+    }
+}
@@ -0,0 +1,269 @@
+use arbitrary::{Arbitrary, Unstructured};
+use ark_bn254::Fq;
+use ark_ff::PrimeField;
+use fiat_shamir::fiat_shamir_transcript::{fq_vec_to_bytes, Transcript};
+pub use multilinear_polynomial::fuzzing::{MAX_TERMS, MAX_VARS};
+use multilinear_polynomial::{
+    composed_polynomial::SumPoly, fuzzing::MAX_ARBITRARY_BYTES,
+    multilinear_polynomial_evaluation::MultilinearPoly,
+};
+use rand::{rngs::StdRng, Rng, SeedableRng};
+
+use crate::{
+    interactive_sum_check::{ChallengeSource, SumCheckProver},
+    sum_check_protocol::{append_statement, Proof},
+};
+
+/// `2bc + 10ac + 5abc`, the three-variable polynomial most tests start
+/// from. Its table is `[0, 0, 0, 2, 0, 10, 0, 17]` and it sums to 29.
+pub fn sample_poly() -> MultilinearPoly<Fq> {
+    MultilinearPoly::new([0, 0, 0, 2, 0, 10, 0, 17].map(Fq::from).to_vec())
+}
+
+/// Runs `property` on `cases` generators seeded `0..cases`, so a failure
+/// names the seed that reproduces it.
+pub fn check_property(cases: u64, mut property: impl FnMut(&mut StdRng)) {
+    for seed in 0..cases {
+        let result = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+            property(&mut StdRng::seed_from_u64(seed))
+        }));
+
+        if let Err(panic) = result {
+            eprintln!("property failed for seed {}", seed);
+            std::panic::resume_unwind(panic);
+        }
+    }
+}
+
+/// Runs the `Arbitrary` impl of `T` on bytes drawn from `rng`, so the
+/// seeded generators and a fuzzer share one definition of each value.
+pub fn arbitrary_from_rng<T: for<'a> Arbitrary<'a>>(rng: &mut impl Rng) -> T {
+    let mut bytes = vec![0u8; MAX_ARBITRARY_BYTES];
+    rng.fill(bytes.as_mut_slice());
+
+    T::arbitrary(&mut Unstructured::new(&bytes)).expect("running out of bytes yields defaults")
+}
+
+/// A random polynomial over 1 to `MAX_VARS` variables.
+pub fn arbitrary_poly<F: PrimeField>(rng: &mut impl Rng) -> MultilinearPoly<F> {
+    arbitrary_from_rng(rng)
+}
+
+/// A random sum of 1 to `MAX_TERMS` products of 1 to `MAX_TERMS` factors,
+/// over 1 to `MAX_VARS` variables.
+pub fn arbitrary_sum_poly<F: PrimeField>(rng: &mut impl Rng) -> SumPoly<F> {
+    arbitrary_from_rng(rng)
+}
+
+/// A well-formed proof over 0 to `MAX_VARS` rounds with random values,
+/// which verifies for no polynomial except by chance.
+pub fn arbitrary_proof<F: PrimeField>(rng: &mut impl Rng) -> Proof<F> {
+    arbitrary_from_rng(rng)
+}
+
+/// How a `CheatingProver` departs from the honest protocol.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Deviation<F: PrimeField> {
+    /// Claims a sum one larger than the real one; the round is ignored.
+    WrongClaimedSum,
+    /// Adds the constant to both evaluations of the round polynomial.
+    OffByConstant(F),
+    /// Sends s(1), s(0) instead of s(0), s(1).
+    SwappedEvaluations,
+    /// Appends an extra evaluation point to the round polynomial.
+    ExtraEvaluation,
+}
+
+impl<F: PrimeField> Deviation<F> {
+    pub fn all() -> Vec<Self> {
+        vec![
+            Deviation::WrongClaimedSum,
+            Deviation::OffByConstant(F::one()),
+            Deviation::SwappedEvaluations,
+            Deviation::ExtraEvaluation,
+        ]
+    }
+}
+
+/// An otherwise honest prover that applies `deviation` at round `round`.
+pub struct CheatingProver<F: PrimeField> {
+    prover: SumCheckProver<F>,
+    digest: [u8; 32],
+    num_vars: usize,
+    deviation: Deviation<F>,
+    round: usize,
+    current_round: usize,
+}
+
+impl<F: PrimeField> CheatingProver<F> {
+    pub fn new(polynomial: &MultilinearPoly<F>, deviation: Deviation<F>, round: usize) -> Self {
+        Self {
+            prover: SumCheckProver::new(polynomial),
+            digest: polynomial.digest(),
+            num_vars: polynomial.num_vars(),
+            deviation,
+            round,
+            current_round: 0,
+        }
+    }
+
+    pub fn claimed_sum(&self) -> F {
+        match self.deviation {
+            Deviation::WrongClaimedSum => self.prover.claimed_sum() + F::one(),
+            _ => self.prover.claimed_sum(),
+        }
+    }
+
+    pub fn rounds_remaining(&self) -> usize {
+        self.prover.rounds_remaining()
+    }
+
+    pub fn round_message(&self) -> Vec<F> {
+        let mut message = self.prover.round_message();
+
+        if self.current_round == self.round {
+            match self.deviation {
+                Deviation::WrongClaimedSum => {}
+                Deviation::OffByConstant(c) => message.iter_mut().for_each(|eval| *eval += c),
+                Deviation::SwappedEvaluations => message.swap(0, 1),
+                Deviation::ExtraEvaluation => message.push(F::zero()),
+            }
+        }
+
+        message
+    }
+
+    pub fn receive_challenge(&mut self, challenge: F) {
+        self.prover.receive_challenge(challenge);
+        self.current_round += 1;
+    }
+
+    /// Runs the whole protocol against a Fiat-Shamir transcript laid out
+    /// exactly like `prove`, so the result can be passed to `verify`.
+    pub fn prove(mut self) -> Proof<F> {
+        let claimed_sum = self.claimed_sum();
+
+        let mut transcript = Transcript::<F>::new();
+        transcript.append(&self.digest);
+        append_statement(&mut transcript, self.num_vars, claimed_sum);
+
+        let mut proof_polynomials = Vec::with_capacity(self.num_vars);
+
+        while self.rounds_remaining() > 0 {
+            let message = self.round_message();
+            let challenge = transcript.next_challenge(&message);
+
+            proof_polynomials.push(message);
+            self.receive_challenge(challenge);
+        }
+
+        let final_evaluation = self.prover.final_evaluation();
+        transcript.append(&fq_vec_to_bytes(&[final_evaluation]));
+
+        Proof {
+            proof_polynomials,
+            claimed_sum,
+            final_evaluation,
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use ark_bn254::Fq;
+    use ark_serialize::{CanonicalDeserialize, CanonicalSerialize};
+    use multilinear_polynomial::multilinear_polynomial_evaluation::MultilinearPoly;
+    use rand::Rng;
+
+    use super::{
+        arbitrary_poly, arbitrary_proof, arbitrary_sum_poly, check_property, CheatingProver,
+        Deviation, MAX_VARS,
+    };
+    use crate::sum_check_protocol::{prove, verify, Proof};
+
+    // Four variables rather than the shared `sample_poly`'s three, so a
+    // deviation has more rounds to land in.
+    fn sixteen_entry_poly() -> MultilinearPoly<Fq> {
+        MultilinearPoly::new((0..16u64).map(|i| Fq::from(3 * i + 1)).collect())
+    }
+
+    #[test]
+    fn honest_prover_without_deviation_verifies() {
+        let polynomial = sixteen_entry_poly();
+
+        // A deviation scheduled past the last round never fires.
+        let proof = CheatingProver::new(&polynomial, Deviation::SwappedEvaluations, 4).prove();
+
+        assert_eq!(verify(&polynomial, proof), Ok(()));
+    }
+
+    #[test]
+    fn verifier_rejects_every_deviation_at_every_round() {
+        let polynomial = sixteen_entry_poly();
+
+        for deviation in Deviation::<Fq>::all() {
+            for round in 0..polynomial.num_vars() {
+                let proof = CheatingProver::new(&polynomial, deviation, round).prove();
+
+                assert!(
+                    verify(&polynomial, proof).is_err(),
+                    "{:?} at round {} was accepted",
+                    deviation,
+                    round
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn honest_proofs_of_random_polynomials_verify() {
+        check_property(32, |rng| {
+            let polynomial = arbitrary_poly::<Fq>(rng);
+            assert!((1..=MAX_VARS).contains(&polynomial.num_vars()));
+
+            assert_eq!(verify(&polynomial, prove(&polynomial)), Ok(()));
+        });
+    }
+
+    #[test]
+    fn flipping_any_bit_of_a_proof_is_rejected() {
+        check_property(32, |rng| {
+            let polynomial = arbitrary_poly::<Fq>(rng);
+
+            let mut bytes = Vec::new();
+            prove(&polynomial).serialize_compressed(&mut bytes).unwrap();
+
+            let bit = rng.gen_range(0..bytes.len() * 8);
+            bytes[bit / 8] ^= 1 << (bit % 8);
+
+            // Either the bytes no longer decode, or the proof they decode to
+            // is wrong.
+            if let Ok(proof) = Proof::<Fq>::deserialize_compressed(bytes.as_slice()) {
+                assert!(
+                    verify(&polynomial, proof).is_err(),
+                    "flipping bit {} was accepted",
+                    bit
+                );
+            }
+        });
+    }
+
+    #[test]
+    fn random_proofs_are_rejected() {
+        check_property(16, |rng| {
+            let polynomial = arbitrary_poly::<Fq>(rng);
+
+            assert!(verify(&polynomial, arbitrary_proof(rng)).is_err());
+        });
+    }
+
+    #[test]
+    fn random_sum_polys_stay_within_the_bounds() {
+        check_property(16, |rng| {
+            let sum_poly = arbitrary_sum_poly::<Fq>(rng);
+
+            assert!((1..=MAX_VARS).contains(&sum_poly.num_vars().unwrap()));
+            assert!((1..=3).contains(&sum_poly.get_degree()));
+        });
+    }
+}
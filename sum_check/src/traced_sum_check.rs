@@ -0,0 +1,200 @@
+use ark_ff::PrimeField;
+use fiat_shamir::fiat_shamir_transcript::{fq_vec_to_bytes, Transcript};
+use multilinear_polynomial::multilinear_polynomial_evaluation::MultilinearPoly;
+use serde::{Serialize, Serializer};
+
+use crate::{
+    interactive_sum_check::{ChallengeSource, SumCheckProver, SumCheckVerifier},
+    sum_check_error::SumCheckError,
+    sum_check_protocol::{append_statement, compute_sum, verify_final_evaluation, Proof},
+};
+
+/// One round as seen by one side of the protocol. `next_sum` is the claim
+/// carried into the next round: the prover's sum of its folded table, or the
+/// verifier's evaluation of the round polynomial at the challenge. An honest
+/// run produces identical traces on both sides.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct RoundTrace<F: PrimeField> {
+    pub round: usize,
+    #[serde(serialize_with = "serialize_fields")]
+    pub round_polynomial: Vec<F>,
+    #[serde(serialize_with = "serialize_field")]
+    pub challenge: F,
+    #[serde(serialize_with = "serialize_field")]
+    pub next_sum: F,
+}
+
+fn serialize_field<F: PrimeField, S: Serializer>(
+    value: &F,
+    serializer: S,
+) -> Result<S::Ok, S::Error> {
+    serializer.serialize_str(&value.to_string())
+}
+
+fn serialize_fields<F: PrimeField, S: Serializer>(
+    values: &[F],
+    serializer: S,
+) -> Result<S::Ok, S::Error> {
+    serializer.collect_seq(values.iter().map(|value| value.to_string()))
+}
+
+/// Same proof as `prove`, along with the prover's view of every round.
+pub fn prove_with_trace<F: PrimeField>(
+    polynomial: &MultilinearPoly<F>,
+) -> (Proof<F>, Vec<RoundTrace<F>>) {
+    let claimed_sum = compute_sum(polynomial);
+
+    let mut transcript = Transcript::<F>::new();
+    transcript.append(&polynomial.digest());
+    append_statement(&mut transcript, polynomial.num_vars(), claimed_sum);
+
+    let mut prover = SumCheckProver::new(polynomial);
+    let mut proof_polynomials = Vec::with_capacity(polynomial.num_vars());
+    let mut trace = Vec::with_capacity(polynomial.num_vars());
+
+    for round in 0..polynomial.num_vars() {
+        let round_polynomial = prover.round_message();
+        let challenge = transcript.next_challenge(&round_polynomial);
+
+        prover.receive_challenge(challenge);
+
+        trace.push(RoundTrace {
+            round,
+            round_polynomial: round_polynomial.clone(),
+            challenge,
+            next_sum: prover.current_sum(),
+        });
+        proof_polynomials.push(round_polynomial);
+    }
+
+    let final_evaluation = prover.final_evaluation();
+    transcript.append(&fq_vec_to_bytes(&[final_evaluation]));
+
+    let proof = Proof {
+        proof_polynomials,
+        claimed_sum,
+        final_evaluation,
+    };
+
+    (proof, trace)
+}
+
+/// Same result as `verify`, along with the verifier's view of every round it
+/// accepted. On failure the trace ends at the last round that passed.
+pub fn verify_with_trace<F: PrimeField>(
+    polynomial: &MultilinearPoly<F>,
+    proof: Proof<F>,
+) -> (Result<(), SumCheckError<F>>, Vec<RoundTrace<F>>) {
+    let mut trace = Vec::with_capacity(proof.proof_polynomials.len());
+    let result = verify_traced(polynomial, proof, &mut trace);
+
+    (result, trace)
+}
+
+fn verify_traced<F: PrimeField>(
+    polynomial: &MultilinearPoly<F>,
+    proof: Proof<F>,
+    trace: &mut Vec<RoundTrace<F>>,
+) -> Result<(), SumCheckError<F>> {
+    let num_vars = polynomial.num_vars();
+
+    let mut transcript = Transcript::<F>::new();
+    transcript.append(&polynomial.digest());
+    append_statement(&mut transcript, num_vars, proof.claimed_sum);
+
+    if proof.proof_polynomials.len() != num_vars {
+        return Err(SumCheckError::WrongNumberOfRounds {
+            expected: num_vars,
+            got: proof.proof_polynomials.len(),
+        });
+    }
+
+    let mut verifier = SumCheckVerifier::new(proof.claimed_sum, num_vars, &mut transcript);
+
+    for (round, round_polynomial) in proof.proof_polynomials.iter().enumerate() {
+        let challenge = verifier.receive_message(round_polynomial)?;
+
+        trace.push(RoundTrace {
+            round,
+            round_polynomial: round_polynomial.clone(),
+            challenge,
+            next_sum: verifier.expected_sum(),
+        });
+    }
+
+    let sub_claim = verifier.into_sub_claim()?;
+    let sub_claim = verify_final_evaluation(sub_claim, proof.final_evaluation, &mut transcript)?;
+
+    let oracle_eval = polynomial.evaluate(&sub_claim.point);
+    if sub_claim.value != oracle_eval {
+        return Err(SumCheckError::FinalEvaluationMismatch {
+            expected: sub_claim.value,
+            got: oracle_eval,
+        });
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod test {
+    use ark_bn254::Fq;
+    use multilinear_polynomial::multilinear_polynomial_evaluation::MultilinearPoly;
+
+    use super::{prove_with_trace, verify_with_trace};
+    use crate::{
+        sum_check_error::SumCheckError,
+        sum_check_protocol::{prove, verify},
+        test_utils::sample_poly,
+    };
+
+    #[test]
+    fn it_produces_matching_traces_for_an_honest_proof() {
+        let polynomial = sample_poly();
+
+        let (proof, prover_trace) = prove_with_trace(&polynomial);
+
+        assert_eq!(
+            proof.proof_polynomials,
+            prove(&polynomial).proof_polynomials
+        );
+        assert!(verify(&polynomial, proof.clone()).is_ok());
+
+        let (result, verifier_trace) = verify_with_trace(&polynomial, proof);
+
+        assert!(result.is_ok());
+        assert_eq!(prover_trace.len(), 3);
+        assert_eq!(verifier_trace, prover_trace);
+    }
+
+    #[test]
+    fn it_stops_the_trace_at_the_failing_round() {
+        let polynomial = sample_poly();
+
+        let (mut proof, _) = prove_with_trace(&polynomial);
+        proof.proof_polynomials[1][0] += Fq::from(1);
+
+        let (result, verifier_trace) = verify_with_trace(&polynomial, proof);
+
+        assert!(matches!(
+            result,
+            Err(SumCheckError::RoundSumMismatch { round: 1, .. })
+        ));
+        assert_eq!(verifier_trace.len(), 1);
+    }
+
+    #[test]
+    fn it_serializes_the_trace_to_json() {
+        let polynomial = MultilinearPoly::new(vec![Fq::from(3), Fq::from(5)]);
+
+        let (_, trace) = prove_with_trace(&polynomial);
+        let json = serde_json::to_value(&trace).unwrap();
+
+        assert_eq!(json[0]["round"], 0);
+        assert_eq!(json[0]["round_polynomial"], serde_json::json!(["3", "5"]));
+        assert_eq!(
+            json[0]["challenge"],
+            serde_json::json!(trace[0].challenge.to_string())
+        );
+    }
+}
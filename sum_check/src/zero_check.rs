@@ -0,0 +1,202 @@
+use ark_ff::PrimeField;
+use fiat_shamir::fiat_shamir_transcript::Transcript;
+use multilinear_polynomial::{
+    evaluation_claim::EvaluationClaim,
+    multilinear_polynomial_evaluation::{eq_eval, MultilinearPoly},
+};
+
+use crate::{
+    product_sum_check::{
+        prove_product_with_transcript, verify_product_partial_with_transcript, ProductProof,
+    },
+    sum_check_error::SumCheckError,
+};
+
+/// Proves `f` vanishes on the whole hypercube by sum-checking `f(x)·eq(x, r)`
+/// to zero for a random `r`. That sum is `f(r)`, so a nonzero `f` only
+/// passes if `r` happens to be one of its roots.
+///
+/// The transcript absorbs `f.digest()` before `r` is drawn, so the caller
+/// only has to bind whatever came before.
+pub fn prove_zero_check<F: PrimeField>(
+    f: &MultilinearPoly<F>,
+    transcript: &mut Transcript<F>,
+) -> ProductProof<F> {
+    transcript.append_with_label(b"zero_check", &f.digest());
+
+    prove_vanishing(f, transcript)
+}
+
+/// Checks a `prove_zero_check` proof against the digest of `f`. The `eq`
+/// factor's claim is checked here; the returned claim on `f` is left to the
+/// caller, e.g. against a commitment opening.
+pub fn verify_zero_check<F: PrimeField>(
+    digest: &[u8; 32],
+    num_vars: usize,
+    proof: ProductProof<F>,
+    transcript: &mut Transcript<F>,
+) -> Result<EvaluationClaim<F>, SumCheckError<F>> {
+    transcript.append_with_label(b"zero_check", digest);
+
+    verify_vanishing(num_vars, proof, transcript)
+}
+
+/// Proves `f` and `g` agree on the hypercube, as the zero-check of `f - g`.
+/// Panics if they have different numbers of variables.
+pub fn prove_equal<F: PrimeField>(
+    f: &MultilinearPoly<F>,
+    g: &MultilinearPoly<F>,
+    transcript: &mut Transcript<F>,
+) -> ProductProof<F> {
+    append_equality_statement(&f.digest(), &g.digest(), transcript);
+
+    prove_vanishing(&(f.clone() - g.clone()), transcript)
+}
+
+/// Checks a `prove_equal` proof. The returned claim is on `f - g`: the
+/// caller must check `f(point) - g(point) == value`.
+pub fn verify_equal<F: PrimeField>(
+    f_digest: &[u8; 32],
+    g_digest: &[u8; 32],
+    num_vars: usize,
+    proof: ProductProof<F>,
+    transcript: &mut Transcript<F>,
+) -> Result<EvaluationClaim<F>, SumCheckError<F>> {
+    append_equality_statement(f_digest, g_digest, transcript);
+
+    verify_vanishing(num_vars, proof, transcript)
+}
+
+fn append_equality_statement<F: PrimeField>(
+    f_digest: &[u8; 32],
+    g_digest: &[u8; 32],
+    transcript: &mut Transcript<F>,
+) {
+    transcript.append_with_label(b"equality_check", &[*f_digest, *g_digest].concat());
+}
+
+fn prove_vanishing<F: PrimeField>(
+    f: &MultilinearPoly<F>,
+    transcript: &mut Transcript<F>,
+) -> ProductProof<F> {
+    let r = sample_point(transcript, f.num_vars());
+
+    prove_product_with_transcript(f, &MultilinearPoly::eq_mle(&r), transcript)
+        .expect("eq ranges over the variables of f")
+}
+
+fn verify_vanishing<F: PrimeField>(
+    num_vars: usize,
+    proof: ProductProof<F>,
+    transcript: &mut Transcript<F>,
+) -> Result<EvaluationClaim<F>, SumCheckError<F>> {
+    let r = sample_point(transcript, num_vars);
+
+    if !proof.claimed_sum.is_zero() {
+        return Err(SumCheckError::ClaimMismatch {
+            expected: F::zero(),
+            got: proof.claimed_sum,
+        });
+    }
+
+    let sub_claim = verify_product_partial_with_transcript(num_vars, proof, transcript)?;
+
+    // The verifier evaluates eq(point, r) itself in O(n).
+    let eq_at_point = eq_eval(&sub_claim.point, &r);
+    if sub_claim.g_eval != eq_at_point {
+        return Err(SumCheckError::FinalEvaluationMismatch {
+            expected: eq_at_point,
+            got: sub_claim.g_eval,
+        });
+    }
+
+    Ok(EvaluationClaim {
+        point: sub_claim.point,
+        value: sub_claim.f_eval,
+    })
+}
+
+fn sample_point<F: PrimeField>(transcript: &mut Transcript<F>, num_vars: usize) -> Vec<F> {
+    (0..num_vars)
+        .map(|_| transcript.get_uniform_challenge())
+        .collect()
+}
+
+#[cfg(test)]
+mod test {
+    use ark_bn254::Fq;
+    use fiat_shamir::fiat_shamir_transcript::Transcript;
+    use multilinear_polynomial::multilinear_polynomial_evaluation::MultilinearPoly;
+    use rand::{rngs::StdRng, SeedableRng};
+
+    use super::{prove_equal, prove_zero_check, verify_equal, verify_zero_check};
+    use crate::sum_check_error::SumCheckError;
+
+    #[test]
+    fn a_zero_polynomial_passes() {
+        let f = MultilinearPoly::<Fq>::zero(5);
+
+        let proof = prove_zero_check(&f, &mut Transcript::new());
+        assert_eq!(proof.proof_polynomials.len(), 5);
+        assert!(proof.proof_polynomials.iter().all(|poly| poly.len() == 3));
+
+        let sub_claim = verify_zero_check(&f.digest(), 5, proof, &mut Transcript::new()).unwrap();
+        assert_eq!(f.evaluate(&sub_claim.point), sub_claim.value);
+    }
+
+    #[test]
+    fn a_polynomial_nonzero_at_one_point_fails() {
+        let mut evaluations = vec![Fq::from(0); 32];
+        evaluations[19] = Fq::from(1);
+        let f = MultilinearPoly::new(evaluations);
+
+        // The honest proof claims the true sum, which is not zero.
+        let proof = prove_zero_check(&f, &mut Transcript::new());
+        assert!(matches!(
+            verify_zero_check(&f.digest(), 5, proof.clone(), &mut Transcript::new()),
+            Err(SumCheckError::ClaimMismatch { .. })
+        ));
+
+        // Claiming zero anyway breaks the first round.
+        let mut forged = proof;
+        forged.claimed_sum = Fq::from(0);
+        assert!(verify_zero_check(&f.digest(), 5, forged, &mut Transcript::new()).is_err());
+    }
+
+    #[test]
+    fn a_wrong_eq_claim_is_rejected() {
+        let f = MultilinearPoly::<Fq>::zero(3);
+
+        let mut proof = prove_zero_check(&f, &mut Transcript::new());
+        proof.f_evaluation = Fq::from(1);
+        proof.g_evaluation = Fq::from(0);
+
+        assert!(verify_zero_check(&f.digest(), 3, proof, &mut Transcript::new()).is_err());
+    }
+
+    #[test]
+    fn it_proves_two_tables_equal() {
+        let mut rng = StdRng::seed_from_u64(79);
+        let f = MultilinearPoly::<Fq>::rand(4, &mut rng);
+        let g = f.clone();
+
+        let proof = prove_equal(&f, &g, &mut Transcript::new());
+        let sub_claim =
+            verify_equal(&f.digest(), &g.digest(), 4, proof, &mut Transcript::new()).unwrap();
+        assert_eq!(
+            f.evaluate(&sub_claim.point) - g.evaluate(&sub_claim.point),
+            sub_claim.value
+        );
+
+        let other = MultilinearPoly::<Fq>::rand(4, &mut rng);
+        let proof = prove_equal(&f, &other, &mut Transcript::new());
+        assert!(verify_equal(
+            &f.digest(),
+            &other.digest(),
+            4,
+            proof,
+            &mut Transcript::new()
+        )
+        .is_err());
+    }
+}
@@ -0,0 +1,178 @@
+use ark_ff::PrimeField;
+use fiat_shamir::fiat_shamir_transcript::{fq_vec_to_bytes, Transcript};
+use multilinear_polynomial::{
+    evaluation_claim::EvaluationClaim, multilinear_polynomial_evaluation::MultilinearPoly,
+};
+use rand::{rngs::StdRng, SeedableRng};
+
+use crate::{
+    sum_check_error::SumCheckError,
+    sum_check_protocol::{prove_rounds, verify_rounds},
+};
+
+/// Sum-check over `f + ρ·g` for a random masking multilinear `g`. The round
+/// polynomials are blinded by `g`, and the verifier adjusts the claim using
+/// the revealed `mask_sum`.
+#[derive(Debug, Clone)]
+pub struct ZkProof<F: PrimeField> {
+    pub proof_polynomials: Vec<Vec<F>>,
+    pub claimed_sum: F,
+    pub mask_commitment: [u8; 32],
+    pub mask_sum: F,
+    pub mask_evaluation: F,
+}
+
+pub fn prove_zk<F: PrimeField>(polynomial: &MultilinearPoly<F>) -> ZkProof<F> {
+    let mut rng = StdRng::from_entropy();
+
+    let mask = MultilinearPoly::new((0..polynomial.len()).map(|_| F::rand(&mut rng)).collect());
+
+    let claimed_sum = polynomial.hypercube_sum();
+    let mask_sum = mask.hypercube_sum();
+    let mask_commitment = mask.digest();
+
+    let mut transcript = Transcript::<F>::new();
+    let rho = append_zk_statement(
+        &mut transcript,
+        polynomial,
+        claimed_sum,
+        &mask_commitment,
+        mask_sum,
+    );
+
+    let masked_poly = polynomial.clone() + mask.scale(rho);
+
+    let (proof_polynomials, random_challenges, _) = prove_rounds(&masked_poly, &mut transcript);
+
+    ZkProof {
+        proof_polynomials,
+        claimed_sum,
+        mask_commitment,
+        mask_sum,
+        mask_evaluation: mask.evaluate(&random_challenges),
+    }
+}
+
+/// Verifies a zero-knowledge proof about `polynomial`.
+///
+/// On success the returned sub-claim states that the mask evaluates to
+/// `proof.mask_evaluation` at the sampled point; the caller must discharge it
+/// against `proof.mask_commitment` with an opening.
+pub fn verify_zk<F: PrimeField>(
+    polynomial: &MultilinearPoly<F>,
+    proof: ZkProof<F>,
+) -> Result<EvaluationClaim<F>, SumCheckError<F>> {
+    let mut transcript = Transcript::<F>::new();
+    let rho = append_zk_statement(
+        &mut transcript,
+        polynomial,
+        proof.claimed_sum,
+        &proof.mask_commitment,
+        proof.mask_sum,
+    );
+
+    let sub_claim = verify_rounds(
+        proof.claimed_sum + rho * proof.mask_sum,
+        polynomial.num_vars(),
+        &proof.proof_polynomials,
+        &mut transcript,
+    )?;
+
+    let masked_eval = polynomial.evaluate(&sub_claim.point) + rho * proof.mask_evaluation;
+    if masked_eval != sub_claim.value {
+        return Err(SumCheckError::FinalEvaluationMismatch {
+            expected: sub_claim.value,
+            got: masked_eval,
+        });
+    }
+
+    Ok(EvaluationClaim {
+        point: sub_claim.point,
+        value: proof.mask_evaluation,
+    })
+}
+
+/// Absorbs the polynomial's digest before the statement, as `prove` does,
+/// so that `ρ` and every challenge after it depend on the polynomial.
+fn append_zk_statement<F: PrimeField>(
+    transcript: &mut Transcript<F>,
+    polynomial: &MultilinearPoly<F>,
+    claimed_sum: F,
+    mask_commitment: &[u8; 32],
+    mask_sum: F,
+) -> F {
+    transcript.append(&polynomial.digest());
+    transcript.append(&fq_vec_to_bytes(&[
+        F::from(polynomial.num_vars() as u64),
+        claimed_sum,
+    ]));
+    transcript.append(mask_commitment);
+    transcript.append(&fq_vec_to_bytes(&[mask_sum]));
+
+    transcript.get_uniform_challenge()
+}
+
+#[cfg(test)]
+mod test {
+    use ark_bn254::Fq;
+    use fiat_shamir::fiat_shamir_transcript::Transcript;
+    use multilinear_polynomial::multilinear_polynomial_evaluation::MultilinearPoly;
+
+    use super::{append_zk_statement, prove_zk, verify_zk};
+    use crate::{sum_check_error::SumCheckError, test_utils::sample_poly};
+
+    #[test]
+    fn it_proves_and_verifies_with_a_mask() {
+        let polynomial = sample_poly();
+
+        let proof = prove_zk(&polynomial);
+
+        assert_eq!(proof.claimed_sum, Fq::from(29));
+        assert!(verify_zk(&polynomial, proof).is_ok());
+    }
+
+    #[test]
+    fn it_produces_different_round_polynomials_each_time() {
+        let polynomial = sample_poly();
+
+        let proof_1 = prove_zk(&polynomial);
+        let proof_2 = prove_zk(&polynomial);
+
+        assert_ne!(proof_1.proof_polynomials, proof_2.proof_polynomials);
+        assert_ne!(proof_1.mask_commitment, proof_2.mask_commitment);
+    }
+
+    #[test]
+    fn it_rejects_a_wrong_claimed_sum() {
+        let polynomial = sample_poly();
+
+        let mut proof = prove_zk(&polynomial);
+        proof.claimed_sum += Fq::from(1);
+
+        assert!(matches!(
+            verify_zk(&polynomial, proof),
+            Err(SumCheckError::RoundSumMismatch { round: 0, .. })
+        ));
+    }
+
+    #[test]
+    fn the_mask_coefficient_depends_on_the_polynomial() {
+        let polynomial = sample_poly();
+        // The same variables and sum, so only the digest tells them apart.
+        let mut table = vec![Fq::from(0); 8];
+        table[0] = Fq::from(29);
+        let other = MultilinearPoly::new(table);
+
+        let rho = |polynomial: &MultilinearPoly<Fq>| {
+            append_zk_statement(
+                &mut Transcript::new(),
+                polynomial,
+                Fq::from(29),
+                &[0; 32],
+                Fq::from(0),
+            )
+        };
+
+        assert_ne!(rho(&polynomial), rho(&other));
+    }
+}
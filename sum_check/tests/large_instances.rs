@@ -0,0 +1,60 @@
+//! Smoke tests at 2^22 evaluations, run with `--features expensive-tests`
+//! and preferably `--release`.
+#![cfg(feature = "expensive-tests")]
+
+use ark_bn254::Fq;
+use fiat_shamir::fiat_shamir_transcript::Transcript;
+use multilinear_polynomial::{
+    composed_polynomial::{ProductPoly, SumPoly},
+    multilinear_polynomial_evaluation::MultilinearPoly,
+};
+use rand::{rngs::StdRng, SeedableRng};
+use sum_check::sum_check_protocol::{
+    gkr_prove, gkr_verify_final, gkr_verify_rounds, prove, verify,
+};
+
+const NUM_VARS: usize = 22;
+
+#[test]
+fn it_proves_and_verifies_a_large_table() {
+    let mut rng = StdRng::seed_from_u64(85);
+    let polynomial = MultilinearPoly::<Fq>::rand(NUM_VARS, &mut rng);
+
+    let proof = prove(&polynomial);
+    assert_eq!(proof.num_rounds(), NUM_VARS);
+    assert_eq!(proof.claimed_sum(), polynomial.hypercube_sum());
+    assert_eq!(verify(&polynomial, proof), Ok(()));
+}
+
+#[test]
+fn it_runs_a_large_gkr_layer_round() {
+    let mut rng = StdRng::seed_from_u64(86);
+    let factor = |rng: &mut StdRng| MultilinearPoly::<Fq>::rand(NUM_VARS, rng).into_evaluations();
+    let composed_polynomial = SumPoly::new(vec![ProductPoly::new(vec![
+        factor(&mut rng),
+        factor(&mut rng),
+    ])]);
+    let next_layer = MultilinearPoly::rand(NUM_VARS / 2, &mut rng);
+    let claimed_sum: Fq = composed_polynomial.reduce().hypercube_sum();
+
+    let proof = gkr_prove(
+        claimed_sum,
+        &composed_polynomial,
+        &next_layer,
+        &mut Transcript::new(),
+        None,
+    )
+    .unwrap();
+
+    let result = gkr_verify_rounds(
+        proof.proof_polynomials,
+        claimed_sum,
+        2,
+        &mut Transcript::new(),
+        None,
+    )
+    .unwrap();
+    let oracle_eval = composed_polynomial.evaluate(&result.point);
+
+    assert_eq!(gkr_verify_final(result.value, oracle_eval), Ok(()));
+}
@@ -0,0 +1,78 @@
+//! The `parallel` feature only changes how the prover's work is split, never
+//! what it sends. Run with and without `--features parallel`: both must
+//! match the same recorded fingerprint.
+
+use std::str::FromStr;
+
+use ark_bn254::Fq;
+use fiat_shamir::fiat_shamir_transcript::{fq_vec_to_bytes, Transcript};
+use multilinear_polynomial::{
+    composed_polynomial::{Factor, ProductPoly, SumPoly},
+    multilinear_polynomial_evaluation::MultilinearPoly,
+    sparse_multilinear_polynomial::SparseMultilinearPoly,
+};
+use rand::{rngs::StdRng, SeedableRng};
+use sum_check::sum_check_protocol::{gkr_prove, GkrProof};
+
+/// A layer with dense, sparse and zero terms, so every path of `reduce` and
+/// `partial_evaluate` is taken.
+fn layer(rng: &mut StdRng) -> SumPoly<Fq> {
+    let [a, b, c] = [(); 3].map(|_| MultilinearPoly::<Fq>::rand(10, rng));
+    let wiring = SparseMultilinearPoly::new(
+        10,
+        (0..1024)
+            .step_by(37)
+            .map(|index| (index, Fq::from(index as u64 + 1)))
+            .collect(),
+    );
+
+    SumPoly::new(vec![
+        ProductPoly::from_factors(vec![Factor::Dense(a.clone()), Factor::Dense(b.clone())]),
+        ProductPoly::from_factors(vec![
+            Factor::Sparse(wiring),
+            Factor::Dense(b),
+            Factor::Dense(c.clone()),
+        ]),
+        ProductPoly::from_factors(vec![
+            Factor::Dense(MultilinearPoly::zero(10)),
+            Factor::Dense(a),
+            Factor::Dense(c),
+        ]),
+    ])
+}
+
+/// Every field element of the proof, absorbed into a fresh transcript.
+fn fingerprint(proof: &GkrProof<Fq>) -> Fq {
+    let mut transcript = Transcript::<Fq>::new();
+    for poly in &proof.proof_polynomials {
+        transcript.append(&fq_vec_to_bytes(poly));
+    }
+    transcript.append(&fq_vec_to_bytes(&[proof.claimed_sum, proof.w_b, proof.w_c]));
+
+    transcript.get_uniform_challenge()
+}
+
+#[test]
+fn the_gkr_proof_does_not_depend_on_the_parallel_feature() {
+    let mut rng = StdRng::seed_from_u64(96);
+    let composed_polynomial = layer(&mut rng);
+    let next_layer = MultilinearPoly::rand(5, &mut rng);
+    let claimed_sum: Fq = composed_polynomial.reduce().hypercube_sum();
+
+    let proof = gkr_prove(
+        claimed_sum,
+        &composed_polynomial,
+        &next_layer,
+        &mut Transcript::new(),
+        None,
+    )
+    .unwrap();
+
+    assert_eq!(
+        fingerprint(&proof),
+        Fq::from_str(
+            "2138677041161846418678873145251234134135259110240139855554616549085260321461"
+        )
+        .unwrap()
+    );
+}
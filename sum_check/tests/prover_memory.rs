@@ -0,0 +1,96 @@
+use std::{
+    alloc::{GlobalAlloc, Layout, System},
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Mutex,
+    },
+};
+
+use ark_bn254::Fq;
+use multilinear_polynomial::multilinear_polynomial_evaluation::MultilinearPoly;
+use sum_check::sum_check_protocol::prove;
+
+/// Tracks the number of live heap bytes and the peak reached since the last
+/// `reset_peak`, and counts the allocations of at least `LARGE` bytes.
+struct PeakAllocator;
+
+static CURRENT: AtomicUsize = AtomicUsize::new(0);
+static PEAK: AtomicUsize = AtomicUsize::new(0);
+static LARGE_ALLOCATIONS: AtomicUsize = AtomicUsize::new(0);
+
+const LARGE: usize = 4096;
+
+/// The counters are global, so the tests take turns.
+static SERIAL: Mutex<()> = Mutex::new(());
+
+unsafe impl GlobalAlloc for PeakAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        let ptr = System.alloc(layout);
+
+        if !ptr.is_null() {
+            let current = CURRENT.fetch_add(layout.size(), Ordering::SeqCst) + layout.size();
+            PEAK.fetch_max(current, Ordering::SeqCst);
+
+            if layout.size() >= LARGE {
+                LARGE_ALLOCATIONS.fetch_add(1, Ordering::SeqCst);
+            }
+        }
+
+        ptr
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout);
+        CURRENT.fetch_sub(layout.size(), Ordering::SeqCst);
+    }
+}
+
+#[global_allocator]
+static ALLOCATOR: PeakAllocator = PeakAllocator;
+
+fn reset_peak() -> usize {
+    let current = CURRENT.load(Ordering::SeqCst);
+    PEAK.store(current, Ordering::SeqCst);
+
+    current
+}
+
+#[test]
+fn prover_peak_memory_is_one_table_copy() {
+    let _serial = SERIAL.lock().unwrap();
+    let num_vars = 20;
+    let evaluations: Vec<Fq> = (0..1u64 << num_vars).map(Fq::from).collect();
+    let poly = MultilinearPoly::new(evaluations);
+    let table_bytes = poly.len() * std::mem::size_of::<Fq>();
+
+    let baseline = reset_peak();
+    let proof = prove(&poly);
+    let peak_extra = PEAK.load(Ordering::SeqCst) - baseline;
+
+    // The prover clones the table once and folds that copy in place; folding
+    // into fresh half-size tables would push this to 1.5 tables.
+    assert!(
+        peak_extra < table_bytes + table_bytes / 8,
+        "prover used {} extra bytes for a {} byte table",
+        peak_extra,
+        table_bytes
+    );
+    assert_eq!(proof.num_rounds(), num_vars);
+}
+
+#[test]
+fn prover_rounds_allocate_nothing_table_sized() {
+    let _serial = SERIAL.lock().unwrap();
+
+    let num_vars = 16;
+    let poly = MultilinearPoly::new((0..1u64 << num_vars).map(Fq::from).collect());
+
+    let before = LARGE_ALLOCATIONS.load(Ordering::SeqCst);
+    let proof = prove(&poly);
+    let large = LARGE_ALLOCATIONS.load(Ordering::SeqCst) - before;
+
+    // Only the prover's working copy of the table; every round folds it in
+    // place and the rest is O(1) per round.
+    assert_eq!(large, 1);
+    assert_eq!(proof.num_rounds(), num_vars);
+}
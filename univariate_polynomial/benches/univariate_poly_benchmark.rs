@@ -38,7 +38,7 @@ pub fn interpolation_benchmark(c: &mut Criterion) {
     c.bench_function("UnivariatePoly interpolate", |b| {
         b.iter(|| {
             // Clone points to ensure each iteration works on an identical input.
-            let poly = UnivariatePoly::interpolate(black_box(points.clone()));
+            let poly = UnivariatePoly::interpolate(black_box(points.clone())).unwrap();
             black_box(poly);
         })
     });
@@ -0,0 +1,99 @@
+use ark_ff::{batch_inversion, PrimeField};
+
+/// The domain `{0, 1, ..., d}` with its barycentric weights precomputed, for
+/// evaluating a polynomial of degree at most `d` from its values there
+/// without interpolating it.
+#[derive(Debug, Clone, PartialEq)]
+pub struct BarycentricDomain<F: PrimeField> {
+    weights: Vec<F>,
+}
+
+impl<F: PrimeField> BarycentricDomain<F> {
+    pub fn new(degree: usize) -> Self {
+        // w_i = 1 / Π_{j != i} (i - j) = (-1)^(d - i) / (i! (d - i)!).
+        let mut factorials = vec![F::one(); degree + 1];
+        for i in 1..=degree {
+            factorials[i] = factorials[i - 1] * F::from(i as u64);
+        }
+
+        let mut weights: Vec<F> = (0..=degree)
+            .map(|i| {
+                let denominator = factorials[i] * factorials[degree - i];
+
+                if (degree - i).is_multiple_of(2) {
+                    denominator
+                } else {
+                    -denominator
+                }
+            })
+            .collect();
+        batch_inversion(&mut weights);
+
+        Self { weights }
+    }
+
+    /// The number of points in the domain, `d + 1`.
+    pub fn size(&self) -> usize {
+        self.weights.len()
+    }
+
+    /// Evaluates at `x` the polynomial taking `evals[i]` at `i`.
+    pub fn evaluate(&self, evals: &[F], x: F) -> F {
+        if evals.len() != self.size() {
+            panic!("expected {} evaluations, got {}", self.size(), evals.len());
+        }
+
+        let mut differences: Vec<F> = (0..self.size()).map(|i| x - F::from(i as u64)).collect();
+
+        if let Some(i) = differences
+            .iter()
+            .position(|difference| difference.is_zero())
+        {
+            return evals[i];
+        }
+
+        let vanishing: F = differences.iter().product();
+        batch_inversion(&mut differences);
+
+        let sum: F = self
+            .weights
+            .iter()
+            .zip(differences.iter())
+            .zip(evals.iter())
+            .map(|((weight, inverse), eval)| *weight * inverse * eval)
+            .sum();
+
+        vanishing * sum
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use ark_bn254::Fq;
+
+    use super::BarycentricDomain;
+    use crate::univariate_polynomial_dense::UnivariatePoly;
+
+    #[test]
+    fn it_matches_interpolation() {
+        let evals = vec![Fq::from(3), Fq::from(9), Fq::from(4), Fq::from(20)];
+        let domain = BarycentricDomain::new(3);
+
+        let points = evals
+            .iter()
+            .enumerate()
+            .map(|(i, eval)| (Fq::from(i as u64), *eval))
+            .collect();
+        let poly = UnivariatePoly::interpolate(points).unwrap();
+
+        for x in [Fq::from(2), Fq::from(7), Fq::from(123456789)] {
+            assert_eq!(domain.evaluate(&evals, x), poly.evaluate(x));
+        }
+    }
+
+    #[test]
+    #[should_panic]
+    fn it_rejects_the_wrong_number_of_evaluations() {
+        let _ = BarycentricDomain::new(2).evaluate(&[Fq::from(1), Fq::from(2)], Fq::from(5));
+    }
+}
@@ -1 +1,2 @@
+pub mod barycentric;
 pub mod univariate_polynomial_dense;
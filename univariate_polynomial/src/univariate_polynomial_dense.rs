@@ -1,6 +1,15 @@
 use ark_ff::PrimeField;
-use std::ops::{Add, Mul};
-
+use std::{
+    fmt,
+    hash::{Hash, Hasher},
+    ops::{Add, Mul},
+};
+
+/// A polynomial by its coefficients, lowest power first.
+///
+/// Trailing zero coefficients do not change the polynomial, so equality and
+/// hashing ignore them: `3 + 0·x` equals `3`, and every spelling of the zero
+/// polynomial, `[]` included, is equal.
 #[derive(Debug, Clone)]
 pub struct UnivariatePoly<F: PrimeField> {
     pub coefficient: Vec<F>,
@@ -11,24 +20,43 @@ impl<F: PrimeField> UnivariatePoly<F> {
         UnivariatePoly { coefficient: coeff }
     }
 
-    fn trim(&mut self) {
-        while self.coefficient.last() == Some(&F::zero()) {
-            self.coefficient.pop();
-        }
+    /// The polynomial with these coefficients, lowest power first.
+    pub fn from_u64_coeffs(coeffs: &[u64]) -> Self {
+        UnivariatePoly::new(coeffs.iter().map(|coeff| F::from(*coeff)).collect())
+    }
+
+    /// Drops trailing zero coefficients, leaving the zero polynomial with
+    /// none at all.
+    pub fn trim(&mut self) {
+        let len = self.trimmed().len();
+        self.coefficient.truncate(len);
+    }
+
+    /// The coefficients up to the last nonzero one.
+    fn trimmed(&self) -> &[F] {
+        let len = self
+            .coefficient
+            .iter()
+            .rposition(|coeff| !coeff.is_zero())
+            .map_or(0, |last| last + 1);
+
+        &self.coefficient[..len]
     }
 
     pub fn evaluate(&self, x: F) -> F {
         self.coefficient
             .iter()
             .enumerate()
-            .map(|(index, coeff)| *coeff * x.pow(&[index as u64]))
+            .map(|(index, coeff)| *coeff * x.pow([index as u64]))
             .sum()
     }
 
+    /// The degree after trimming, taking the zero polynomial to have degree
+    /// 0.
     pub fn degree(&mut self) -> usize {
         self.trim();
 
-        self.coefficient.len() - 1
+        self.coefficient.len().saturating_sub(1)
     }
 
     fn scalar_mul(&self, scalar: F) -> Self {
@@ -45,23 +73,27 @@ impl<F: PrimeField> UnivariatePoly<F> {
         poly
     }
 
-    pub fn interpolate(points: Vec<(F, F)>) -> UnivariatePoly<F> {
-        let n = points.len();
+    /// The lowest-degree polynomial through `points`, which must be nonempty
+    /// and have distinct x-coordinates.
+    pub fn interpolate(points: Vec<(F, F)>) -> Result<UnivariatePoly<F>, InterpolationError<F>> {
+        if points.is_empty() {
+            return Err(InterpolationError::EmptyInput);
+        }
+
         let mut result = UnivariatePoly::new(vec![F::zero()]);
 
-        for i in 0..n {
-            let (x_i, y_i) = points[i];
+        for (i, &(x_i, y_i)) in points.iter().enumerate() {
             let mut l_i = UnivariatePoly::new(vec![F::one()]);
 
-            for j in 0..n {
+            for (j, &(x_j, _)) in points.iter().enumerate() {
                 if i != j {
-                    let (x_j, _) = points[j];
-
                     let numerator = UnivariatePoly::new(vec![-x_j, F::one()]);
 
-                    let denominator = x_i - x_j;
+                    let inverse = (x_i - x_j)
+                        .inverse()
+                        .ok_or(InterpolationError::DuplicateX { x: x_i })?;
 
-                    l_i = l_i * numerator.scalar_mul(F::one() / denominator);
+                    l_i = l_i * numerator.scalar_mul(inverse);
                 }
             }
 
@@ -70,7 +102,108 @@ impl<F: PrimeField> UnivariatePoly<F> {
 
         result.trim();
 
-        result
+        Ok(result)
+    }
+}
+
+/// Why a set of points has no interpolating polynomial.
+#[derive(Debug, Clone, PartialEq)]
+pub enum InterpolationError<F: PrimeField> {
+    EmptyInput,
+    DuplicateX { x: F },
+}
+
+impl<F: PrimeField> fmt::Display for InterpolationError<F> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            InterpolationError::EmptyInput => write!(f, "no points to interpolate"),
+            InterpolationError::DuplicateX { x } => {
+                write!(f, "two points share the x-coordinate {}", x)
+            }
+        }
+    }
+}
+
+impl<F: PrimeField> std::error::Error for InterpolationError<F> {}
+
+/// A polynomial printed lowest power first, e.g. `3 - x + 2*x^2`, with
+/// coefficients closer to `p` than to zero as negative numbers. Built by
+/// `UnivariatePoly::display_in`.
+pub struct UnivariateDisplay<'a, F: PrimeField> {
+    polynomial: &'a UnivariatePoly<F>,
+    variable: &'a str,
+}
+
+impl<F: PrimeField> UnivariatePoly<F> {
+    /// The polynomial in terms of `variable`; `{}` on the polynomial itself
+    /// uses `x`.
+    pub fn display_in<'a>(&'a self, variable: &'a str) -> UnivariateDisplay<'a, F> {
+        UnivariateDisplay {
+            polynomial: self,
+            variable,
+        }
+    }
+}
+
+impl<F: PrimeField> fmt::Display for UnivariateDisplay<'_, F> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let terms: Vec<(usize, &F)> = self
+            .polynomial
+            .coefficient
+            .iter()
+            .enumerate()
+            .filter(|(_, coeff)| !coeff.is_zero())
+            .collect();
+
+        if terms.is_empty() {
+            return write!(f, "0");
+        }
+
+        for (position, (power, coeff)) in terms.into_iter().enumerate() {
+            let negative = (-*coeff).into_bigint() < coeff.into_bigint();
+            let magnitude = if negative { -*coeff } else { *coeff };
+
+            match (position, negative) {
+                (0, true) => write!(f, "-")?,
+                (0, false) => {}
+                (_, true) => write!(f, " - ")?,
+                (_, false) => write!(f, " + ")?,
+            }
+
+            let monomial = match power {
+                0 => String::new(),
+                1 => self.variable.to_string(),
+                _ => format!("{}^{}", self.variable, power),
+            };
+
+            match (magnitude.is_one(), monomial.is_empty()) {
+                (true, false) => write!(f, "{}", monomial)?,
+                (_, true) => write!(f, "{}", magnitude)?,
+                (false, false) => write!(f, "{}*{}", magnitude, monomial)?,
+            }
+        }
+
+        Ok(())
+    }
+}
+
+impl<F: PrimeField> fmt::Display for UnivariatePoly<F> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.display_in("x").fmt(f)
+    }
+}
+
+impl<F: PrimeField> PartialEq for UnivariatePoly<F> {
+    fn eq(&self, other: &Self) -> bool {
+        self.trimmed() == other.trimmed()
+    }
+}
+
+impl<F: PrimeField> Eq for UnivariatePoly<F> {}
+
+impl<F: PrimeField> Hash for UnivariatePoly<F> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.trimmed().hash(state);
     }
 }
 
@@ -115,18 +248,15 @@ mod test {
 
     #[test]
     fn it_returns_degree() {
-        let mut poly_1: UnivariatePoly<Fq> = UnivariatePoly {
-            coefficient: vec![Fq::from(3), Fq::from(4), Fq::from(3)],
-        };
+        let mut poly_1 = UnivariatePoly::<Fq>::from_u64_coeffs(&[3, 4, 3]);
 
         assert!(poly_1.degree() == 2);
+        assert!(UnivariatePoly::<Fq>::from_u64_coeffs(&[3, 4, 3, 0]).degree() == 2);
     }
 
     #[test]
     fn it_evaluates_poly() {
-        let poly_1 = UnivariatePoly {
-            coefficient: vec![Fq::from(3), Fq::from(4), Fq::from(3)],
-        };
+        let poly_1 = UnivariatePoly::<Fq>::from_u64_coeffs(&[3, 4, 3]);
 
         assert!(poly_1.evaluate(Fq::from(3)) == Fq::from(42));
     }
@@ -190,8 +320,75 @@ mod test {
             (Fq::from(2), Fq::from(6)),
         ];
 
-        let new_poly = UnivariatePoly::interpolate(points);
+        let new_poly = UnivariatePoly::interpolate(points).unwrap();
 
         assert!(new_poly.coefficient == vec![Fq::from(2), Fq::from(2)]);
     }
+
+    #[test]
+    fn it_interpolates_a_single_point_as_a_constant() {
+        let poly = UnivariatePoly::interpolate(vec![(Fq::from(5), Fq::from(7))]).unwrap();
+
+        assert_eq!(poly, UnivariatePoly::new(vec![Fq::from(7)]));
+    }
+
+    #[test]
+    fn it_rejects_points_it_cannot_interpolate() {
+        assert_eq!(
+            UnivariatePoly::<Fq>::interpolate(vec![]),
+            Err(InterpolationError::EmptyInput)
+        );
+
+        let points = vec![
+            (Fq::from(0), Fq::from(2)),
+            (Fq::from(3), Fq::from(4)),
+            (Fq::from(3), Fq::from(6)),
+        ];
+
+        assert_eq!(
+            UnivariatePoly::interpolate(points),
+            Err(InterpolationError::DuplicateX { x: Fq::from(3) })
+        );
+    }
+
+    #[test]
+    fn it_prints_in_terms_of_its_variable() {
+        let poly = UnivariatePoly::new(vec![Fq::from(3), Fq::from(-1), Fq::from(0), Fq::from(2)]);
+
+        assert_eq!(poly.to_string(), "3 - x + 2*x^3");
+        assert_eq!(poly.display_in("r_0").to_string(), "3 - r_0 + 2*r_0^3");
+        assert_eq!(
+            UnivariatePoly::new(vec![Fq::from(0), Fq::from(-4)]).to_string(),
+            "-4*x"
+        );
+        assert_eq!(UnivariatePoly::<Fq>::new(vec![]).to_string(), "0");
+    }
+
+    #[test]
+    fn equality_ignores_trailing_zeros() {
+        use std::collections::hash_map::DefaultHasher;
+
+        let hash = |poly: &UnivariatePoly<Fq>| {
+            let mut hasher = DefaultHasher::new();
+            poly.hash(&mut hasher);
+            hasher.finish()
+        };
+
+        let poly = UnivariatePoly::<Fq>::from_u64_coeffs(&[3, 4]);
+        let padded = UnivariatePoly::<Fq>::from_u64_coeffs(&[3, 4, 0, 0]);
+        assert_eq!(poly, padded);
+        assert_eq!(hash(&poly), hash(&padded));
+        assert_ne!(poly, UnivariatePoly::from_u64_coeffs(&[3, 4, 1]));
+        assert_ne!(poly, UnivariatePoly::from_u64_coeffs(&[0, 3, 4]));
+
+        let mut zero = UnivariatePoly::<Fq>::from_u64_coeffs(&[0, 0, 0]);
+        assert_eq!(zero, UnivariatePoly::new(vec![]));
+        assert_eq!(hash(&zero), hash(&UnivariatePoly::new(vec![])));
+        assert_eq!(zero.degree(), 0);
+        assert!(zero.coefficient.is_empty());
+
+        let mut padded = padded;
+        padded.trim();
+        assert_eq!(padded.coefficient, poly.coefficient);
+    }
 }